@@ -0,0 +1,378 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::{ fs, io };
+
+use image::{ ImageError, Rgba, RgbaImage };
+use image::imageops::{ self, FilterType };
+
+use common::math::Direction;
+
+use crate::mesh::TextureLayers;
+
+/// Identifies a block texture, by the [looks](common::world::Block::looks) of the
+/// blocks it's drawn on and optionally the only face it's drawn on
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextureKey
+{
+    /// Looks of the blocks drawn with this texture
+    pub looks: String,
+    /// Face drawn with this texture, or `None` for every face without its own
+    pub face: Option<Direction>,
+}
+
+/// Loads the encoded images(ie. PNG files) of block textures
+pub trait TextureLoader
+{
+    /// Get the encoded image of the texture `key`, in any format the `image` crate
+    /// can guess
+    fn load(&self, key: &TextureKey) -> io::Result<Vec<u8>>;
+}
+
+/// Loads textures from the PNG files in a directory, see [TextureKey::file_name]
+#[derive(Debug, Clone)]
+pub struct FsLoader
+{
+    /// Directory the texture files are in
+    root: PathBuf,
+}
+
+/// Every block texture resized to the same square tile and stacked into the layers
+/// of a texture array, see [Atlas::build]
+#[derive(Debug)]
+pub struct Atlas
+{
+    /// Width and height of every layer, in pixels
+    tile_size: u32,
+    /// Mip chain of every layer, from `tile_size` down to one pixel
+    layers: Vec<Vec<RgbaImage>>,
+    /// Layer of every texture key built, including missing ones
+    indices: HashMap<TextureKey, u32>,
+    /// Keys that couldn't be loaded, in the order they were given
+    missing: Vec<MissingTexture>,
+}
+
+/// A texture which couldn't be loaded, and is drawn with [Atlas::MISSING] instead
+#[derive(Debug)]
+pub struct MissingTexture
+{
+    /// Key of the texture
+    pub key: TextureKey,
+    /// Why it couldn't be loaded or decoded
+    pub error: ImageError,
+}
+
+impl TextureKey
+{
+    /// Creates a new `TextureKey` for the blocks that look like `looks`, or only
+    /// their faces pointing towards `face` if any
+    pub fn new(looks: impl Into<String>, face: Option<Direction>) -> Self
+    {
+        Self { looks: looks.into(), face }
+    }
+
+    /// Get the name of the file this texture is loaded from, ie. `"grass.png"` or
+    /// `"grass_up.png"` for only its top face
+    pub fn file_name(&self) -> String
+    {
+        match self.face
+        {
+            Some(face) => format!("{}_{}.png", self.looks, face.as_str()),
+            None => format!("{}.png", self.looks),
+        }
+    }
+}
+
+impl FsLoader
+{
+    /// Creates a new `FsLoader` of the textures in the directory `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self
+    {
+        Self { root: root.into() }
+    }
+}
+
+impl TextureLoader for FsLoader
+{
+    fn load(&self, key: &TextureKey) -> io::Result<Vec<u8>>
+    {
+        fs::read(self.root.join(key.file_name()))
+    }
+}
+
+impl Atlas
+{
+    /// Layer of the magenta and black checker drawn in place of missing textures
+    pub const MISSING: u32 = TextureLayers::MISSING;
+
+    /// Load the textures `keys` from `loader` into layers of `tile_size` pixels,
+    /// after [Atlas::MISSING], in order and skipping duplicates. Textures of another
+    /// size are stretched to fit(nearest neighbor, to keep pixel art crisp), and
+    /// those that can't be loaded are drawn as [Atlas::MISSING] and reported in
+    /// [Atlas::missing]. Panics if `tile_size` is zero.
+    pub fn build(keys: impl IntoIterator<Item = TextureKey>, loader: &dyn TextureLoader, tile_size: u32) -> Self
+    {
+        assert!(tile_size > 0, "atlas tiles can't be empty");
+
+        let mut atlas = Self
+        {
+            tile_size,
+            layers: vec![mip_chain(checker(tile_size))],
+            indices: HashMap::new(),
+            missing: Vec::new(),
+        };
+        for key in keys
+        {
+            if atlas.indices.contains_key(&key)
+            {
+                continue
+            }
+            let image = loader
+                .load(&key)
+                .map_err(ImageError::from)
+                .and_then(|bytes| image::load_from_memory(&bytes));
+
+            let layer = match image
+            {
+                Ok(image) =>
+                {
+                    let image = imageops::resize(&image.into_rgba8(), tile_size, tile_size, FilterType::Nearest);
+
+                    atlas.layers.push(mip_chain(image));
+                    atlas.layers.len() as u32 - 1
+                },
+                Err(error) =>
+                {
+                    atlas.missing.push(MissingTexture { key: key.clone(), error });
+                    Self::MISSING
+                },
+            };
+            atlas.indices.insert(key, layer);
+        }
+        atlas
+    }
+
+    /// Get the width and height of every layer's first mip level, in pixels
+    pub fn tile_size(&self) -> u32
+    {
+        self.tile_size
+    }
+
+    /// Get the number of layers in this atlas, including [Atlas::MISSING]
+    pub fn layer_count(&self) -> u32
+    {
+        self.layers.len() as u32
+    }
+
+    /// Get the number of mip levels of every layer, down to one pixel
+    pub fn mip_level_count(&self) -> u32
+    {
+        self.layers[0].len() as u32
+    }
+
+    /// Get the mip chain of the layer `layer`, from largest to smallest. Panics
+    /// if there's no such layer.
+    pub fn mips(&self, layer: u32) -> &[RgbaImage]
+    {
+        &self.layers[layer as usize]
+    }
+
+    /// Get the layer of every texture key built, including missing ones, which are
+    /// [Atlas::MISSING]
+    pub fn indices(&self) -> &HashMap<TextureKey, u32>
+    {
+        &self.indices
+    }
+
+    /// Get the textures which couldn't be loaded, in the order they were given
+    pub fn missing(&self) -> &[MissingTexture]
+    {
+        &self.missing
+    }
+
+    /// Get the layers of this atlas in the form the mesher takes them
+    pub fn texture_layers(&self) -> TextureLayers
+    {
+        let mut layers = TextureLayers::new();
+
+        for (key, &layer) in self.indices()
+        {
+            layers.insert(key.looks.clone(), key.face, layer);
+        }
+        layers
+    }
+}
+
+/// Get the magenta and black checker of `size` pixels drawn for missing textures
+fn checker(size: u32) -> RgbaImage
+{
+    const MAGENTA: Rgba<u8> = Rgba([255, 0, 255, 255]);
+    const BLACK: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+    // Four squares, so that it's still visible when mipmapped
+    let half = (size / 2).max(1);
+
+    RgbaImage::from_fn(size, size, |x, y| match (x / half + y / half) % 2
+    {
+        0 => MAGENTA,
+        _ => BLACK,
+    })
+}
+
+/// Get the mip chain of `image`, each level half the size of the last(rounding
+/// down) and box filtered, down to one pixel
+fn mip_chain(image: RgbaImage) -> Vec<RgbaImage>
+{
+    let mut chain = vec![image];
+
+    loop
+    {
+        let last = chain.last().unwrap();
+        let (w, h) = last.dimensions();
+
+        if w == 1 && h == 1
+        {
+            return chain
+        }
+        let (nw, nh) = ((w / 2).max(1), (h / 2).max(1));
+        let next = RgbaImage::from_fn(nw, nh, |x, y|
+        {
+            // Pixels of `last` covered by this one, which is more than 2x2 when
+            // halving odd sizes
+            let (x0, x1) = (x * w / nw, (x + 1) * w / nw);
+            let (y0, y1) = (y * h / nh, (y + 1) * h / nh);
+
+            let mut sum = [0u32; 4];
+            for (px, py) in (y0..y1).flat_map(|py| (x0..x1).map(move |px| (px, py)))
+            {
+                for (sum, &n) in sum.iter_mut().zip(&last.get_pixel(px, py).0)
+                {
+                    *sum += n as u32;
+                }
+            }
+            let count = (x1 - x0) * (y1 - y0);
+
+            Rgba(sum.map(|n| ((n + count / 2) / count) as u8))
+        });
+        chain.push(next);
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use std::collections::HashMap;
+    use std::io;
+
+    use image::{ DynamicImage, ImageOutputFormat, Rgba, RgbaImage };
+
+    use common::math::Direction;
+
+    use super::{ Atlas, TextureKey, TextureLoader };
+
+    /// Loads textures from encoded images kept in memory
+    #[derive(Debug, Clone, Default)]
+    struct MemoryLoader
+    {
+        /// Encoded images, by key
+        textures: HashMap<TextureKey, Vec<u8>>,
+    }
+
+    impl MemoryLoader
+    {
+        /// Creates a new `MemoryLoader`, with no textures
+        fn new() -> Self
+        {
+            Self::default()
+        }
+
+        /// Load the texture `key` from the encoded image `bytes`
+        fn insert(&mut self, key: TextureKey, bytes: impl Into<Vec<u8>>)
+        {
+            self.textures.insert(key, bytes.into());
+        }
+    }
+
+    impl TextureLoader for MemoryLoader
+    {
+        fn load(&self, key: &TextureKey) -> io::Result<Vec<u8>>
+        {
+            self.textures
+                .get(key)
+                .cloned()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("no texture `{}`", key.file_name())))
+        }
+    }
+
+    /// Encode a `size` pixels square of `color` as a PNG
+    fn png(size: u32, color: [u8; 4]) -> Vec<u8>
+    {
+        let mut bytes = Vec::new();
+
+        DynamicImage::ImageRgba8(RgbaImage::from_pixel(size, size, Rgba(color)))
+            .write_to(&mut bytes, ImageOutputFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn build()
+    {
+        let stone = TextureKey::new("stone", None);
+        let grass = TextureKey::new("grass", Some(Direction::Up));
+        let dirt = TextureKey::new("dirt", None);
+
+        let mut loader = MemoryLoader::new();
+        loader.insert(stone.clone(), png(16, [128, 128, 128, 255]));
+        // Stretched to the tile size
+        loader.insert(grass.clone(), png(8, [0, 255, 0, 255]));
+
+        let keys = vec![stone.clone(), grass.clone(), dirt.clone(), stone.clone()];
+        let atlas = Atlas::build(keys, &loader, 16);
+        let layer = |key| atlas.indices().get(key).copied();
+
+        assert_eq!(layer(&stone), Some(1));
+        assert_eq!(layer(&grass), Some(2));
+        assert_eq!(layer(&dirt), Some(Atlas::MISSING));
+        assert_eq!(layer(&TextureKey::new("sand", None)), None);
+        assert_eq!(atlas.layer_count(), 3);
+        assert_eq!(atlas.indices().len(), 3);
+
+        assert_eq!(atlas.missing().len(), 1);
+        assert_eq!(atlas.missing()[0].key, dirt);
+
+        // Layers are filled in, and the missing texture is a checker
+        assert_eq!(atlas.mips(1)[0].get_pixel(3, 12), &Rgba([128, 128, 128, 255]));
+        assert_eq!(atlas.mips(2)[0].get_pixel(15, 15), &Rgba([0, 255, 0, 255]));
+        assert_eq!(atlas.mips(2)[0].dimensions(), (16, 16));
+        assert_eq!(atlas.mips(0)[0].get_pixel(0, 0), &Rgba([255, 0, 255, 255]));
+        assert_eq!(atlas.mips(0)[0].get_pixel(8, 0), &Rgba([0, 0, 0, 255]));
+        assert_eq!(atlas.mips(0)[0].get_pixel(8, 8), &Rgba([255, 0, 255, 255]));
+
+        // Handed to the mesher
+        let layers = atlas.texture_layers();
+
+        assert_eq!(layers.get("stone", Direction::North), 1);
+        assert_eq!(layers.get("grass", Direction::Up), 2);
+        assert_eq!(layers.get("grass", Direction::North), Atlas::MISSING);
+        assert_eq!(layers.get("dirt", Direction::Down), Atlas::MISSING);
+    }
+
+    #[test]
+    fn mipmaps()
+    {
+        let atlas = Atlas::build(None, &MemoryLoader::new(), 16);
+        let mips = atlas.mips(Atlas::MISSING);
+
+        assert_eq!(atlas.mip_level_count(), 5);
+        assert_eq!(mips.iter().map(|mip| mip.width()).collect::<Vec<_>>(), vec![16, 8, 4, 2, 1]);
+        // Checker squares stay put until they're a pixel each, then blend
+        assert_eq!(mips[3].get_pixel(0, 0), &Rgba([255, 0, 255, 255]));
+        assert_eq!(mips[3].get_pixel(1, 0), &Rgba([0, 0, 0, 255]));
+        assert_eq!(mips[4].get_pixel(0, 0), &Rgba([128, 0, 128, 255]));
+
+        // Sizes that aren't powers of two round down
+        let atlas = Atlas::build(None, &MemoryLoader::new(), 12);
+
+        assert_eq!(atlas.mips(0).iter().map(|mip| mip.width()).collect::<Vec<_>>(), vec![12, 6, 3, 1]);
+    }
+}
@@ -0,0 +1,144 @@
+use common::math::{ Mat4, Vec3 };
+
+/// First-person perspective camera, looking from `position` along `yaw` and `pitch`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera
+{
+    /// Position of the eye, in world space
+    pub position: Vec3<f32>,
+    /// Rotation around the `Y` axis, in radians. `0` looks `North`(`-Z`), and
+    /// increasing values turn clockwise as seen from above, towards `East`(`+X`).
+    pub yaw: f32,
+    /// Rotation above the horizon, in radians, from `-π/2`(straight down) to
+    /// `π/2`(straight up)
+    pub pitch: f32,
+    /// Vertical field of view, in radians
+    pub fov: f32,
+    /// Distance to the near clipping plane, which must be positive
+    pub near: f32,
+    /// Distance to the far clipping plane, which must be greater than `near`
+    pub far: f32,
+}
+
+impl Camera
+{
+    /// Creates a new `Camera` at `position` looking `North`, with a 70 degree
+    /// field of view and a view distance of 1000 blocks
+    pub fn new(position: Vec3<f32>) -> Self
+    {
+        Self
+        {
+            position,
+            yaw: 0.0,
+            pitch: 0.0,
+            fov: 70f32.to_radians(),
+            near: 0.1,
+            far: 1000.0,
+        }
+    }
+
+    /// Get the unit vector this camera is looking along
+    pub fn forward(&self) -> Vec3<f32>
+    {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+
+        Vec3::new(sin_yaw * cos_pitch, sin_pitch, -cos_yaw * cos_pitch)
+    }
+
+    /// Get the unit vector pointing to the right of this camera, which is always
+    /// horizontal regardless of its pitch, ie. to strafe
+    pub fn right(&self) -> Vec3<f32>
+    {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+
+        Vec3::new(cos_yaw, 0.0, sin_yaw)
+    }
+
+    /// Get the matrix transforming world space into this camera's view space
+    pub fn view(&self) -> Mat4<f32>
+    {
+        Mat4::look_at_rh(self.position, self.position + self.forward(), Vec3::unit_y())
+    }
+
+    /// Get the matrix transforming view space into wgpu's clip space(`0 <= z <= w`),
+    /// for a viewport whose width divided by its height is `aspect`
+    pub fn projection(&self, aspect: f32) -> Mat4<f32>
+    {
+        Mat4::perspective_rh_zo(self.fov, aspect, self.near, self.far)
+    }
+
+    /// Get the matrix transforming world space into clip space, see [Camera::view]
+    /// and [Camera::projection]
+    pub fn view_projection(&self, aspect: f32) -> Mat4<f32>
+    {
+        self.projection(aspect) * self.view()
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use common::math::{ Vec3, Vec4 };
+    use super::Camera;
+
+    /// Whether `point` lands inside wgpu's clip space once transformed by `camera`
+    fn visible(camera: &Camera, point: Vec3<f32>) -> bool
+    {
+        let clip = camera.view_projection(16.0 / 9.0) * Vec4::from_point(point);
+
+        clip.w > 0.0
+            && clip.x.abs() <= clip.w
+            && clip.y.abs() <= clip.w
+            && clip.z >= 0.0 && clip.z <= clip.w
+    }
+
+    #[test]
+    fn basis()
+    {
+        let mut camera = Camera::new(Vec3::zero());
+
+        assert!((camera.forward() - Vec3::new(0.0, 0.0, -1.0)).magnitude() < 1e-6);
+        assert!((camera.right() - Vec3::unit_x()).magnitude() < 1e-6);
+
+        camera.yaw = std::f32::consts::FRAC_PI_2;
+        assert!((camera.forward() - Vec3::unit_x()).magnitude() < 1e-6);
+        assert!((camera.right() - Vec3::unit_z()).magnitude() < 1e-6);
+
+        camera.pitch = 0.7;
+        assert!((camera.forward().magnitude() - 1.0).abs() < 1e-6);
+        assert!(camera.forward().dot(camera.right()).abs() < 1e-6);
+        assert_eq!(camera.right().y, 0.0);
+    }
+
+    #[test]
+    fn projection()
+    {
+        let mut camera = Camera::new(Vec3::new(3.0, 64.0, -2.0));
+
+        // Ahead, behind, then out of range
+        assert!(visible(&camera, camera.position + camera.forward() * 10.0));
+        assert!(!visible(&camera, camera.position - camera.forward() * 10.0));
+        assert!(!visible(&camera, camera.position + camera.forward() * 0.05));
+        assert!(!visible(&camera, camera.position + camera.forward() * 1001.0));
+        // Off to the side
+        assert!(!visible(&camera, camera.position + camera.forward() + camera.right() * 10.0));
+
+        camera.yaw = 2.0;
+        camera.pitch = -0.4;
+        assert!(visible(&camera, camera.position + camera.forward() * 500.0));
+        assert!(!visible(&camera, camera.position - camera.forward() * 500.0));
+    }
+
+    #[test]
+    fn aspect()
+    {
+        let camera = Camera::new(Vec3::zero());
+        // 45 degrees to the right, within a wide viewport but not a square one
+        let point = Vec3::new(3.0, 0.0, -3.0);
+        let clip = |aspect| camera.projection(aspect) * camera.view() * Vec4::from_point(point);
+
+        assert!(clip(16.0 / 9.0).x.abs() <= clip(16.0 / 9.0).w);
+        assert!(clip(1.0).x.abs() > clip(1.0).w);
+    }
+}
@@ -0,0 +1,65 @@
+// Draws chunk meshes, see `crate::mesh::BlockVertex`
+
+[[block]]
+struct Camera
+{
+    view_projection: mat4x4<f32>;
+};
+
+[[block]]
+struct Chunk
+{
+    // World-space position of the chunk's minimum corner
+    origin: vec3<f32>;
+};
+
+[[group(0), binding(0)]]
+var<uniform> camera: Camera;
+
+[[group(1), binding(0)]]
+var atlas: texture_2d_array<f32>;
+[[group(1), binding(1)]]
+var atlas_sampler: sampler;
+
+[[group(2), binding(0)]]
+var<uniform> chunk: Chunk;
+
+struct VertexOutput
+{
+    [[builtin(position)]] clip: vec4<f32>;
+    [[location(0)]] uv: vec2<f32>;
+    [[location(1), interpolate(flat)]] layer: u32;
+    [[location(2)]] shade: f32;
+};
+
+[[stage(vertex)]]
+fn vs_main(
+    [[location(0)]] position: vec3<f32>,
+    [[location(1)]] normal: vec3<f32>,
+    [[location(2)]] uv: vec2<f32>,
+    [[location(3)]] layer: u32,
+) -> VertexOutput
+{
+    var out: VertexOutput;
+
+    out.clip = camera.view_projection * vec4<f32>(chunk.origin + position, 1.0);
+    out.uv = uv;
+    out.layer = layer;
+    // Fixed sun from above, so that faces pointing different ways stand apart
+    out.shade = 0.7 + 0.3 * dot(normal, normalize(vec3<f32>(0.3, 1.0, 0.5)));
+
+    return out;
+}
+
+[[stage(fragment)]]
+fn fs_main(in: VertexOutput) -> [[location(0)]] vec4<f32>
+{
+    let color = textureSample(atlas, atlas_sampler, in.uv, i32(in.layer));
+
+    // Cut-out transparency, ie. for leaves
+    if (color.a < 0.5)
+    {
+        discard;
+    }
+    return vec4<f32>(color.rgb * in.shade, 1.0);
+}
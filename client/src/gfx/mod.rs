@@ -0,0 +1,523 @@
+use std::future::Future;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::task::{ Context, Poll, Wake };
+use std::thread::{ self, Thread };
+
+use wgpu::util::DeviceExt;
+use winit::dpi::PhysicalSize;
+use winit::window::Window;
+
+use common::math::Vec3;
+use common::world::Chunk;
+
+use crate::atlas::Atlas;
+use crate::camera::Camera;
+use crate::mesh::{ ChunkMesh, MeshWorker };
+
+/// Owns the graphics device and the swap chain presenting to a window
+pub struct Renderer
+{
+    /// Surface of the window being presented to
+    surface: wgpu::Surface,
+    /// Logical graphics device, which creates every other resource
+    device: wgpu::Device,
+    /// Queue on which command buffers are submitted to the `device`
+    queue: wgpu::Queue,
+    /// Parameters of the `swap_chain`, kept to recreate it when resizing
+    sc_desc: wgpu::SwapChainDescriptor,
+    /// Textures presented to the `surface` in turn
+    swap_chain: wgpu::SwapChain,
+    /// Uniform buffer holding the camera's view-projection matrix
+    camera_buffer: wgpu::Buffer,
+    /// Binds the `camera_buffer` to group `0`
+    camera_bind_group: wgpu::BindGroup,
+    /// Layout of group `1`, the block texture atlas and its sampler
+    atlas_layout: wgpu::BindGroupLayout,
+    /// Binds the atlas last [uploaded](Renderer::upload_atlas) to group `1`, if any
+    atlas_bind_group: Option<wgpu::BindGroup>,
+    /// Layout of group `2`, the position of the chunk being drawn
+    chunk_layout: wgpu::BindGroupLayout,
+    /// Draws [GpuMesh]es
+    block_pipeline: wgpu::RenderPipeline,
+    /// Depth buffer, the size of the swap chain
+    depth_view: wgpu::TextureView,
+}
+
+/// Vertex and index buffers of a chunk's mesh, see [Renderer::upload_meshes]
+pub struct GpuMesh
+{
+    /// [BlockVertex](crate::mesh::BlockVertex)es of the mesh, tightly packed
+    pub vertices: wgpu::Buffer,
+    /// Triangle indices of the mesh, as `u32`s
+    pub indices: wgpu::Buffer,
+    /// Number of indices in `indices`
+    pub index_count: u32,
+    /// Binds the world-space position of the chunk's minimum corner to group `2`
+    pub origin: wgpu::BindGroup,
+}
+
+impl Renderer
+{
+    /// Color the screen is cleared to every frame
+    pub const SKY: wgpu::Color = wgpu::Color { r: 0.47, g: 0.65, b: 1.0, a: 1.0 };
+
+    /// Format of the depth buffer
+    const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+    /// Creates a new `Renderer` presenting to `window`, blocking until a graphics
+    /// device is acquired. Panics if no adapter compatible with `window` is found.
+    pub fn new(window: &Window) -> Self
+    {
+        let size = window.inner_size();
+        let instance = wgpu::Instance::new(wgpu::BackendBit::PRIMARY);
+        // Safety: `window` is valid, and outlives the surface as both live until
+        // the event loop exits
+        let surface = unsafe { instance.create_surface(window) };
+
+        let adapter = block_on(instance.request_adapter(&wgpu::RequestAdapterOptions
+        {
+            power_preference: wgpu::PowerPreference::default(),
+            compatible_surface: Some(&surface),
+        }))
+        .expect("no graphics adapter compatible with the window");
+
+        let (device, queue) = block_on(adapter.request_device(&wgpu::DeviceDescriptor
+        {
+            label: Some("device"),
+            features: wgpu::Features::empty(),
+            limits: wgpu::Limits::default(),
+        }, None))
+        .expect("couldn't acquire a graphics device");
+
+        let sc_desc = wgpu::SwapChainDescriptor
+        {
+            usage: wgpu::TextureUsage::RENDER_ATTACHMENT,
+            format: adapter
+                .get_swap_chain_preferred_format(&surface)
+                .unwrap_or(wgpu::TextureFormat::Bgra8UnormSrgb),
+            width: size.width,
+            height: size.height,
+            present_mode: wgpu::PresentMode::Fifo,
+        };
+        let swap_chain = device.create_swap_chain(&surface, &sc_desc);
+
+        let camera_buffer = device.create_buffer(&wgpu::BufferDescriptor
+        {
+            label: Some("camera"),
+            size: std::mem::size_of::<[f32; 16]>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let camera_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor
+        {
+            label: Some("camera"),
+            entries: &[wgpu::BindGroupLayoutEntry
+            {
+                binding: 0,
+                visibility: wgpu::ShaderStage::VERTEX,
+                ty: wgpu::BindingType::Buffer
+                {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let camera_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor
+        {
+            label: Some("camera"),
+            layout: &camera_layout,
+            entries: &[wgpu::BindGroupEntry
+            {
+                binding: 0,
+                resource: camera_buffer.as_entire_binding(),
+            }],
+        });
+
+        let atlas_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor
+        {
+            label: Some("atlas"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry
+                {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::Texture
+                    {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2Array,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry
+                {
+                    binding: 1,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler { filtering: true, comparison: false },
+                    count: None,
+                },
+            ],
+        });
+        let chunk_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor
+        {
+            label: Some("chunk"),
+            entries: &[wgpu::BindGroupLayoutEntry
+            {
+                binding: 0,
+                visibility: wgpu::ShaderStage::VERTEX,
+                ty: wgpu::BindingType::Buffer
+                {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let shader = device.create_shader_module(&wgpu::ShaderModuleDescriptor
+        {
+            label: Some("block"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("block.wgsl").into()),
+            flags: wgpu::ShaderFlags::all(),
+        });
+        let block_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor
+        {
+            label: Some("block"),
+            bind_group_layouts: &[&camera_layout, &atlas_layout, &chunk_layout],
+            push_constant_ranges: &[],
+        });
+        let block_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor
+        {
+            label: Some("block"),
+            layout: Some(&block_layout),
+            vertex: wgpu::VertexState
+            {
+                module: &shader,
+                entry_point: "vs_main",
+                // Same layout as `Renderer::upload_mesh` packs `BlockVertex`es in
+                buffers: &[wgpu::VertexBufferLayout
+                {
+                    array_stride: 36,
+                    step_mode: wgpu::InputStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3, 2 => Float32x2, 3 => Uint32],
+                }],
+            },
+            primitive: wgpu::PrimitiveState
+            {
+                // Faces are counter-clockwise from the front, see `mesh::push_quad`
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState
+            {
+                format: Self::DEPTH_FORMAT,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Less,
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: Default::default(),
+            fragment: Some(wgpu::FragmentState
+            {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[sc_desc.format.into()],
+            }),
+        });
+        let depth_view = create_depth_view(&device, &sc_desc);
+
+        Self
+        {
+            surface,
+            device,
+            queue,
+            sc_desc,
+            swap_chain,
+            camera_buffer,
+            camera_bind_group,
+            atlas_layout,
+            atlas_bind_group: None,
+            chunk_layout,
+            block_pipeline,
+            depth_view,
+        }
+    }
+
+    /// Recreate the swap chain for a window of `size` physical pixels, ie. after
+    /// it's resized or its scale factor changes. Zero-sized windows(ie. minimized)
+    /// keep the previous swap chain, as wgpu can't create an empty one.
+    pub fn resize(&mut self, size: PhysicalSize<u32>)
+    {
+        if size.width == 0 || size.height == 0
+        {
+            return
+        }
+        self.sc_desc.width = size.width;
+        self.sc_desc.height = size.height;
+        self.swap_chain = self.device.create_swap_chain(&self.surface, &self.sc_desc);
+        self.depth_view = create_depth_view(&self.device, &self.sc_desc);
+    }
+
+    /// Upload every layer of `atlas`, along with their mipmaps, to a new 2D texture
+    /// array which blocks are drawn with from then on. Chunks aren't drawn until an
+    /// atlas is uploaded.
+    pub fn upload_atlas(&mut self, atlas: &Atlas)
+    {
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor
+        {
+            label: Some("atlas"),
+            size: wgpu::Extent3d
+            {
+                width: atlas.tile_size(),
+                height: atlas.tile_size(),
+                depth_or_array_layers: atlas.layer_count(),
+            },
+            mip_level_count: atlas.mip_level_count(),
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST,
+        });
+        for layer in 0..atlas.layer_count()
+        {
+            for (level, mip) in atlas.mips(layer).iter().enumerate()
+            {
+                let (width, height) = mip.dimensions();
+
+                self.queue.write_texture(wgpu::ImageCopyTexture
+                {
+                    texture: &texture,
+                    mip_level: level as u32,
+                    origin: wgpu::Origin3d { x: 0, y: 0, z: layer },
+                },
+                mip.as_raw(),
+                wgpu::ImageDataLayout
+                {
+                    offset: 0,
+                    bytes_per_row: NonZeroU32::new(4 * width),
+                    rows_per_image: NonZeroU32::new(height),
+                },
+                wgpu::Extent3d { width, height, depth_or_array_layers: 1 });
+            }
+        }
+
+        // Array views of a single layer have to be asked for explicitly
+        let view = texture.create_view(&wgpu::TextureViewDescriptor
+        {
+            dimension: Some(wgpu::TextureViewDimension::D2Array),
+            ..Default::default()
+        });
+        let sampler = self.device.create_sampler(&wgpu::SamplerDescriptor
+        {
+            label: Some("atlas"),
+            // Merged faces repeat their texture once per block
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::Repeat,
+            // Crisp pixel art up close, smoothly mipmapped in the distance
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        self.atlas_bind_group = Some(self.device.create_bind_group(&wgpu::BindGroupDescriptor
+        {
+            label: Some("atlas"),
+            layout: &self.atlas_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&view) },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(&sampler) },
+            ],
+        }));
+    }
+
+    /// Upload the chunk meshes `worker` finished building since the last call, along
+    /// with their chunk position. Meant to be called every frame, so that finished
+    /// meshes don't pile up.
+    pub fn upload_meshes(&self, worker: &mut MeshWorker) -> Vec<(Vec3<i32>, GpuMesh)>
+    {
+        worker.drain(|pos, mesh| self.upload_mesh(pos, &mesh))
+    }
+
+    /// Upload `mesh`, of the chunk at the chunk position `pos`, to new vertex and
+    /// index buffers
+    fn upload_mesh(&self, pos: Vec3<i32>, mesh: &ChunkMesh) -> GpuMesh
+    {
+        let vertices = mesh.vertices
+            .iter()
+            .flat_map(|v| v.position
+                .iter()
+                .chain(&v.normal)
+                .chain(&v.uv)
+                .flat_map(|n| n.to_le_bytes())
+                .chain(v.layer.to_le_bytes()))
+            .collect::<Vec<_>>();
+        let indices = mesh.indices
+            .iter()
+            .flat_map(|i| i.to_le_bytes())
+            .collect::<Vec<_>>();
+        // Padded to a `vec3<f32>`'s alignment
+        let origin = (pos * <Chunk>::SIZE as i32)
+            .map(|n| n as f32)
+            .into_array()
+            .iter()
+            .chain(&[0.0])
+            .flat_map(|n| n.to_le_bytes())
+            .collect::<Vec<_>>();
+        let origin = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor
+        {
+            label: Some("chunk origin"),
+            contents: &origin,
+            usage: wgpu::BufferUsage::UNIFORM,
+        });
+
+        GpuMesh
+        {
+            vertices: self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor
+            {
+                label: Some("chunk vertices"),
+                contents: &vertices,
+                usage: wgpu::BufferUsage::VERTEX,
+            }),
+            indices: self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor
+            {
+                label: Some("chunk indices"),
+                contents: &indices,
+                usage: wgpu::BufferUsage::INDEX,
+            }),
+            index_count: mesh.indices.len() as u32,
+            origin: self.device.create_bind_group(&wgpu::BindGroupDescriptor
+            {
+                label: Some("chunk origin"),
+                layout: &self.chunk_layout,
+                entries: &[wgpu::BindGroupEntry
+                {
+                    binding: 0,
+                    resource: origin.as_entire_binding(),
+                }],
+            }),
+        }
+    }
+
+    /// Render and present a frame of `meshes` as seen from `camera`, over [Renderer::SKY].
+    /// Lost or outdated swap chains are left to the caller to [resize](Renderer::resize).
+    pub fn render<'a>(&mut self, camera: &Camera, meshes: impl IntoIterator<Item = &'a GpuMesh>) -> Result<(), wgpu::SwapChainError>
+    {
+        // Tracks the window's size, as of the last resize
+        let aspect = self.sc_desc.width as f32 / self.sc_desc.height as f32;
+        let uniform = camera
+            .view_projection(aspect)
+            .into_col_array()
+            .iter()
+            .flat_map(|n| n.to_le_bytes())
+            .collect::<Vec<_>>();
+        self.queue.write_buffer(&self.camera_buffer, 0, &uniform);
+
+        let frame = self.swap_chain.get_current_frame()?.output;
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor
+        {
+            label: Some("frame"),
+        });
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor
+        {
+            label: Some("blocks"),
+            color_attachments: &[wgpu::RenderPassColorAttachment
+            {
+                view: &frame.view,
+                resolve_target: None,
+                ops: wgpu::Operations
+                {
+                    load: wgpu::LoadOp::Clear(Self::SKY),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment
+            {
+                view: &self.depth_view,
+                depth_ops: Some(wgpu::Operations
+                {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: true,
+                }),
+                stencil_ops: None,
+            }),
+        });
+        if let Some(atlas) = &self.atlas_bind_group
+        {
+            pass.set_pipeline(&self.block_pipeline);
+            pass.set_bind_group(0, &self.camera_bind_group, &[]);
+            pass.set_bind_group(1, atlas, &[]);
+
+            // Chunks of only air have nothing to draw
+            for mesh in meshes.into_iter().filter(|mesh| mesh.index_count != 0)
+            {
+                pass.set_bind_group(2, &mesh.origin, &[]);
+                pass.set_vertex_buffer(0, mesh.vertices.slice(..));
+                pass.set_index_buffer(mesh.indices.slice(..), wgpu::IndexFormat::Uint32);
+                pass.draw_indexed(0..mesh.index_count, 0, 0..1);
+            }
+        }
+        drop(pass);
+
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        Ok(())
+    }
+}
+
+/// Create a depth buffer the size of the swap chain described by `sc_desc`, and
+/// get its view
+fn create_depth_view(device: &wgpu::Device, sc_desc: &wgpu::SwapChainDescriptor) -> wgpu::TextureView
+{
+    device
+        .create_texture(&wgpu::TextureDescriptor
+        {
+            label: Some("depth"),
+            size: wgpu::Extent3d
+            {
+                width: sc_desc.width,
+                height: sc_desc.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: Renderer::DEPTH_FORMAT,
+            usage: wgpu::TextureUsage::RENDER_ATTACHMENT,
+        })
+        .create_view(&Default::default())
+}
+
+/// Run `future` to completion on the current thread, parking it while pending. wgpu
+/// only needs this to acquire its device, so there's no point pulling in a runtime.
+fn block_on<F: Future>(future: F) -> F::Output
+{
+    /// Wakes the thread blocked on the future
+    struct Unpark(Thread);
+
+    impl Wake for Unpark
+    {
+        fn wake(self: Arc<Self>)
+        {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Arc::new(Unpark(thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+
+    loop
+    {
+        match future.as_mut().poll(&mut cx)
+        {
+            Poll::Ready(out) => return out,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use winit::event::{ DeviceEvent, ElementState, Event, KeyboardInput, MouseButton, VirtualKeyCode, WindowEvent };
+
+use common::math::Vec3;
+
+use crate::camera::Camera;
+
+/// Flies a [Camera] around with WASD, space and shift, looking around with the
+/// mouse. This only interprets events: the window's cursor is left for the caller
+/// to grab whenever [CameraController::is_grabbed] changes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CameraController
+{
+    /// Radians turned per unit(roughly pixels) of mouse motion
+    pub sensitivity: f32,
+    /// Blocks travelled per second
+    pub speed: f32,
+    /// Factor `speed` is multiplied by while sprinting
+    pub sprint_multiplier: f32,
+    /// Whether the cursor should be grabbed, ie. the mouse is looking around
+    grabbed: bool,
+    /// Mouse motion not yet applied to the camera
+    look: (f32, f32),
+    /// Whether each of the movement keys is held
+    keys: Keys,
+}
+
+/// Movement keys held down
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct Keys
+{
+    forward: bool,
+    back: bool,
+    left: bool,
+    right: bool,
+    up: bool,
+    down: bool,
+    sprint: bool,
+}
+
+impl CameraController
+{
+    /// Furthest the camera can pitch up or down, just short of straight up or down
+    /// where yaw becomes ambiguous
+    pub const MAX_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
+    /// Creates a new `CameraController` with the cursor released and no keys held
+    pub fn new() -> Self
+    {
+        Self
+        {
+            sensitivity: 0.003,
+            speed: 10.0,
+            sprint_multiplier: 2.5,
+            grabbed: false,
+            look: (0.0, 0.0),
+            keys: Keys::default(),
+        }
+    }
+
+    /// Whether the cursor should be grabbed and hidden. It's grabbed when the window
+    /// gains focus or is clicked, and released when it loses focus or Escape is pressed.
+    pub fn is_grabbed(&self) -> bool
+    {
+        self.grabbed
+    }
+
+    /// Update this controller's state with `event`. Returns whether it was consumed,
+    /// in which case it shouldn't be handled elsewhere.
+    pub fn process_event<T>(&mut self, event: &Event<'_, T>) -> bool
+    {
+        match event
+        {
+            Event::DeviceEvent { event: DeviceEvent::MouseMotion { delta: (dx, dy) }, .. } =>
+            {
+                // Mouse motion is reported even when unfocused
+                if !self.grabbed { return false }
+
+                self.look.0 += *dx as f32;
+                self.look.1 += *dy as f32;
+                true
+            },
+            Event::WindowEvent { event, .. } => match event
+            {
+                WindowEvent::Focused(focused) =>
+                {
+                    self.grabbed = *focused;
+                    // Keys released while unfocused are never reported
+                    self.keys = Keys::default();
+                    false
+                },
+                WindowEvent::MouseInput { state: ElementState::Pressed, button: MouseButton::Left, .. } if !self.grabbed =>
+                {
+                    self.grabbed = true;
+                    true
+                },
+                WindowEvent::KeyboardInput { input: KeyboardInput { state, virtual_keycode: Some(key), .. }, .. } =>
+                {
+                    self.process_key(*key, *state == ElementState::Pressed)
+                },
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Update this controller's state with `key` being pressed or released
+    fn process_key(&mut self, key: VirtualKeyCode, pressed: bool) -> bool
+    {
+        let held = match key
+        {
+            VirtualKeyCode::W => &mut self.keys.forward,
+            VirtualKeyCode::S => &mut self.keys.back,
+            VirtualKeyCode::A => &mut self.keys.left,
+            VirtualKeyCode::D => &mut self.keys.right,
+            VirtualKeyCode::Space => &mut self.keys.up,
+            VirtualKeyCode::LShift => &mut self.keys.down,
+            VirtualKeyCode::LControl => &mut self.keys.sprint,
+            VirtualKeyCode::Escape if pressed =>
+            {
+                self.grabbed = false;
+                self.keys = Keys::default();
+                return true
+            },
+            _ => return false,
+        };
+        *held = pressed;
+        true
+    }
+
+    /// Apply the mouse motion and held keys to `camera`, moving it as it would
+    /// have over `dt` seconds. Moving forward stays level regardless of pitch.
+    pub fn update(&mut self, camera: &mut Camera, dt: f32)
+    {
+        let (dx, dy) = std::mem::take(&mut self.look);
+
+        camera.yaw = (camera.yaw + dx * self.sensitivity) % std::f32::consts::TAU;
+        camera.pitch = (camera.pitch - dy * self.sensitivity).clamp(-Self::MAX_PITCH, Self::MAX_PITCH);
+
+        let axis = |pos: bool, neg: bool| pos as i8 as f32 - neg as i8 as f32;
+        let right = camera.right();
+        let forward = Vec3::new(right.z, 0.0, -right.x);
+
+        let dir = forward * axis(self.keys.forward, self.keys.back)
+            + right * axis(self.keys.right, self.keys.left)
+            + Vec3::unit_y() * axis(self.keys.up, self.keys.down);
+        if dir == Vec3::zero() { return }
+
+        let speed = match self.keys.sprint
+        {
+            true => self.speed * self.sprint_multiplier,
+            false => self.speed,
+        };
+        camera.position += dir.normalized() * speed * dt;
+    }
+}
+
+impl Default for CameraController
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use winit::event::{ DeviceEvent, DeviceId, ElementState, Event, KeyboardInput, ModifiersState, VirtualKeyCode, WindowEvent };
+    use winit::window::WindowId;
+
+    use common::math::Vec3;
+
+    use crate::camera::Camera;
+    use super::CameraController;
+
+    fn key(key: VirtualKeyCode, state: ElementState) -> Event<'static, ()>
+    {
+        #[allow(deprecated)]
+        let input = KeyboardInput { scancode: 0, state, virtual_keycode: Some(key), modifiers: ModifiersState::empty() };
+
+        Event::WindowEvent
+        {
+            // Safety: only compared against, never passed to the platform
+            window_id: unsafe { WindowId::dummy() },
+            event: WindowEvent::KeyboardInput { device_id: unsafe { DeviceId::dummy() }, input, is_synthetic: false },
+        }
+    }
+
+    fn motion(dx: f64, dy: f64) -> Event<'static, ()>
+    {
+        Event::DeviceEvent { device_id: unsafe { DeviceId::dummy() }, event: DeviceEvent::MouseMotion { delta: (dx, dy) } }
+    }
+
+    fn focus(focused: bool) -> Event<'static, ()>
+    {
+        Event::WindowEvent { window_id: unsafe { WindowId::dummy() }, event: WindowEvent::Focused(focused) }
+    }
+
+    /// Hold `keys` down for one second, from the origin looking `North`, and get
+    /// where the camera ends up
+    fn fly(keys: &[VirtualKeyCode]) -> Vec3<f32>
+    {
+        let mut controller = CameraController::new();
+        let mut camera = Camera::new(Vec3::zero());
+
+        for &k in keys
+        {
+            assert!(controller.process_event(&key(k, ElementState::Pressed)));
+        }
+        controller.update(&mut camera, 1.0);
+        camera.position
+    }
+
+    fn approx(a: Vec3<f32>, b: Vec3<f32>) -> bool
+    {
+        (a - b).magnitude() < 1e-4
+    }
+
+    #[test]
+    fn movement()
+    {
+        use VirtualKeyCode::*;
+
+        assert!(approx(fly(&[W]), Vec3::new(0.0, 0.0, -10.0)));
+        assert!(approx(fly(&[S]), Vec3::new(0.0, 0.0, 10.0)));
+        assert!(approx(fly(&[A]), Vec3::new(-10.0, 0.0, 0.0)));
+        assert!(approx(fly(&[D]), Vec3::new(10.0, 0.0, 0.0)));
+        assert!(approx(fly(&[Space]), Vec3::new(0.0, 10.0, 0.0)));
+        assert!(approx(fly(&[LShift]), Vec3::new(0.0, -10.0, 0.0)));
+        assert!(approx(fly(&[W, LControl]), Vec3::new(0.0, 0.0, -25.0)));
+        // Opposites cancel, diagonals aren't faster
+        assert!(approx(fly(&[W, S]), Vec3::zero()));
+        assert!((fly(&[W, D]).magnitude() - 10.0).abs() < 1e-4);
+        // Unbound
+        assert!(!CameraController::new().process_event(&key(Q, ElementState::Pressed)));
+    }
+
+    #[test]
+    fn release_keys()
+    {
+        let mut controller = CameraController::new();
+        let mut camera = Camera::new(Vec3::zero());
+
+        controller.process_event(&key(VirtualKeyCode::W, ElementState::Pressed));
+        controller.update(&mut camera, 0.5);
+        controller.process_event(&key(VirtualKeyCode::W, ElementState::Released));
+        controller.update(&mut camera, 0.5);
+
+        assert!(approx(camera.position, Vec3::new(0.0, 0.0, -5.0)));
+
+        // Relative to where the camera's looking, but level
+        camera.yaw = std::f32::consts::FRAC_PI_2;
+        camera.pitch = 1.0;
+        controller.process_event(&key(VirtualKeyCode::W, ElementState::Pressed));
+        controller.update(&mut camera, 0.5);
+
+        assert!(approx(camera.position, Vec3::new(5.0, 0.0, -5.0)));
+    }
+
+    #[test]
+    fn look()
+    {
+        let mut controller = CameraController::new();
+        let mut camera = Camera::new(Vec3::zero());
+
+        // Ignored until grabbed
+        assert!(!controller.process_event(&motion(100.0, 0.0)));
+        controller.process_event(&focus(true));
+        assert!(controller.is_grabbed());
+
+        assert!(controller.process_event(&motion(100.0, -50.0)));
+        controller.update(&mut camera, 0.0);
+        assert!((camera.yaw - 0.3).abs() < 1e-6);
+        assert!((camera.pitch - 0.15).abs() < 1e-6);
+
+        // Clamped
+        controller.process_event(&motion(0.0, -10000.0));
+        controller.update(&mut camera, 0.0);
+        assert_eq!(camera.pitch, CameraController::MAX_PITCH);
+        controller.process_event(&motion(0.0, 10000.0));
+        controller.update(&mut camera, 0.0);
+        assert_eq!(camera.pitch, -CameraController::MAX_PITCH);
+
+        // Released
+        assert!(controller.process_event(&key(VirtualKeyCode::Escape, ElementState::Pressed)));
+        assert!(!controller.is_grabbed());
+        assert!(!controller.process_event(&motion(100.0, 0.0)));
+
+        controller.process_event(&focus(true));
+        controller.process_event(&focus(false));
+        assert!(!controller.is_grabbed());
+    }
+}
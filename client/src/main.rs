@@ -1,8 +1,29 @@
 mod framework;
+mod atlas;
+mod camera;
+mod gfx;
+mod input;
+mod mesh;
 
 use winit::event_loop::{ControlFlow, EventLoop };
 use winit::window::WindowBuilder;
 use winit::event::*;
+use std::collections::HashMap;
+use std::time::Instant;
+use std::sync::Arc;
+
+use common::math::{ Direction, Vec3 };
+use common::world::{ Chunk, ChunkEvent, World, block };
+use common::vanilla::blocks::register_all;
+
+use atlas::{ Atlas, FsLoader, TextureKey };
+use camera::Camera;
+use gfx::Renderer;
+use input::CameraController;
+use mesh::MeshWorker;
+
+/// Radius, in chunks, of the ball of chunks loaded and drawn around the camera
+const RENDER_DISTANCE: u32 = 4;
 
 fn main()
 {
@@ -10,18 +31,159 @@ fn main()
     let window = WindowBuilder::new()
         .build(&event_loop)
         .unwrap();
+    let mut renderer = Renderer::new(&window);
+
+    let mut registry = block::Registry::default();
+    register_all(&mut registry);
+
+    // One texture per type of block, drawn on every face
+    let keys = registry
+        .default_instances()
+        .map(|(_, block)| TextureKey::new(block.looks(), None));
+    let atlas = Atlas::build(keys, &FsLoader::new("assets/textures"), 16);
+    for missing in atlas.missing()
+    {
+        eprintln!("Couldn't load texture `{}`: {}", missing.key.file_name(), missing.error);
+    }
+    renderer.upload_atlas(&atlas);
+
+    let mut mesher = MeshWorker::new(Arc::new(atlas.texture_layers()));
+    let mut meshes = HashMap::new();
+
+    let mut world = World::new(registry);
+    let chunk_events = world.subscribe_chunk_events();
+    // Chunk the camera was last in, to stream chunks around whenever it changes
+    let mut center = None;
+
+    let mut camera = Camera::new(Vec3::new(0.0, 64.0, 0.0));
+    let mut controller = CameraController::new();
+    let mut last_frame = Instant::now();
 
     event_loop.run(move |event, _, control_flow|
     {
+        // Render continuously, rather than waiting on window events
+        *control_flow = ControlFlow::Poll;
+
+        let grabbed = controller.is_grabbed();
+        let consumed = controller.process_event(&event);
+
+        if controller.is_grabbed() != grabbed
+        {
+            // Not all platforms support grabbing, in which case the cursor's only hidden
+            let _ = window.set_cursor_grab(controller.is_grabbed());
+            window.set_cursor_visible(!controller.is_grabbed());
+        }
+        if consumed
+        {
+            return
+        }
+
         match event
         {
-            Event::WindowEvent { ref event, window_id }
-                if window_id == window.id()
-                && matches!(event, WindowEvent::CloseRequested) =>
+            Event::WindowEvent { ref event, window_id } if window_id == window.id() => match event
             {
-                *control_flow = ControlFlow::Exit
+                WindowEvent::CloseRequested =>
+                {
+                    *control_flow = ControlFlow::Exit
+                },
+                WindowEvent::Resized(size) =>
+                {
+                    renderer.resize(*size)
+                },
+                WindowEvent::ScaleFactorChanged { new_inner_size, .. } =>
+                {
+                    renderer.resize(**new_inner_size)
+                },
+                // Toggle greedy meshing, remeshing every chunk to compare
+                WindowEvent::KeyboardInput
+                {
+                    input: KeyboardInput { state: ElementState::Pressed, virtual_keycode: Some(VirtualKeyCode::F3), .. },
+                    ..
+                } =>
+                {
+                    mesher.set_greedy(!mesher.is_greedy());
+                    for pos in world.chunk_positions()
+                    {
+                        submit_chunk(&mut mesher, &world, pos);
+                    }
+                    eprintln!("Remeshing {} chunks, greedy: {}", mesher.pending(), mesher.is_greedy());
+                },
+                _ => { }
+            },
+            Event::RedrawRequested(window_id) if window_id == window.id() =>
+            {
+                let now = Instant::now();
+                controller.update(&mut camera, (now - last_frame).as_secs_f32());
+                last_frame = now;
+
+                let chunk = camera.position.map(|n| (n / <Chunk>::SIZE as f32).floor() as i32);
+                if center != Some(chunk)
+                {
+                    center = Some(chunk);
+                    world.ensure_loaded_around(chunk, RENDER_DISTANCE);
+                    world.evict_out_of_range(chunk, RENDER_DISTANCE + 1);
+                }
+                for event in chunk_events.try_iter()
+                {
+                    let pos = match event
+                    {
+                        ChunkEvent::Loaded(pos) | ChunkEvent::Generated(pos) =>
+                        {
+                            submit_chunk(&mut mesher, &world, pos);
+                            pos
+                        },
+                        ChunkEvent::Unloaded(pos) =>
+                        {
+                            meshes.remove(&pos);
+                            pos
+                        },
+                    };
+                    // Faces on the neighbors' bounds are culled against this chunk
+                    for dir in Direction::ALL
+                    {
+                        submit_chunk(&mut mesher, &world, pos + dir.offset());
+                    }
+                }
+                // Meshes of chunks unloaded while they were built are dropped
+                meshes.extend(renderer
+                    .upload_meshes(&mut mesher)
+                    .into_iter()
+                    .filter(|&(pos, _)| world.is_chunk_loaded(pos)));
+
+                match renderer.render(&camera, meshes.values())
+                {
+                    Ok(()) => { },
+                    // Recreate the swap chain, and try again next frame
+                    Err(wgpu::SwapChainError::Lost | wgpu::SwapChainError::Outdated) =>
+                    {
+                        renderer.resize(window.inner_size())
+                    },
+                    Err(wgpu::SwapChainError::OutOfMemory) =>
+                    {
+                        eprintln!("Out of graphics memory, exiting");
+                        *control_flow = ControlFlow::Exit
+                    },
+                    // Skip this frame
+                    Err(wgpu::SwapChainError::Timeout) => { },
+                }
+            },
+            Event::MainEventsCleared =>
+            {
+                window.request_redraw()
             },
             _ => { }
         }
     })
 }
+
+/// Mesh the chunk at the chunk position `pos` along with its neighbors in `world`,
+/// if it's loaded
+fn submit_chunk(mesher: &mut MeshWorker, world: &World, pos: Vec3<i32>)
+{
+    if let Some(chunk) = world.get_chunk_arc(pos)
+    {
+        let neighbors = Direction::ALL.map(|dir| world.get_chunk_arc(pos + dir.offset()));
+
+        mesher.submit(pos, chunk, neighbors);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,745 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{ AtomicBool, Ordering };
+
+use parking_lot::RwLock;
+
+use common::world::{ Chunk, block };
+use common::vanilla::blocks::BlockAir;
+use common::math::{ Direction, Vec3 };
+use common::util::ThreadJobs;
+
+/// Vertex of a block face, as uploaded to vertex buffers
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockVertex
+{
+    /// Position in chunk-space, where blocks span one unit
+    pub position: [f32; 3],
+    /// Unit vector the face is pointing along
+    pub normal: [f32; 3],
+    /// Texture coordinates, `(0, 0)` being the texture's top left
+    pub uv: [f32; 2],
+    /// Layer of the block texture array this face is drawn with
+    pub layer: u32,
+}
+
+/// Vertices and triangle indices of a chunk, see [mesh_chunk]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChunkMesh
+{
+    /// Four vertices per face
+    pub vertices: Vec<BlockVertex>,
+    /// Two counter-clockwise triangles per face, indexing `vertices`
+    pub indices: Vec<u32>,
+}
+
+/// A chunk along with the chunks adjacent to it, so that faces on its bounds can
+/// be culled against what's on the other side
+#[derive(Clone, Copy)]
+pub struct ChunkView<'a>
+{
+    /// The chunk being meshed
+    pub chunk: &'a Chunk,
+    /// Adjacent chunks, indexed by `Direction as usize`. Faces bordering missing
+    /// ones are never culled.
+    pub neighbors: [Option<&'a Chunk>; 6],
+}
+
+/// Layers of the block texture array, by the [looks](common::world::Block::looks)
+/// of the blocks they're drawn on
+#[derive(Debug, Clone, Default)]
+pub struct TextureLayers
+{
+    /// Layer of the whole block, then of each face overriding it, by key
+    layers: HashMap<String, (Option<u32>, [Option<u32>; 6])>,
+}
+
+/// Builds chunk meshes with [mesh_chunk_greedy](or [mesh_chunk], see [MeshWorker::set_greedy])
+/// on threads of its own, so that the render thread only has to upload them once
+/// they're done
+pub struct MeshWorker
+{
+    /// Meshes being built, keyed by chunk position and submission. Cancelled jobs
+    /// have no mesh.
+    jobs: ThreadJobs<Option<ChunkMesh>, (Vec3<i32>, u64)>,
+    /// Texture layers meshes are built with
+    textures: Arc<TextureLayers>,
+    /// Latest submission of every position whose mesh hasn't been drained yet, and
+    /// its cancellation handle
+    latest: HashMap<Vec3<i32>, (u64, Arc<AtomicBool>)>,
+    /// Identifies the next submission
+    next: u64,
+    /// Whether meshes are built with [mesh_chunk_greedy] rather than [mesh_chunk]
+    greedy: bool,
+}
+
+impl<'a> ChunkView<'a>
+{
+    /// Creates a new `ChunkView` of `chunk`, with no neighbors
+    pub fn new(chunk: &'a Chunk) -> Self
+    {
+        Self { chunk, neighbors: [None; 6] }
+    }
+
+    /// Set the chunk adjacent to this view's in the direction `dir`
+    pub fn with_neighbor(mut self, dir: Direction, chunk: &'a Chunk) -> Self
+    {
+        self.neighbors[dir as usize] = Some(chunk);
+        self
+    }
+
+    /// Get the block at `pos`, in the chunk-space of this view's chunk, which may be
+    /// up to one block outside of its bounds along one axis. Returns `None` outside
+    /// of that, or if the neighbor `pos` is in is missing.
+    pub fn get(&self, pos: Vec3<i32>) -> Option<&'a dyn block::Object>
+    {
+        const SIZE: i32 = <Chunk>::SIZE as i32;
+
+        let outside = pos.map(|n| !(0..SIZE).contains(&n));
+        let chunk = match (outside.x, outside.y, outside.z)
+        {
+            (false, false, false) => self.chunk,
+            (true, false, false) | (false, true, false) | (false, false, true) =>
+            {
+                let dir = Direction::from_normal(pos.map(|n| if n < 0 { -1 } else if n >= SIZE { 1 } else { 0 }))?;
+
+                self.neighbors[dir as usize]?
+            },
+            _ => return None,
+        };
+        chunk.get(pos.map(|n| n.rem_euclid(SIZE) as usize))
+    }
+}
+
+impl TextureLayers
+{
+    /// Layer faces are drawn with when their block's looks have none
+    pub const MISSING: u32 = 0;
+
+    /// Creates a new `TextureLayers` with no layers but [TextureLayers::MISSING]
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    /// Draw blocks that look like `key` with `layer`, or only their faces pointing
+    /// towards `face` if any
+    pub fn insert(&mut self, key: impl Into<String>, face: Option<Direction>, layer: u32)
+    {
+        let (all, faces) = self.layers.entry(key.into()).or_default();
+
+        match face
+        {
+            Some(face) => faces[face as usize] = Some(layer),
+            None => *all = Some(layer),
+        }
+    }
+
+    /// Get the layer the face pointing towards `face` of blocks that look like
+    /// `key` is drawn with
+    pub fn get(&self, key: &str, face: Direction) -> u32
+    {
+        self.layers
+            .get(key)
+            .and_then(|(all, faces)| faces[face as usize].or(*all))
+            .unwrap_or(Self::MISSING)
+    }
+}
+
+/// Build the mesh of every face of `view`'s chunk that isn't hidden by the block
+/// in front of it, as [occluded](common::world::Block::occludes) on its opposite
+/// face. Air is skipped, and the other blocks are drawn as full cubes textured by
+/// `textures`.
+pub fn mesh_chunk(view: &ChunkView<'_>, textures: &TextureLayers) -> ChunkMesh
+{
+    let mut mesh = ChunkMesh::default();
+
+    for (pos, _) in view.chunk
+    {
+        let pos = pos.map(|n| n as i32);
+
+        for &dir in &Direction::ALL
+        {
+            if let Some(looks) = visible_face(view, pos, dir)
+            {
+                let min = pos.map(|n| n as f32);
+
+                push_quad(&mut mesh, min, min + 1.0, dir, textures.get(&looks, dir));
+            }
+        }
+    }
+    mesh
+}
+
+/// Build the mesh of the same faces as [mesh_chunk], merging adjacent faces pointing
+/// the same way into rectangles, which takes far fewer vertices on flat terrain.
+/// Faces are only merged if they're drawn with the same texture layer, and `key`
+/// (given the position in chunk-space of a face's block and its direction) returns
+/// the same for them, ie. the face's light. Merged faces' textures repeat rather
+/// than stretch.
+pub fn mesh_chunk_greedy<K: PartialEq>(
+    view: &ChunkView<'_>,
+    textures: &TextureLayers,
+    mut key: impl FnMut(Vec3<i32>, Direction) -> K,
+) -> ChunkMesh
+{
+    const SIZE: usize = <Chunk>::SIZE;
+
+    let mut mesh = ChunkMesh::default();
+    // Faces of the slice being swept, by their position along its two axes
+    let mut mask = Vec::with_capacity(SIZE * SIZE);
+
+    for &dir in &Direction::ALL
+    {
+        let axis = dir.axis() as usize;
+        // The two other axes, in the slice
+        let (a, b) = ((axis + 1) % 3, (axis + 2) % 3);
+
+        for slice in 0..SIZE
+        {
+            mask.clear();
+            for j in 0..SIZE
+            {
+                for i in 0..SIZE
+                {
+                    let mut pos = Vec3::zero();
+                    pos[axis] = slice as i32;
+                    pos[a] = i as i32;
+                    pos[b] = j as i32;
+
+                    mask.push(visible_face(view, pos, dir).map(|looks|
+                    {
+                        (textures.get(&looks, dir), key(pos, dir))
+                    }));
+                }
+            }
+
+            // Grow rectangles along `a`, then `b`, from each face not yet merged
+            for j in 0..SIZE
+            {
+                let mut i = 0;
+                while i < SIZE
+                {
+                    let face = match mask[j * SIZE + i].take()
+                    {
+                        Some(face) => face,
+                        None => { i += 1; continue }
+                    };
+                    let mut width = 1;
+                    while i + width < SIZE && mask[j * SIZE + i + width].as_ref() == Some(&face)
+                    {
+                        mask[j * SIZE + i + width] = None;
+                        width += 1;
+                    }
+                    let mut height = 1;
+                    while j + height < SIZE
+                        && (i..i + width).all(|i| mask[(j + height) * SIZE + i].as_ref() == Some(&face))
+                    {
+                        for i in i..i + width
+                        {
+                            mask[(j + height) * SIZE + i] = None;
+                        }
+                        height += 1;
+                    }
+
+                    let mut min = Vec3::zero();
+                    min[axis] = slice as f32;
+                    min[a] = i as f32;
+                    min[b] = j as f32;
+                    let mut max = min + 1.0;
+                    max[a] += (width - 1) as f32;
+                    max[b] += (height - 1) as f32;
+
+                    push_quad(&mut mesh, min, max, dir, face.0);
+                    i += width;
+                }
+            }
+        }
+    }
+    mesh
+}
+
+/// Get the looks of the block at `pos`, in chunk-space, if its face pointing
+/// towards `dir` is visible, see [mesh_chunk]
+fn visible_face(view: &ChunkView<'_>, pos: Vec3<i32>, dir: Direction) -> Option<std::borrow::Cow<'static, str>>
+{
+    let block = view.get(pos)?;
+
+    if block.is::<BlockAir>() { return None }
+
+    let hidden = view
+        .get(pos + dir.offset())
+        .is_some_and(|neighbor| neighbor.occludes(dir.opposite()));
+    match hidden
+    {
+        true => None,
+        false => Some(block.looks()),
+    }
+}
+
+/// Append the face pointing towards `dir` of the box of blocks between the
+/// corners `min` and `max`, in chunk-space, to `mesh`. Its texture repeats once
+/// per block.
+fn push_quad(mesh: &mut ChunkMesh, min: Vec3<f32>, max: Vec3<f32>, dir: Direction, layer: u32)
+{
+    let (u, v) = dir.tangents();
+    let (u, v) = (u.normal::<f32>(), v.normal::<f32>());
+    let normal = dir.normal::<f32>();
+    let size = max - min;
+    let (width, height) = ((size * u).sum().abs(), (size * v).sum().abs());
+    let center = (min + max) / 2.0 + normal * size / 2.0;
+    let (u, v) = (u * width / 2.0, v * height / 2.0);
+
+    // Counter-clockwise seen from the front, as `u × v` points out of the face
+    let corners =
+    [
+        (-u - v, [0.0, height]),
+        (u - v, [width, height]),
+        (u + v, [width, 0.0]),
+        (v - u, [0.0, 0.0]),
+    ];
+    let base = mesh.vertices.len() as u32;
+
+    mesh.vertices.extend(corners.iter().map(|&(offset, uv)| BlockVertex
+    {
+        position: (center + offset).into_array(),
+        normal: normal.into_array(),
+        uv,
+        layer,
+    }));
+    mesh.indices.extend([0, 1, 2, 0, 2, 3].iter().map(|i| base + i));
+}
+
+impl MeshWorker
+{
+    /// Creates a new `MeshWorker`, meshing with `textures` on one thread per CPU
+    pub fn new(textures: Arc<TextureLayers>) -> Self
+    {
+        Self
+        {
+            jobs: ThreadJobs::with_name("mesh", 0),
+            textures,
+            latest: HashMap::new(),
+            next: 0,
+            greedy: true,
+        }
+    }
+
+    /// Build the meshes submitted from now on with [mesh_chunk_greedy] if `greedy`,
+    /// or one quad per face with [mesh_chunk] otherwise, ie. to compare them. Meshes
+    /// are greedy by default.
+    pub fn set_greedy(&mut self, greedy: bool)
+    {
+        self.greedy = greedy;
+    }
+
+    /// Whether meshes submitted from now on are greedy, see [MeshWorker::set_greedy]
+    pub fn is_greedy(&self) -> bool
+    {
+        self.greedy
+    }
+
+    /// Mesh `chunk` at the chunk position `pos`, culling its faces against the
+    /// adjacent `neighbors`(indexed by `Direction as usize`) if they're loaded. Each
+    /// chunk is read-locked only while it's meshed. Supersedes the last submission
+    /// of `pos` if it hasn't been drained yet, whose mesh is then never drained.
+    pub fn submit(&mut self, pos: Vec3<i32>, chunk: Arc<RwLock<Chunk>>, neighbors: [Option<Arc<RwLock<Chunk>>>; 6])
+    {
+        let id = self.next;
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        self.next += 1;
+        if let Some((_, old)) = self.latest.insert(pos, (id, Arc::clone(&cancelled)))
+        {
+            old.store(true, Ordering::Release);
+        }
+
+        let textures = Arc::clone(&self.textures);
+        let greedy = self.greedy;
+        self.jobs.push_keyed((pos, id), move ||
+        {
+            // Superseded before it even started
+            if cancelled.load(Ordering::Acquire)
+            {
+                return None
+            }
+            let chunk = chunk.read();
+            let neighbors = neighbors
+                .iter()
+                .map(|neighbor| neighbor.as_ref().map(|neighbor| neighbor.read()))
+                .collect::<Vec<_>>();
+
+            let mut view = ChunkView::new(&chunk);
+            for (&dir, neighbor) in Direction::ALL.iter().zip(&neighbors)
+            {
+                if let Some(neighbor) = neighbor
+                {
+                    view = view.with_neighbor(dir, neighbor);
+                }
+            }
+            Some(match greedy
+            {
+                true => mesh_chunk_greedy(&view, &textures, |_, _| ()),
+                false => mesh_chunk(&view, &textures),
+            })
+        });
+    }
+
+    /// Get the number of chunk positions submitted whose mesh hasn't been drained
+    /// yet, not counting superseded submissions
+    pub fn pending(&self) -> usize
+    {
+        self.latest.len()
+    }
+
+    /// Pass the meshes finished since the last drain to `upload`(ie. to create their
+    /// GPU buffers), and get what it returns along with their chunk position. Meant
+    /// to be called every frame on the render thread. This is non-blocking, and
+    /// superseded meshes are discarded without being uploaded.
+    pub fn drain<M>(&mut self, mut upload: impl FnMut(Vec3<i32>, ChunkMesh) -> M) -> Vec<(Vec3<i32>, M)>
+    {
+        let latest = &mut self.latest;
+
+        self.jobs
+            .pull_keyed()
+            .filter_map(|((pos, id), mesh)|
+            {
+                if latest.get(&pos).map(|&(latest, _)| latest) != Some(id)
+                {
+                    return None
+                }
+                latest.remove(&pos);
+
+                // Only cancelled if superseded, so the latest always has a mesh
+                mesh.map(|mesh| (pos, upload(pos, mesh)))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use std::sync::Arc;
+    use std::time::Duration;
+    use std::thread;
+
+    use parking_lot::RwLock;
+
+    use common::world::{ Chunk, block };
+    use common::vanilla::blocks::{ BlockAir, BlockStone, BlockWater };
+    use common::math::{ Direction, Vec3, vec3 };
+
+    use super::{ ChunkMesh, ChunkView, MeshWorker, TextureLayers, mesh_chunk, mesh_chunk_greedy };
+
+    fn registry() -> Arc<block::Registry>
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockStone>();
+        registry.register::<BlockWater>();
+
+        Arc::new(registry)
+    }
+
+    /// Count the vertices and indices of the mesh of `chunk`, with `neighbors`
+    fn count(chunk: &Chunk, neighbors: [Option<&Chunk>; 6]) -> (usize, usize)
+    {
+        let mesh = mesh_chunk(&ChunkView { chunk, neighbors }, &TextureLayers::new());
+
+        (mesh.vertices.len(), mesh.indices.len())
+    }
+
+    #[test]
+    fn cubes()
+    {
+        let mut chunk = Chunk::new(vec3(0, 0, 0), &registry());
+
+        assert_eq!(count(&chunk, [None; 6]), (0, 0));
+
+        chunk.set(vec3(4, 5, 6), BlockStone);
+        assert_eq!(count(&chunk, [None; 6]), (24, 36));
+
+        chunk.set(vec3(5, 5, 6), BlockStone);
+        assert_eq!(count(&chunk, [None; 6]), (40, 60));
+
+        // Faces aren't hidden by blocks that can be seen through
+        chunk.set(vec3(4, 6, 6), BlockWater { level: 0, falling: false });
+        assert_eq!(count(&chunk, [None; 6]).0, 40 + 20);
+    }
+
+    #[test]
+    fn buried()
+    {
+        let registry = registry();
+        let full = ||
+        {
+            let mut chunk = Chunk::new(vec3(0, 0, 0), &registry);
+
+            chunk.fill_region(vec3(0, 0, 0), vec3(31, 31, 31), BlockStone);
+            chunk
+        };
+        let (chunk, neighbor) = (full(), full());
+
+        // Only the chunk's outside
+        assert_eq!(count(&chunk, [None; 6]).0, 6 * 32 * 32 * 4);
+        // Nothing with every neighbor
+        assert_eq!(count(&chunk, [Some(&neighbor); 6]), (0, 0));
+
+        // A single cube, buried
+        let mut chunk = Chunk::new(vec3(0, 0, 0), &registry);
+        let center = vec3(10, 10, 10);
+
+        chunk.set(center, BlockStone);
+        for &dir in &Direction::ALL
+        {
+            chunk.set((center.map(|n| n as i32) + dir.offset()).map(|n| n as usize), BlockStone);
+        }
+        let mesh = mesh_chunk(&ChunkView::new(&chunk), &TextureLayers::new());
+
+        // Only the outside faces of its neighbors, none of which touch it
+        assert_eq!(mesh.vertices.len(), 6 * 5 * 4);
+        assert!(mesh.vertices.chunks(4).all(|face|
+        {
+            let centroid = face.iter().map(|v| Vec3::from(v.position)).sum::<Vec3<f32>>() / 4.0;
+
+            centroid.distance(center.map(|n| n as f32 + 0.5)) > 0.5
+        }));
+    }
+
+    #[test]
+    fn seams()
+    {
+        let registry = registry();
+        let mut chunk = Chunk::new(vec3(0, 0, 0), &registry);
+        let mut east = Chunk::new(vec3(1, 0, 0), &registry);
+
+        chunk.set(vec3(31, 0, 0), BlockStone);
+        assert_eq!(count(&chunk, [None; 6]).0, 24);
+
+        // The block across the seam isn't there yet
+        let view = ChunkView::new(&chunk).with_neighbor(Direction::East, &east);
+        assert_eq!(mesh_chunk(&view, &TextureLayers::new()).vertices.len(), 24);
+        assert!(view.get(vec3(32, 0, 0)).unwrap().is::<BlockAir>());
+        assert!(view.get(vec3(-1, 0, 0)).is_none());
+        assert!(view.get(vec3(32, -1, 0)).is_none());
+
+        east.set(vec3(0, 0, 0), BlockStone);
+        let view = ChunkView::new(&chunk).with_neighbor(Direction::East, &east);
+        assert_eq!(mesh_chunk(&view, &TextureLayers::new()).vertices.len(), 20);
+    }
+
+    #[test]
+    fn faces()
+    {
+        let mut chunk = Chunk::new(vec3(0, 0, 0), &registry());
+        let mut textures = TextureLayers::new();
+
+        textures.insert("stone", None, 3);
+        textures.insert("stone", Some(Direction::Up), 7);
+        chunk.set(vec3(0, 0, 0), BlockStone);
+
+        let mesh = mesh_chunk(&ChunkView::new(&chunk), &textures);
+
+        for face in mesh.vertices.chunks(4)
+        {
+            let normal = Vec3::<f32>::from(face[0].normal);
+            let expected = if normal == Vec3::unit_y() { 7 } else { 3 };
+
+            // Flat, facing outwards, and wound counter-clockwise
+            assert!(face.iter().all(|v| v.normal == face[0].normal && v.layer == expected));
+            let [a, b, c] = [0, 1, 2].map(|i| Vec3::<f32>::from(face[i].position));
+            assert_eq!((b - a).cross(c - a).normalized(), normal);
+            assert!(face.iter().all(|v| Vec3::from(v.position).dot(normal) == normal.sum().max(0.0)));
+        }
+        assert_eq!(textures.get("dirt", Direction::Up), TextureLayers::MISSING);
+    }
+
+    /// Get the total area of the faces of `mesh`
+    fn area(mesh: &ChunkMesh) -> f32
+    {
+        mesh.vertices
+            .chunks(4)
+            .map(|face|
+            {
+                let [a, b, d] = [0, 1, 3].map(|i| Vec3::<f32>::from(face[i].position));
+
+                (b - a).cross(d - a).magnitude()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn greedy()
+    {
+        let registry = registry();
+        let mut chunk = Chunk::new(vec3(0, 0, 0), &registry);
+
+        chunk.fill_region(vec3(0, 7, 0), vec3(31, 7, 31), BlockStone);
+
+        let view = ChunkView::new(&chunk);
+        let naive = mesh_chunk(&view, &TextureLayers::new());
+        let greedy = mesh_chunk_greedy(&view, &TextureLayers::new(), |_, _| ());
+
+        // Top and bottom, then the four sides of the rim
+        assert_eq!((greedy.vertices.len(), greedy.indices.len()), (6 * 4, 6 * 6));
+        assert_eq!(naive.vertices.len(), (2 * 32 * 32 + 4 * 32) * 4);
+        assert_eq!(area(&greedy), area(&naive));
+
+        for face in greedy.vertices.chunks(4)
+        {
+            let normal = Vec3::<f32>::from(face[0].normal);
+            let [a, b, c] = [0, 1, 2].map(|i| Vec3::<f32>::from(face[i].position));
+
+            // Still wound counter-clockwise, and tiled once per block
+            assert_eq!((b - a).cross(c - a).normalized(), normal);
+            assert_eq!(face[1].uv[0] * face[1].uv[1], (b - a).magnitude() * (c - b).magnitude());
+        }
+        let top = greedy.vertices
+            .chunks(4)
+            .find(|face| face[0].normal == [0.0, 1.0, 0.0])
+            .unwrap();
+        assert!(top.iter().all(|v| v.position[1] == 8.0));
+        assert_eq!(top.iter().map(|v| v.position[0]).fold(0.0, f32::max), 32.0);
+    }
+
+    #[test]
+    fn greedy_keys()
+    {
+        let registry = registry();
+        let mut chunk = Chunk::new(vec3(0, 0, 0), &registry);
+        let mut textures = TextureLayers::new();
+
+        chunk.fill_region(vec3(0, 0, 0), vec3(31, 0, 31), BlockStone);
+        textures.insert("stone", Some(Direction::Down), 2);
+
+        let view = ChunkView::new(&chunk);
+        let naive = mesh_chunk(&view, &textures);
+
+        // Split in halves along `X`, but only on top
+        let greedy = mesh_chunk_greedy(&view, &textures, |pos, dir| dir == Direction::Up && pos.x < 16);
+        assert_eq!(greedy.vertices.len(), 7 * 4);
+        assert_eq!(area(&greedy), area(&naive));
+
+        // Layers never merge, so the water's top and bottom stay apart from the stone's
+        textures.insert("water", None, 5);
+        chunk.set(vec3(5, 0, 5), BlockWater { level: 0, falling: false });
+        let view = ChunkView::new(&chunk);
+        let greedy = mesh_chunk_greedy(&view, &textures, |_, _| ());
+
+        assert_eq!(greedy.vertices.iter().filter(|v| v.layer == 5).count(), 2 * 4);
+        assert!(greedy.vertices.chunks(4).all(|face| face.iter().all(|v| v.layer == face[0].layer)));
+        assert_eq!(area(&greedy), area(&mesh_chunk(&view, &textures)));
+    }
+
+    /// Drain `worker` until every position submitted surfaces, uploading nothing
+    fn drain_all(worker: &mut MeshWorker) -> Vec<(Vec3<i32>, ChunkMesh)>
+    {
+        let mut meshes = Vec::new();
+
+        while worker.pending() != 0
+        {
+            meshes.extend(worker.drain(|_, mesh| mesh));
+            thread::yield_now();
+        }
+        meshes
+    }
+
+    #[test]
+    fn worker()
+    {
+        let registry = registry();
+        let shared = |pos: Vec3<usize>|
+        {
+            let mut chunk = Chunk::new(vec3(0, 0, 0), &registry);
+
+            chunk.set(pos, BlockStone);
+            Arc::new(RwLock::new(chunk))
+        };
+        let east = Direction::from_normal(vec3(1, 0, 0)).unwrap();
+        let mut neighbors = [None, None, None, None, None, None];
+        neighbors[east as usize] = Some(shared(vec3(0, 5, 5)));
+
+        let mut worker = MeshWorker::new(Arc::new(TextureLayers::new()));
+        worker.submit(vec3(0, 0, 0), shared(vec3(31, 5, 5)), neighbors);
+        worker.submit(vec3(1, 0, 0), shared(vec3(31, 5, 5)), Default::default());
+        assert_eq!(worker.pending(), 2);
+
+        let mut meshes = drain_all(&mut worker);
+        meshes.sort_unstable_by_key(|(pos, _)| pos.x);
+
+        // The neighbor hides a face across the seam
+        assert_eq!(meshes.len(), 2);
+        assert_eq!(meshes[0].0, vec3(0, 0, 0));
+        assert_eq!(meshes[0].1.vertices.len(), 5 * 4);
+        assert_eq!(meshes[1].0, vec3(1, 0, 0));
+        assert_eq!(meshes[1].1.vertices.len(), 6 * 4);
+        assert_eq!(worker.drain(|_, mesh| mesh).len(), 0);
+
+        // A slab of faces that only merge when greedy
+        let slab = Arc::new(RwLock::new(Chunk::new(vec3(0, 0, 0), &registry)));
+        for (x, z) in (0..4).flat_map(|x| (0..4).map(move |z| (x, z)))
+        {
+            slab.write().set(vec3(x, 0, z), BlockStone);
+        }
+        assert!(worker.is_greedy());
+        worker.submit(vec3(0, 0, 0), Arc::clone(&slab), Default::default());
+        worker.set_greedy(false);
+        worker.submit(vec3(1, 0, 0), slab, Default::default());
+
+        let mut meshes = drain_all(&mut worker);
+        meshes.sort_unstable_by_key(|(pos, _)| pos.x);
+
+        assert_eq!(meshes[0].1.vertices.len(), 6 * 4);
+        assert_eq!(meshes[1].1.vertices.len(), (2 * 16 + 4 * 4) * 4);
+    }
+
+    #[test]
+    fn worker_supersede()
+    {
+        let registry = registry();
+        // `n` separate cubes, which never merge
+        let shared = |n: usize|
+        {
+            let mut chunk = Chunk::new(vec3(0, 0, 0), &registry);
+
+            for i in 0..n
+            {
+                chunk.set(vec3(2 * i, 0, 0), BlockStone);
+            }
+            Arc::new(RwLock::new(chunk))
+        };
+        let mut worker = MeshWorker::new(Arc::new(TextureLayers::new()));
+        let mut uploads = 0;
+
+        // Superseded before or while they're built
+        for n in 1..=16
+        {
+            worker.submit(vec3(0, 0, 0), shared(n), Default::default());
+        }
+        worker.submit(vec3(0, 1, 0), shared(1), Default::default());
+        assert_eq!(worker.pending(), 2);
+
+        let mut meshes = Vec::new();
+        while worker.pending() != 0
+        {
+            meshes.extend(worker.drain(|_, mesh| { uploads += 1; mesh.vertices.len() }));
+        }
+        meshes.sort_unstable_by_key(|(pos, _)| pos.y);
+
+        assert_eq!(uploads, 2);
+        assert_eq!(meshes, vec![(vec3(0, 0, 0), 16 * 24), (vec3(0, 1, 0), 24)]);
+
+        // Superseded after being built, but before being drained
+        worker.submit(vec3(0, 0, 0), shared(2), Default::default());
+        thread::sleep(Duration::from_millis(100));
+        worker.submit(vec3(0, 0, 0), shared(3), Default::default());
+
+        let meshes = drain_all(&mut worker);
+
+        assert_eq!(meshes.len(), 1);
+        assert_eq!(meshes[0].1.vertices.len(), 3 * 24);
+        assert_eq!(worker.drain(|_, mesh| mesh).len(), 0);
+    }
+}
@@ -0,0 +1,439 @@
+use std::borrow::Cow;
+
+/// Identifier of a type of item, ie. `"coal"`. Usually a string literal, but
+/// can also be created at runtime(ie. when read from disk)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ItemId(Cow<'static, str>);
+
+/// A number of items of the same type, as held in an inventory slot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemStack
+{
+    /// Type of item
+    pub id: ItemId,
+    /// Number of items in this stack, never `0` nor over [ItemStack::MAX_COUNT]
+    pub count: u8,
+}
+
+/// A fixed number of slots, each holding an [ItemStack] or nothing. Not tied to
+/// blocks, so anything can carry one.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Inventory
+{
+    /// This inventory's slots, in order
+    slots: Vec<Option<ItemStack>>,
+}
+
+impl ItemId
+{
+    /// Maximum length of an identifier, in bytes
+    pub const MAX_LEN: usize = u8::MAX as usize;
+
+    /// Create an item identifier from a string literal. Panics if it's empty or longer
+    /// than [ItemId::MAX_LEN] bytes.
+    pub const fn new(id: &'static str) -> Self
+    {
+        assert!(Self::valid(id), "item identifiers must be 1..=255 bytes long");
+
+        Self(Cow::Borrowed(id))
+    }
+
+    /// Get this identifier as a string
+    pub fn as_str(&self) -> &str
+    {
+        &self.0
+    }
+
+    /// Whether `id` is a valid identifier, ie. one that fits in [ItemStack::serialize_slot]
+    const fn valid(id: &str) -> bool
+    {
+        !id.is_empty() && id.len() <= Self::MAX_LEN
+    }
+}
+
+impl From<&'static str> for ItemId
+{
+    fn from(id: &'static str) -> Self
+    {
+        Self::new(id)
+    }
+}
+
+impl From<String> for ItemId
+{
+    /// Panics if `id` is empty or longer than [ItemId::MAX_LEN] bytes, see [ItemId::new]
+    fn from(id: String) -> Self
+    {
+        assert!(Self::valid(&id), "item identifiers must be 1..=255 bytes long, got {:?}", id);
+
+        Self(Cow::Owned(id))
+    }
+}
+
+impl std::fmt::Display for ItemId
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.write_str(&self.0)
+    }
+}
+
+impl ItemStack
+{
+    /// Maximum number of items in a stack
+    pub const MAX_COUNT: u8 = 64;
+
+    /// Create a stack of `count` items of type `id`. Panics if `count` is `0` or
+    /// over [ItemStack::MAX_COUNT].
+    pub fn new(id: impl Into<ItemId>, count: u8) -> Self
+    {
+        assert!(count != 0, "item stacks can't be empty");
+        assert!(count <= Self::MAX_COUNT, "item stacks hold at most {} items", Self::MAX_COUNT);
+
+        Self { id: id.into(), count }
+    }
+
+    /// Move as many items from `other` into this stack as fit under [ItemStack::MAX_COUNT],
+    /// returning what's left of `other`. Stacks of different types don't merge.
+    pub fn merge(&mut self, mut other: ItemStack) -> Option<ItemStack>
+    {
+        if other.id != self.id
+        {
+            return Some(other)
+        }
+        let moved = other.count.min(Self::MAX_COUNT.saturating_sub(self.count));
+
+        self.count += moved;
+        other.count -= moved;
+        match other.count
+        {
+            0 => None,
+            _ => Some(other),
+        }
+    }
+
+    /// Put as much of `stack` into `slot` as fits, merging with what it holds or
+    /// filling it if empty, and return what's left. Empty stacks are dropped rather
+    /// than left in the slot.
+    pub fn insert_into(slot: &mut Option<ItemStack>, mut stack: ItemStack) -> Option<ItemStack>
+    {
+        if stack.count == 0
+        {
+            return None
+        }
+        match slot
+        {
+            Some(held) => held.merge(stack),
+            None =>
+            {
+                let count = stack.count.min(Self::MAX_COUNT);
+
+                stack.count -= count;
+                *slot = Some(ItemStack::new(stack.id.clone(), count));
+                Some(stack).filter(|rest| rest.count != 0)
+            },
+        }
+    }
+
+    /// Remove one item from the stack in `slot`, emptying it if it was the last,
+    /// and return that item
+    pub fn take_one(slot: &mut Option<ItemStack>) -> Option<ItemStack>
+    {
+        let stack = slot.as_mut()?;
+        let one = ItemStack::new(stack.id.clone(), 1);
+
+        stack.count -= 1;
+        if stack.count == 0
+        {
+            *slot = None;
+        }
+        Some(one)
+    }
+
+    /// Write the contents of an inventory slot.
+    /// ```text
+    /// len: u8             // 0 if empty
+    /// id: [u8; len]
+    /// count: u8           // if not empty
+    /// ```
+    pub fn serialize_slot(slot: &Option<ItemStack>, out: &mut Vec<u8>)
+    {
+        match slot
+        {
+            Some(stack) =>
+            {
+                // `ItemId`s are always 1..=255 bytes long, so `0` is left for empty slots
+                let id = stack.id.as_str().as_bytes();
+
+                out.push(id.len() as u8);
+                out.extend_from_slice(id);
+                out.push(stack.count);
+            },
+            None => out.push(0),
+        }
+    }
+
+    /// Read the contents of an inventory slot written by [ItemStack::serialize_slot]
+    /// from the front of `bytes`, advancing past it. Returns `None` if invalid.
+    pub fn deserialize_slot(bytes: &mut &[u8]) -> Option<Option<ItemStack>>
+    {
+        let (&len, rest) = bytes.split_first()?;
+        if len == 0
+        {
+            *bytes = rest;
+            return Some(None)
+        }
+        if rest.len() < len as usize + 1
+        {
+            return None
+        }
+        let (id, rest) = rest.split_at(len as usize);
+        let (&count, rest) = rest.split_first()?;
+
+        if count == 0 || count > Self::MAX_COUNT
+        {
+            return None
+        }
+        *bytes = rest;
+        Some(Some(ItemStack::new(String::from_utf8(id.to_vec()).ok()?, count)))
+    }
+}
+
+impl Inventory
+{
+    /// Create an inventory with `size` empty slots
+    pub fn new(size: usize) -> Self
+    {
+        Self { slots: vec![None; size] }
+    }
+
+    /// Get this inventory's slots, in order
+    pub fn slots(&self) -> &[Option<ItemStack>]
+    {
+        &self.slots
+    }
+
+    /// Get the number of slots in this inventory
+    pub fn len(&self) -> usize
+    {
+        self.slots.len()
+    }
+
+    /// Whether this inventory has no slots at all
+    pub fn is_empty(&self) -> bool
+    {
+        self.slots.is_empty()
+    }
+
+    /// Get the stack in the `idx`th slot, if any
+    pub fn get(&self, idx: usize) -> Option<&ItemStack>
+    {
+        self.slots.get(idx)?.as_ref()
+    }
+
+    /// Add `stack` to this inventory, topping off stacks of the same type first
+    /// then filling empty slots in order. Returns whatever didn't fit.
+    pub fn insert(&mut self, stack: ItemStack) -> Option<ItemStack>
+    {
+        // Empty stacks are dropped rather than taking up a slot
+        let mut stack = Some(stack).filter(|stack| stack.count != 0);
+
+        for held in self.slots.iter_mut().flatten()
+        {
+            stack = held.merge(stack?);
+        }
+        for slot in self.slots.iter_mut().filter(|slot| slot.is_none())
+        {
+            stack = ItemStack::insert_into(slot, stack?);
+        }
+        stack
+    }
+
+    /// Take the stack out of the `idx`th slot, if any
+    pub fn take(&mut self, idx: usize) -> Option<ItemStack>
+    {
+        self.slots.get_mut(idx)?.take()
+    }
+
+    /// Move as many stacks from `other` into this inventory as fit, see [Inventory::insert].
+    /// Whatever didn't fit is left in `other`.
+    pub fn merge(&mut self, other: &mut Inventory)
+    {
+        for slot in &mut other.slots
+        {
+            if let Some(stack) = slot.take()
+            {
+                *slot = self.insert(stack);
+            }
+        }
+    }
+
+    /// Write this inventory's slots.
+    /// ```text
+    /// len: u16
+    /// slots: [slot; len] // see `ItemStack::serialize_slot`
+    /// ```
+    pub fn serialize(&self, out: &mut Vec<u8>)
+    {
+        // Inventories are never this big
+        let len = self.slots.len().min(u16::MAX as usize);
+
+        out.extend_from_slice(&(len as u16).to_le_bytes());
+        for slot in &self.slots[..len]
+        {
+            ItemStack::serialize_slot(slot, out);
+        }
+    }
+
+    /// Read an inventory written by [Inventory::serialize] from the front of `bytes`,
+    /// advancing past it. Returns `None` if invalid.
+    pub fn deserialize(bytes: &mut &[u8]) -> Option<Self>
+    {
+        if bytes.len() < 2
+        {
+            return None
+        }
+        let (len, mut rest) = bytes.split_at(2);
+        let len = u16::from_le_bytes([len[0], len[1]]);
+
+        let slots = (0..len)
+            .map(|_| ItemStack::deserialize_slot(&mut rest))
+            .collect::<Option<_>>()?;
+
+        *bytes = rest;
+        Some(Self { slots })
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use super::{ Inventory, ItemStack, ItemId };
+
+    #[test]
+    fn merge()
+    {
+        let mut stack = ItemStack::new("coal", 60);
+
+        assert_eq!(stack.merge(ItemStack::new("stick", 1)), Some(ItemStack::new("stick", 1)));
+        assert_eq!(stack.merge(ItemStack::new("coal", 3)), None);
+        assert_eq!(stack.merge(ItemStack::new(String::from("coal"), 3)), Some(ItemStack::new("coal", 2)));
+        assert_eq!(stack, ItemStack::new("coal", ItemStack::MAX_COUNT));
+        assert_eq!(ItemId::new("coal"), ItemId::from(String::from("coal")));
+
+        let mut slot = Some(ItemStack::new("coal", 2));
+
+        assert_eq!(ItemStack::take_one(&mut slot), Some(ItemStack::new("coal", 1)));
+        assert_eq!(ItemStack::take_one(&mut slot), Some(ItemStack::new("coal", 1)));
+        assert_eq!(ItemStack::take_one(&mut slot), None);
+    }
+
+    #[test]
+    fn slot_ids()
+    {
+        // Built at runtime, at the longest they can be
+        let id = ItemId::from("é".repeat(ItemId::MAX_LEN / 2) + "s");
+        let slots = [Some(ItemStack::new(id, 3)), None, Some(ItemStack::new(String::from("s"), 1))];
+
+        let mut bytes = Vec::new();
+        for slot in &slots
+        {
+            ItemStack::serialize_slot(slot, &mut bytes);
+        }
+        assert_eq!(bytes[0] as usize, ItemId::MAX_LEN);
+
+        let mut rest = &bytes[..];
+        for slot in &slots
+        {
+            assert_eq!(ItemStack::deserialize_slot(&mut rest).as_ref(), Some(slot));
+        }
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "1..=255 bytes")]
+    fn empty_id()
+    {
+        let _ = ItemId::from(String::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "1..=255 bytes")]
+    fn long_id()
+    {
+        let _ = ItemId::from("a".repeat(ItemId::MAX_LEN + 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "can't be empty")]
+    fn empty_stack()
+    {
+        ItemStack::new("coal", 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 64 items")]
+    fn full_stack()
+    {
+        let _ = ItemStack::new("coal", 200);
+    }
+
+    #[test]
+    fn insert_into()
+    {
+        let mut slot = None;
+
+        assert_eq!(ItemStack::insert_into(&mut slot, ItemStack { id: "coal".into(), count: 0 }), None);
+        assert_eq!(slot, None);
+        assert_eq!(ItemStack::insert_into(&mut slot, ItemStack { id: "coal".into(), count: 100 }), Some(ItemStack::new("coal", 36)));
+        assert_eq!(slot, Some(ItemStack::new("coal", 64)));
+        assert_eq!(ItemStack::insert_into(&mut slot, ItemStack::new("stick", 1)), Some(ItemStack::new("stick", 1)));
+
+        // over-full stacks aren't read back
+        assert_eq!(ItemStack::deserialize_slot(&mut &[4, b'c', b'o', b'a', b'l', 65][..]), None);
+        assert!(ItemStack::deserialize_slot(&mut &[4, b'c', b'o', b'a', b'l', 64][..]).is_some());
+    }
+
+    #[test]
+    fn inventory()
+    {
+        let mut inv = Inventory::new(3);
+
+        assert_eq!(inv.insert(ItemStack::new("dirt", 40)), None);
+        assert_eq!(inv.insert(ItemStack::new("stone", 1)), None);
+        // empty stacks don't take up a slot
+        assert_eq!(inv.insert(ItemStack { id: "sand".into(), count: 0 }), None);
+        // tops off the first stack, then overflows into the empty slot
+        assert_eq!(inv.insert(ItemStack::new("dirt", 40)), None);
+        assert_eq!(inv.slots(), &[
+            Some(ItemStack::new("dirt", 64)),
+            Some(ItemStack::new("stone", 1)),
+            Some(ItemStack::new("dirt", 16)),
+        ]);
+        assert_eq!(inv.insert(ItemStack::new("dirt", 50)), Some(ItemStack::new("dirt", 2)));
+        assert_eq!(inv.insert(ItemStack::new("sand", 1)), Some(ItemStack::new("sand", 1)));
+
+        assert_eq!(inv.take(1), Some(ItemStack::new("stone", 1)));
+        assert_eq!(inv.take(1), None);
+        assert_eq!(inv.take(3), None);
+
+        let mut other = Inventory::new(2);
+
+        other.insert(ItemStack::new("sand", 5));
+        other.insert(ItemStack::new("gravel", 5));
+        inv.merge(&mut other);
+        assert_eq!(inv.get(1), Some(&ItemStack::new("sand", 5)));
+        assert_eq!(other.slots(), &[None, Some(ItemStack::new("gravel", 5))]);
+
+        // serde
+        let mut bytes = Vec::new();
+
+        inv.serialize(&mut bytes);
+        bytes.push(42);
+
+        let mut rest = &bytes[..];
+        assert_eq!(Inventory::deserialize(&mut rest), Some(inv));
+        assert_eq!(rest, &[42]);
+        assert_eq!(Inventory::deserialize(&mut &bytes[..bytes.len() - 2]), None);
+    }
+}
\ No newline at end of file
@@ -1,5 +1,6 @@
 pub mod world;
 pub mod math;
 pub mod util;
+pub mod item;
 
 pub mod vanilla;
\ No newline at end of file
@@ -0,0 +1,227 @@
+use std::ops::{ Add, Sub };
+
+use crate::math::{ Direction, Vec3 };
+
+/// An axis-aligned bounding box, ie. a block's collision shape in its cell's space
+/// or an entity's in world space. Boxes that only touch don't overlap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Aabb<T>
+{
+    /// Corner with the smallest coordinates
+    pub min: Vec3<T>,
+    /// Corner with the largest coordinates
+    pub max: Vec3<T>,
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Aabb<T>
+{
+    /// Whether this box and `other` overlap, which isn't the case if they're only
+    /// touching
+    pub fn intersects(&self, other: &Self) -> bool
+    {
+        (0..3).all(|axis| self.min[axis] < other.max[axis] && other.min[axis] < self.max[axis])
+    }
+
+    /// Get the smallest box containing both this box and `other`
+    pub fn union(self, other: Self) -> Self
+    {
+        Self
+        {
+            min: self.min.map2(other.min, |a, b| if b < a { b } else { a }),
+            max: self.max.map2(other.max, |a, b| if b > a { b } else { a }),
+        }
+    }
+
+    /// Get this box moved by `offset`, ie. from a block's cell to world space
+    pub fn translated(self, offset: Vec3<T>) -> Self
+    {
+        Self { min: self.min + offset, max: self.max + offset }
+    }
+
+    /// Get this box grown by `amount` on both sides of every axis
+    pub fn expand(self, amount: Vec3<T>) -> Self
+    {
+        Self { min: self.min - amount, max: self.max + amount }
+    }
+}
+
+impl Aabb<f32>
+{
+    /// Get the distance along the ray from `origin` along `dir`, in multiples of
+    /// `dir`, to where it enters this box along with the face it enters through.
+    /// A ray starting inside hits at `0`, through the face it would've entered
+    /// from. Rays that only graze this box, or with a zero `dir`, don't hit it.
+    pub fn ray_intersect(&self, origin: Vec3<f32>, dir: Vec3<f32>) -> Option<(f32, Direction)>
+    {
+        // Slab method: intersect the ranges along the ray within each axis' bounds
+        let mut enter = None::<(f32, usize, i32)>;
+        let mut exit = f32::INFINITY;
+
+        for axis in 0..3
+        {
+            if dir[axis] == 0.0
+            {
+                // Never crosses this axis' bounds
+                if origin[axis] <= self.min[axis] || origin[axis] >= self.max[axis]
+                {
+                    return None
+                }
+                continue;
+            }
+            let (near, far, normal) = match dir[axis] > 0.0
+            {
+                true => ((self.min[axis] - origin[axis]) / dir[axis], (self.max[axis] - origin[axis]) / dir[axis], -1),
+                false => ((self.max[axis] - origin[axis]) / dir[axis], (self.min[axis] - origin[axis]) / dir[axis], 1),
+            };
+
+            if enter.is_none_or(|(t, ..)| near > t)
+            {
+                enter = Some((near, axis, normal));
+            }
+            exit = exit.min(far);
+        }
+
+        let (t, axis, normal) = enter?;
+
+        if t >= exit || exit <= 0.0
+        {
+            return None
+        }
+
+        let mut face = Vec3::zero();
+
+        face[axis] = normal;
+        Direction::from_normal(face).map(|face| (t.max(0.0), face))
+    }
+
+    /// Get the fraction of `velocity` this box can move by before running into
+    /// `other`, along with the face of `other` it runs into. Boxes already overlapping
+    /// collide at `0`, but those touching can slide along or away from each other.
+    pub fn swept_collision(&self, velocity: Vec3<f32>, other: &Self) -> Option<(f32, Direction)>
+    {
+        // Sweeping this box's minimum corner against `other` grown by this box's size
+        // is the same as sweeping this whole box against `other`
+        let target = Self { min: other.min - (self.max - self.min), max: other.max };
+
+        target
+            .ray_intersect(self.min, velocity)
+            .filter(|&(t, _)| t < 1.0)
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use super::Aabb;
+    use crate::math::{ Direction, Vec3, vec3 };
+
+    fn unit() -> Aabb<f32>
+    {
+        Aabb { min: Vec3::zero(), max: Vec3::one() }
+    }
+
+    #[test]
+    fn intersects()
+    {
+        let a = unit();
+
+        assert!(a.intersects(&a));
+        assert!(a.intersects(&a.translated(vec3(0.5, 0.5, -0.5))));
+        assert!(a.intersects(&Aabb { min: vec3(0.25, 0.25, 0.25), max: vec3(0.75, 0.75, 0.75) }));
+
+        // Touching faces, edges and corners
+        for &dir in &Direction::ALL
+        {
+            assert!(!a.intersects(&a.translated(dir.normal())));
+        }
+        assert!(!a.intersects(&a.translated(vec3(1.0, 1.0, 0.0))));
+        assert!(!a.intersects(&a.translated(vec3(-1.0, 1.0, 1.0))));
+        assert!(!a.intersects(&a.translated(vec3(2.0, 0.0, 0.0))));
+
+        let b = Aabb { min: vec3(0, 0, 0), max: vec3(2, 2, 2) };
+
+        assert!(b.intersects(&b.translated(vec3(1, 1, 1))));
+        assert!(!b.intersects(&b.translated(vec3(2, 0, 0))));
+    }
+
+    #[test]
+    fn union_expand()
+    {
+        let a = unit();
+        let b = a.translated(vec3(2.0, -1.0, 0.5));
+
+        assert_eq!(a.union(b), Aabb { min: vec3(0.0, -1.0, 0.0), max: vec3(3.0, 1.0, 1.5) });
+        assert_eq!(a.union(b), b.union(a));
+        assert_eq!(a.union(a), a);
+        assert_eq!(a.expand(vec3(0.5, 0.0, 1.0)), Aabb { min: vec3(-0.5, 0.0, -1.0), max: vec3(1.5, 1.0, 2.0) });
+    }
+
+    #[test]
+    fn ray_intersect()
+    {
+        let a = unit();
+
+        // Straight at each face
+        for &dir in &Direction::ALL
+        {
+            let origin = Vec3::broadcast(0.5) + dir.normal::<f32>() * 2.0;
+
+            assert_eq!(a.ray_intersect(origin, -dir.normal::<f32>()), Some((1.5, dir)));
+            assert_eq!(a.ray_intersect(origin, -dir.normal::<f32>() * 3.0), Some((0.5, dir)));
+            assert_eq!(a.ray_intersect(origin, dir.normal()), None);
+        }
+
+        // Diagonally, through the face entered last
+        assert_eq!(a.ray_intersect(vec3(-1.0, -0.5, 0.5), vec3(1.0, 1.0, 0.0)), Some((1.0, Direction::West)));
+        assert_eq!(a.ray_intersect(vec3(-0.5, -1.0, 0.5), vec3(1.0, 1.0, 0.0)), Some((1.0, Direction::Down)));
+        assert_eq!(a.ray_intersect(vec3(-1.0, -0.5, 0.5), vec3(1.0, -1.0, 0.0)), None);
+
+        // Starting inside
+        assert_eq!(a.ray_intersect(vec3(0.5, 0.5, 0.5), vec3(0.0, 0.0, 1.0)), Some((0.0, Direction::North)));
+        assert_eq!(a.ray_intersect(vec3(0.9, 0.5, 0.5), vec3(-1.0, 0.0, 0.0)), Some((0.0, Direction::East)));
+        // Starting on a face, heading in or out
+        assert_eq!(a.ray_intersect(vec3(0.0, 0.5, 0.5), vec3(1.0, 0.0, 0.0)), Some((0.0, Direction::West)));
+        assert_eq!(a.ray_intersect(vec3(0.0, 0.5, 0.5), vec3(-1.0, 0.0, 0.0)), None);
+
+        // Grazing a face or an edge
+        assert_eq!(a.ray_intersect(vec3(-1.0, 1.0, 0.5), vec3(1.0, 0.0, 0.0)), None);
+        assert_eq!(a.ray_intersect(vec3(-1.0, 0.0, 0.5), vec3(1.0, 1.0, 0.0)), None);
+        assert_eq!(a.ray_intersect(vec3(0.5, 0.5, 0.5), Vec3::zero()), None);
+    }
+
+    #[test]
+    fn swept_collision()
+    {
+        let player = Aabb { min: vec3(0.0, 0.0, 0.0), max: vec3(0.5, 2.0, 0.5) };
+
+        // Along each axis
+        for &dir in &Direction::ALL
+        {
+            let block = unit().translated(dir.normal::<f32>() * 3.0);
+            let velocity = dir.normal::<f32>() * 4.0;
+            // Gap between the player and the block along `dir`
+            let gap = match dir
+            {
+                Direction::East | Direction::South => 2.5,
+                Direction::Up => 1.0,
+                _ => 2.0,
+            };
+
+            assert_eq!(player.swept_collision(velocity, &block), Some((gap / 4.0, dir.opposite())), "{:?}", dir);
+            // Stops short, or heads away
+            assert_eq!(player.swept_collision(velocity * (gap / 4.0) * 0.5, &block), None, "{:?}", dir);
+            assert_eq!(player.swept_collision(-velocity, &block), None, "{:?}", dir);
+        }
+
+        // Standing on a block, walking along or jumping off it
+        let ground = unit().translated(vec3(0.0, -1.0, 0.0));
+
+        assert_eq!(player.swept_collision(vec3(0.3, 0.0, 0.2), &ground), None);
+        assert_eq!(player.swept_collision(vec3(0.0, 1.0, 0.0), &ground), None);
+        assert_eq!(player.swept_collision(vec3(0.2, -1.0, 0.0), &ground), Some((0.0, Direction::Up)));
+
+        // Already overlapping
+        assert_eq!(player.swept_collision(vec3(1.0, 0.0, 0.0), &unit()), Some((0.0, Direction::West)));
+        assert_eq!(player.swept_collision(vec3(-1.0, 0.0, 0.0), &unit()), Some((0.0, Direction::East)));
+    }
+}
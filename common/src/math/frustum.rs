@@ -0,0 +1,170 @@
+use crate::math::{ Aabb, Mat4, Vec3, Vec4 };
+
+/// The volume visible through a camera, bounded by six planes, ie. to cull chunks
+/// that can't be seen.
+///
+/// Frustums are extracted from view-projection matrices mapping to wgpu's clip
+/// space, where `-w <= x, y <= w` and `0 <= z <= w`. That holds for reversed-Z(near
+/// at `z = w`, far at `z = 0`) as much as for regular depth, and for infinite far
+/// planes too. OpenGL's `-w <= z <= w` isn't supported.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frustum
+{
+    /// Left, right, bottom, top, and both depth planes, as `(normal, distance)`
+    /// where points `p` with `normal.dot(p) + distance >= 0` are on the inside.
+    /// Normals aren't normalized, and are zero for planes at infinity.
+    planes: [Vec4<f32>; 6],
+}
+
+impl Frustum
+{
+    /// Extract the frustum of the view-projection matrix `m`, in whichever space
+    /// `m` transforms from(ie. world space). See [Frustum] for clip space conventions.
+    pub fn from_matrix(m: Mat4<f32>) -> Self
+    {
+        let [x, y, z, w] = m.into_row_arrays().map(Vec4::from);
+
+        Self
+        {
+            planes:
+            [
+                w + x,
+                w - x,
+                w + y,
+                w - y,
+                z,
+                w - z,
+            ],
+        }
+    }
+
+    /// Whether `point` is inside this frustum, or on its bounds
+    pub fn contains_point(&self, point: Vec3<f32>) -> bool
+    {
+        self.planes
+            .iter()
+            .all(|plane| distance(*plane, point) >= 0.0)
+    }
+
+    /// Whether any of `aabb` might be inside this frustum. This is conservative:
+    /// boxes near the frustum's corners may pass without being visible, but those
+    /// that fail never are.
+    pub fn intersects_aabb(&self, aabb: &Aabb<f32>) -> bool
+    {
+        self.planes.iter().all(|plane|
+        {
+            // The corner furthest along the plane's normal(p-vertex) is the last
+            // to leave its inside
+            let p = Vec3::new(plane.x, plane.y, plane.z)
+                .map3(aabb.min, aabb.max, |n, min, max| if n >= 0.0 { max } else { min });
+
+            distance(*plane, p) >= 0.0
+        })
+    }
+}
+
+/// Get the signed distance of `point` to `plane`, scaled by the length of its normal
+#[inline]
+fn distance(plane: Vec4<f32>, point: Vec3<f32>) -> f32
+{
+    plane.dot(Vec4::from_point(point))
+}
+
+#[cfg(test)]
+mod test
+{
+    use super::Frustum;
+    use crate::math::{ Aabb, Mat4, Vec3 };
+
+    /// Camera at `(0, 0, 5)` looking down `-Z`, 90 degrees vertically and
+    /// horizontally, from `0.1` to `100` units away
+    fn frustum(reversed_z: bool) -> Frustum
+    {
+        let mut proj = Mat4::perspective_rh_zo(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0);
+        if reversed_z
+        {
+            // `z = w - z`
+            proj = Mat4::new(
+                1.0, 0.0, 0.0, 0.0,
+                0.0, 1.0, 0.0, 0.0,
+                0.0, 0.0, -1.0, 1.0,
+                0.0, 0.0, 0.0, 1.0,
+            ) * proj;
+        }
+        let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, 5.0), Vec3::zero(), Vec3::unit_y());
+
+        Frustum::from_matrix(proj * view)
+    }
+
+    fn cube(center: Vec3<f32>) -> Aabb<f32>
+    {
+        Aabb { min: center - 0.5, max: center + 0.5 }
+    }
+
+    #[test]
+    fn contains_point()
+    {
+        for &reversed_z in &[false, true]
+        {
+            let frustum = frustum(reversed_z);
+
+            assert!(frustum.contains_point(Vec3::zero()));
+            assert!(frustum.contains_point(Vec3::new(0.0, 0.0, -90.0)));
+            assert!(frustum.contains_point(Vec3::new(4.0, -4.0, 0.0)));
+            // behind, too close or too far
+            assert!(!frustum.contains_point(Vec3::new(0.0, 0.0, 6.0)));
+            assert!(!frustum.contains_point(Vec3::new(0.0, 0.0, 4.95)));
+            assert!(!frustum.contains_point(Vec3::new(0.0, 0.0, -96.0)));
+            // off to the sides
+            assert!(!frustum.contains_point(Vec3::new(5.5, 0.0, 0.0)));
+            assert!(!frustum.contains_point(Vec3::new(0.0, -5.5, 0.0)));
+        }
+    }
+
+    #[test]
+    fn intersects_aabb()
+    {
+        for &reversed_z in &[false, true]
+        {
+            let frustum = frustum(reversed_z);
+
+            // straight ahead
+            assert!(frustum.intersects_aabb(&cube(Vec3::zero())));
+            assert!(frustum.intersects_aabb(&cube(Vec3::new(0.0, 0.0, -50.0))));
+            // around the camera
+            assert!(frustum.intersects_aabb(&cube(Vec3::new(0.0, 0.0, 5.0))));
+            // behind
+            assert!(!frustum.intersects_aabb(&cube(Vec3::new(0.0, 0.0, 7.0))));
+            assert!(!frustum.intersects_aabb(&cube(Vec3::new(3.0, 1.0, 20.0))));
+            // straddling the right, then the top planes
+            assert!(frustum.intersects_aabb(&cube(Vec3::new(5.2, 0.0, 0.0))));
+            assert!(frustum.intersects_aabb(&cube(Vec3::new(0.0, 10.3, -5.0))));
+            // just past them
+            assert!(!frustum.intersects_aabb(&cube(Vec3::new(6.5, 0.0, 0.0))));
+            assert!(!frustum.intersects_aabb(&cube(Vec3::new(0.0, 11.5, -5.0))));
+            // past the far plane
+            assert!(!frustum.intersects_aabb(&cube(Vec3::new(0.0, 0.0, -100.0))));
+            // large box containing the whole frustum
+            assert!(frustum.intersects_aabb(&Aabb { min: Vec3::broadcast(-500.0), max: Vec3::broadcast(500.0) }));
+        }
+    }
+
+    #[test]
+    fn infinite_far()
+    {
+        // reversed-Z, far plane at infinity: `z = near`, `w = -z`
+        let proj = Mat4::new(
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 0.1,
+            0.0, 0.0, -1.0, 0.0,
+        );
+        let frustum = Frustum::from_matrix(proj);
+
+        assert!(frustum.contains_point(Vec3::new(0.0, 0.0, -1e6)));
+        assert!(frustum.intersects_aabb(&cube(Vec3::new(0.0, 0.0, -1e6))));
+        assert!(!frustum.intersects_aabb(&cube(Vec3::new(0.0, 0.0, 2.0))));
+        assert!(!frustum.contains_point(Vec3::new(0.0, 0.0, -0.05)));
+        assert!(!frustum.contains_point(Vec3::new(-3.0, 0.0, -2.0)));
+    }
+}
@@ -0,0 +1,203 @@
+use crate::math::{ Direction, Vec3 };
+
+/// Iterator over the cells of a grid of unit cubes a ray passes through, in order,
+/// along with the face it enters each through and the distance along the ray to
+/// where it does. It never ends, so callers should bound it, ie. `take_while` the
+/// distance is within range.
+///
+/// The first cell is the one the ray starts in, at a distance of `0` and through
+/// the face it would've entered from along its main axis. Rays starting exactly on
+/// a cell boundary start in the cell they're heading into, and those running along
+/// a boundary belong to the cell on its positive side.
+#[derive(Debug, Clone)]
+pub struct GridRay
+{
+    /// Cell the ray is currently in
+    cell: Vec3<i32>,
+    /// Direction the ray steps in along each axis, `-1`, `0` or `1`
+    step: Vec3<i32>,
+    /// Distance along the ray to cross one cell, per axis
+    delta: Vec3<f32>,
+    /// Distance along the ray to the next cell boundary, per axis
+    next: Vec3<f32>,
+    /// Axis along which the ray last stepped into `cell`
+    axis: usize,
+    /// Distance along the ray to where it entered `cell`
+    distance: f32,
+}
+
+impl GridRay
+{
+    /// Create a ray from `origin` along `dir`, whose length doesn't matter. Returns
+    /// `None` if `dir` is zero.
+    pub fn new(origin: Vec3<f32>, dir: Vec3<f32>) -> Option<Self>
+    {
+        // Amanatides & Woo, "A Fast Voxel Traversal Algorithm for Ray Tracing"
+        let dir = dir.try_normalized()?;
+
+        let mut cell = origin.map(|n| n.floor() as i32);
+        let step = dir.map(|n| if n > 0.0 { 1 } else if n < 0.0 { -1 } else { 0 });
+        let delta = dir.map(|n| if n == 0.0 { f32::INFINITY } else { 1.0 / n.abs() });
+        let mut next = Vec3::<f32>::zero();
+
+        for axis in 0..3
+        {
+            next[axis] = match step[axis]
+            {
+                1 => (cell[axis] as f32 + 1.0 - origin[axis]) * delta[axis],
+                -1 =>
+                {
+                    // Already on the boundary, so the ray is really leaving the
+                    // cell below
+                    if origin[axis] == cell[axis] as f32
+                    {
+                        cell[axis] -= 1;
+                    }
+                    (origin[axis] - cell[axis] as f32) * delta[axis]
+                },
+                _ => f32::INFINITY,
+            };
+        }
+
+        let axis = (0..3)
+            .max_by(|&a, &b| dir[a].abs().total_cmp(&dir[b].abs()))
+            .unwrap();
+
+        Some(Self { cell, step, delta, next, axis, distance: 0.0 })
+    }
+}
+
+impl Iterator for GridRay
+{
+    type Item = (Vec3<i32>, Direction, f32);
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        // Entered through the face opposite to the last step
+        let mut normal = Vec3::zero();
+
+        normal[self.axis] = -self.step[self.axis];
+
+        let out = (self.cell, Direction::from_normal(normal)?, self.distance);
+
+        // Step to the closest boundary
+        self.axis = (0..3)
+            .min_by(|&a, &b| self.next[a].total_cmp(&self.next[b]))
+            .unwrap();
+        self.distance = self.next[self.axis];
+        self.cell[self.axis] += self.step[self.axis];
+        self.next[self.axis] += self.delta[self.axis];
+
+        Some(out)
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use super::GridRay;
+    use crate::math::{ Direction, Vec3, vec3 };
+
+    /// Cells and distances of the first `n` steps of a ray
+    fn walk(origin: Vec3<f32>, dir: Vec3<f32>, n: usize) -> Vec<(Vec3<i32>, Direction, f32)>
+    {
+        GridRay::new(origin, dir).unwrap().take(n).collect()
+    }
+
+    #[test]
+    fn axis_aligned()
+    {
+        assert_eq!(walk(vec3(0.5, 0.5, 0.5), vec3(1.0, 0.0, 0.0), 3), vec![
+            (vec3(0, 0, 0), Direction::West, 0.0),
+            (vec3(1, 0, 0), Direction::West, 0.5),
+            (vec3(2, 0, 0), Direction::West, 1.5),
+        ]);
+        assert_eq!(walk(vec3(0.25, 0.5, -0.5), vec3(0.0, -2.0, 0.0), 3), vec![
+            (vec3(0, 0, -1), Direction::Up, 0.0),
+            (vec3(0, -1, -1), Direction::Up, 0.5),
+            (vec3(0, -2, -1), Direction::Up, 1.5),
+        ]);
+        assert!(GridRay::new(vec3(0.5, 0.5, 0.5), Vec3::zero()).is_none());
+    }
+
+    #[test]
+    fn boundaries()
+    {
+        // Starting on a boundary, heading either way
+        assert_eq!(walk(vec3(1.0, 0.5, 0.5), vec3(1.0, 0.0, 0.0), 2), vec![
+            (vec3(1, 0, 0), Direction::West, 0.0),
+            (vec3(2, 0, 0), Direction::West, 1.0),
+        ]);
+        assert_eq!(walk(vec3(1.0, 0.5, 0.5), vec3(-1.0, 0.0, 0.0), 2), vec![
+            (vec3(0, 0, 0), Direction::East, 0.0),
+            (vec3(-1, 0, 0), Direction::East, 1.0),
+        ]);
+        // Running along a boundary
+        assert_eq!(walk(vec3(0.5, 1.0, 0.0), vec3(0.0, 0.0, -1.0), 2), vec![
+            (vec3(0, 1, -1), Direction::South, 0.0),
+            (vec3(0, 1, -2), Direction::South, 1.0),
+        ]);
+    }
+
+    #[test]
+    fn random_rays()
+    {
+        // xorshift64, so that the rays are the same on every run
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let mut random = move ||
+        {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 40) as f32 / (1u64 << 24) as f32 * 2.0 - 1.0
+        };
+
+        for _ in 0..500
+        {
+            let origin = vec3(random(), random(), random()) * 20.0;
+            // Some rays have a zero component
+            let mut dir = vec3(random(), random(), random());
+            if random() > 0.5
+            {
+                dir[(random().abs() * 2.99) as usize] = 0.0;
+            }
+            let Some(ray) = GridRay::new(origin, dir) else { continue };
+            let dir = dir.normalized();
+
+            let cells = ray
+                .take_while(|&(_, _, t)| t < 30.0)
+                .collect::<Vec<_>>();
+
+            for pair in cells.windows(2)
+            {
+                let (a, _, enter) = pair[0];
+                let (b, face, leave) = pair[1];
+
+                // Neighboring cells, entered through their shared face
+                assert_eq!(Direction::from_normal(a - b), Some(face));
+                assert!(leave >= enter);
+                // The ray is within each cell between entering and leaving it
+                if leave - enter > 1e-3
+                {
+                    let mid = origin + dir * (enter + leave) * 0.5;
+
+                    assert_eq!(mid.map(|n| n.floor() as i32), a, "ray from {} along {}", origin, dir);
+                }
+            }
+
+            // Every cell sampled along the line is visited, in order
+            let mut visited = 0;
+
+            for i in 0..2900
+            {
+                let sample = (origin + dir * (i as f32 * 0.01)).map(|n| n.floor() as i32);
+
+                while cells[visited].0 != sample
+                {
+                    visited += 1;
+                    assert!(visited < cells.len(), "ray from {} along {} skipped {}", origin, dir, sample);
+                }
+            }
+        }
+    }
+}
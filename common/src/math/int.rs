@@ -0,0 +1,158 @@
+//! Integer and interpolation helpers shared by world generation and indexing, so
+//! that rounding negative coordinates is done in exactly one place.
+
+use crate::world::Chunk;
+use crate::math::Vec3;
+
+/// Divide `a` by `b`, rounding towards negative infinity rather than towards zero
+/// like `a / b` does. Panics if `b` is zero.
+#[inline]
+pub const fn floor_div(a: i32, b: i32) -> i32
+{
+    let q = a / b;
+
+    // Truncated towards zero past the exact quotient
+    match a % b != 0 && (a < 0) != (b < 0)
+    {
+        true => q - 1,
+        false => q,
+    }
+}
+
+/// Remainder of [floor_div], which has the same sign as `b` unlike `a % b`. Panics
+/// if `b` is zero.
+#[inline]
+pub const fn floor_mod(a: i32, b: i32) -> i32
+{
+    a - floor_div(a, b) * b
+}
+
+/// Split the world coordinates `pos` into the position of the chunk that contains
+/// it(1 unit = 32 blocks) and its position local to that chunk. Unlike plain
+/// division, this rounds towards negative infinity so that negative coordinates
+/// land in the right chunk.
+#[inline]
+pub fn block_to_chunk(pos: Vec3<i32>) -> (Vec3<i32>, Vec3<usize>)
+{
+    const CHUNK_SIZE: i32 = <Chunk>::SIZE as i32;
+
+    (
+        pos.map(|n| floor_div(n, CHUNK_SIZE)),
+        pos.map(|n| floor_mod(n, CHUNK_SIZE) as usize),
+    )
+}
+
+/// Get the world coordinates of the minimum and maximum(inclusive) blocks of the
+/// chunk at the chunk position `chunk`, ie. to find which part of a box is in it.
+#[inline]
+pub fn chunk_block_range(chunk: Vec3<i32>) -> (Vec3<i32>, Vec3<i32>)
+{
+    const CHUNK_SIZE: i32 = <Chunk>::SIZE as i32;
+
+    let min = chunk * CHUNK_SIZE;
+
+    (min, min + (CHUNK_SIZE - 1))
+}
+
+/// Linearly interpolate from `a`(`t = 0`) to `b`(`t = 1`). `t` isn't clamped, so
+/// values outside of `[0, 1]` extrapolate.
+#[inline]
+pub fn lerp(a: f64, b: f64, t: f64) -> f64
+{
+    a + (b - a) * t
+}
+
+/// Smoothly step from `0` at `edge0` to `1` at `edge1`, with zero slope at both
+/// ends, clamping `x` to that range. `edge0` must be less than `edge1`.
+#[inline]
+pub fn smoothstep(edge0: f64, edge1: f64, x: f64) -> f64
+{
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+
+    t * t * (3.0 - 2.0 * t)
+}
+
+#[cfg(test)]
+mod test
+{
+    use super::{ floor_div, floor_mod, block_to_chunk, chunk_block_range, lerp, smoothstep };
+    use crate::math::vec3;
+
+    #[test]
+    fn floor_div_mod()
+    {
+        let cases =
+        [
+            (-1, 32, -1, 31),
+            (0, 32, 0, 0),
+            (31, 32, 0, 31),
+            (32, 32, 1, 0),
+            (-32, 32, -1, 0),
+            (-33, 32, -2, 31),
+            (-64, 32, -2, 0),
+            (7, -2, -4, -1),
+            (-7, -2, 3, -1),
+            (-8, -2, 4, 0),
+        ];
+        for &(a, b, div, rem) in &cases
+        {
+            assert_eq!(floor_div(a, b), div, "{} / {}", a, b);
+            assert_eq!(floor_mod(a, b), rem, "{} % {}", a, b);
+            assert_eq!(floor_div(a, b) * b + floor_mod(a, b), a);
+        }
+        assert_eq!(floor_div(i32::MIN, 32), i32::MIN / 32);
+        assert_eq!(floor_mod(i32::MAX, 32), 31);
+    }
+
+    #[test]
+    fn block_to_chunk_sweep()
+    {
+        // Slow, but obviously correct
+        fn reference(n: i32) -> (i32, usize)
+        {
+            let mut chunk = 0;
+
+            while n < chunk * 32 { chunk -= 1 }
+            while n >= (chunk + 1) * 32 { chunk += 1 }
+
+            (chunk, (n - chunk * 32) as usize)
+        }
+
+        for n in -200..200
+        {
+            let (cx, lx) = reference(n);
+            let (cy, ly) = reference(-n);
+
+            assert_eq!(block_to_chunk(vec3(n, -n, 5)), (vec3(cx, cy, 0), vec3(lx, ly, 5)));
+
+            // And back
+            let (min, max) = chunk_block_range(vec3(cx, cy, 0));
+
+            assert!(min.x <= n && n <= max.x && min.y <= -n && -n <= max.y);
+            assert_eq!(min + vec3(lx, ly, 5).map(|n| n as i32), vec3(n, -n, 5));
+        }
+    }
+
+    #[test]
+    fn chunk_block_range_bounds()
+    {
+        assert_eq!(chunk_block_range(vec3(0, 0, 0)), (vec3(0, 0, 0), vec3(31, 31, 31)));
+        assert_eq!(chunk_block_range(vec3(-1, 1, -2)), (vec3(-32, 32, -64), vec3(-1, 63, -33)));
+    }
+
+    #[test]
+    fn interpolation()
+    {
+        assert_eq!(lerp(2.0, 4.0, 0.0), 2.0);
+        assert_eq!(lerp(2.0, 4.0, 0.5), 3.0);
+        assert_eq!(lerp(2.0, 4.0, 1.0), 4.0);
+        assert_eq!(lerp(2.0, 4.0, -1.0), 0.0);
+
+        assert_eq!(smoothstep(-1.0, 1.0, -2.0), 0.0);
+        assert_eq!(smoothstep(-1.0, 1.0, -1.0), 0.0);
+        assert_eq!(smoothstep(-1.0, 1.0, 0.0), 0.5);
+        assert_eq!(smoothstep(-1.0, 1.0, 1.0), 1.0);
+        assert_eq!(smoothstep(-1.0, 1.0, 3.0), 1.0);
+        assert!(smoothstep(-1.0, 1.0, 0.5) > 0.5);
+    }
+}
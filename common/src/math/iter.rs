@@ -0,0 +1,327 @@
+//! Allocation-free iterators over the integer positions of regions of space, ie.
+//! to place structures or edit the world without nesting loops.
+
+use crate::math::{ Direction, Vec3 };
+
+/// Iterate over every position in the box between the corners `min` and `max`
+/// (inclusive), with `x` changing the fastest, then `y`, then `z`. Empty if `max`
+/// is less than `min` along any axis.
+pub fn positions_in_box(min: Vec3<i32>, max: Vec3<i32>) -> BoxPositions
+{
+    let size = (max - min + 1).map(|n| n.max(0) as u64);
+
+    BoxPositions { min, size, next: 0, end: size.product() }
+}
+
+/// Iterate over every position within `radius` of `center`, ie. whose euclidean
+/// distance to it is at most `radius`, in the order of [positions_in_box]. Empty
+/// if `radius` is negative.
+pub fn positions_in_sphere(center: Vec3<i32>, radius: f32) -> SpherePositions
+{
+    Sphere::new(center, radius).positions(false)
+}
+
+/// Iterate over the positions on the surface of the sphere of [positions_in_sphere],
+/// ie. those in it with at least one neighbor along an axis that isn't, such that the
+/// shell is watertight. Same order as [positions_in_box].
+pub fn sphere_shell(center: Vec3<i32>, radius: f32) -> SpherePositions
+{
+    Sphere::new(center, radius).positions(true)
+}
+
+/// Iterate over every position in the square spanning `radius` on every side of
+/// `center` along `x` and `z`, spiraling out from `center` one ring at a time. Each
+/// ring is further from `center`(along `x` or `z`, whichever is furthest) than the
+/// last, so positions come nearest first, ie. to load chunks around a player. `y` is
+/// left as `center`'s. Empty if `radius` is negative.
+pub fn spiral_xz(center: Vec3<i32>, radius: i32) -> Spiral
+{
+    let end = match radius
+    {
+        r if r < 0 => 0,
+        r => (2 * r as u64 + 1).pow(2),
+    };
+    Spiral { center, next: 0, end }
+}
+
+/// Iterator over the positions in a box, see [positions_in_box]
+#[derive(Debug, Clone)]
+pub struct BoxPositions
+{
+    /// Minimum corner of the box
+    min: Vec3<i32>,
+    /// Number of positions along each axis
+    size: Vec3<u64>,
+    /// Index of the next position, with `x` changing the fastest
+    next: u64,
+    /// Number of positions in the box
+    end: u64,
+}
+
+/// Iterator over the positions in a sphere or on its surface, see [positions_in_sphere]
+/// and [sphere_shell]
+#[derive(Debug, Clone)]
+pub struct SpherePositions
+{
+    /// The sphere being iterated
+    sphere: Sphere,
+    /// Positions in the sphere's bounding box
+    bounds: BoxPositions,
+    /// Whether only positions on the sphere's surface are yielded
+    shell: bool,
+}
+
+/// Iterator over the positions in a square, spiraling outwards. See [spiral_xz]
+#[derive(Debug, Clone)]
+pub struct Spiral
+{
+    /// Center of the spiral
+    center: Vec3<i32>,
+    /// Index of the next position, `0` being `center`
+    next: u64,
+    /// Number of positions in the square
+    end: u64,
+}
+
+/// A sphere of integer positions
+#[derive(Debug, Clone, Copy)]
+struct Sphere
+{
+    /// Center of the sphere
+    center: Vec3<i32>,
+    /// Radius of the sphere, negative if it's empty
+    radius: f32,
+}
+
+impl Iterator for BoxPositions
+{
+    type Item = Vec3<i32>;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        if self.next >= self.end
+        {
+            return None
+        }
+        let i = self.next;
+        let offset = Vec3::new(
+            i % self.size.x,
+            (i / self.size.x) % self.size.y,
+            i / (self.size.x * self.size.y),
+        );
+        self.next += 1;
+
+        Some(self.min + offset.map(|n| n as i32))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let len = (self.end - self.next) as usize;
+
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for BoxPositions { }
+
+impl Sphere
+{
+    fn new(center: Vec3<i32>, radius: f32) -> Self
+    {
+        Self { center, radius }
+    }
+
+    /// Iterate over the positions in this sphere, or only on its surface
+    fn positions(self, shell: bool) -> SpherePositions
+    {
+        // Positions are never further than the nearest integer below `radius`
+        let reach = self.radius.max(-1.0).floor() as i32;
+
+        SpherePositions
+        {
+            sphere: self,
+            bounds: positions_in_box(self.center - reach, self.center + reach),
+            shell,
+        }
+    }
+
+    /// Whether `pos` is within this sphere
+    #[inline]
+    fn contains(&self, pos: Vec3<i32>) -> bool
+    {
+        self.radius >= 0.0 && (pos - self.center).map(|n| n as f32).magnitude_squared() <= self.radius * self.radius
+    }
+}
+
+impl Iterator for SpherePositions
+{
+    type Item = Vec3<i32>;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        let sphere = self.sphere;
+        let shell = self.shell;
+
+        self.bounds.find(|&pos| sphere.contains(pos) && (!shell || Direction::ALL
+            .iter()
+            .any(|dir| !sphere.contains(pos + dir.offset()))))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        (0, self.bounds.size_hint().1)
+    }
+}
+
+impl Iterator for Spiral
+{
+    type Item = Vec3<i32>;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        if self.next >= self.end
+        {
+            return None
+        }
+        let i = self.next;
+        self.next += 1;
+
+        if i == 0
+        {
+            return Some(self.center)
+        }
+        // Ring `k` spans `(2k - 1)^2..(2k + 1)^2`, and has four sides of `2k` positions
+        let mut sqrt = (i as f64).sqrt() as u64;
+        // Rounding error
+        while sqrt * sqrt > i { sqrt -= 1 }
+        while (sqrt + 1) * (sqrt + 1) <= i { sqrt += 1 }
+
+        let k = sqrt.div_ceil(2);
+        let t = i - (2 * k - 1).pow(2);
+        let (side, t, k) = (t / (2 * k), (t % (2 * k)) as i32, k as i32);
+
+        let (x, z) = match side
+        {
+            0 => (k, -k + 1 + t),
+            1 => (k - 1 - t, k),
+            2 => (-k, k - 1 - t),
+            _ => (-k + 1 + t, -k),
+        };
+        Some(self.center + Vec3::new(x, 0, z))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let len = (self.end - self.next) as usize;
+
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Spiral { }
+
+#[cfg(test)]
+mod test
+{
+    use std::collections::HashSet;
+
+    use super::{ positions_in_box, positions_in_sphere, sphere_shell, spiral_xz };
+    use crate::math::{ Direction, Vec3, vec3 };
+
+    #[test]
+    fn box_positions()
+    {
+        let positions = positions_in_box(vec3(-1, 2, 0), vec3(1, 3, 0)).collect::<Vec<_>>();
+
+        assert_eq!(positions, vec![
+            vec3(-1, 2, 0), vec3(0, 2, 0), vec3(1, 2, 0),
+            vec3(-1, 3, 0), vec3(0, 3, 0), vec3(1, 3, 0),
+        ]);
+
+        let mut iter = positions_in_box(vec3(-4, -5, -6), vec3(5, 4, 3));
+
+        assert_eq!(iter.len(), 1000);
+        iter.next();
+        assert_eq!(iter.len(), 999);
+        assert_eq!(iter.collect::<HashSet<_>>().len(), 999);
+
+        assert_eq!(positions_in_box(vec3(0, 0, 0), vec3(0, 0, 0)).count(), 1);
+        assert_eq!(positions_in_box(vec3(0, 0, 0), vec3(3, -1, 3)).len(), 0);
+        assert_eq!(positions_in_box(vec3(0, 0, 0), vec3(3, -1, 3)).count(), 0);
+    }
+
+    #[test]
+    fn sphere_positions()
+    {
+        let center = vec3(3, -7, 12);
+
+        for &radius in &[8.0f32, 15.5, 20.0]
+        {
+            let positions = positions_in_sphere(center, radius).collect::<Vec<_>>();
+            let volume = 4.0 / 3.0 * std::f32::consts::PI * radius.powi(3);
+
+            assert!((positions.len() as f32 - volume).abs() / volume < 0.03, "{} for r = {}", positions.len(), radius);
+            assert!(positions
+                .iter()
+                .all(|pos| (pos - center).map(|n| n as f32).magnitude() <= radius));
+        }
+
+        assert_eq!(positions_in_sphere(center, 0.0).collect::<Vec<_>>(), vec![center]);
+        assert_eq!(positions_in_sphere(center, 0.99).count(), 1);
+        assert_eq!(positions_in_sphere(center, 1.0).count(), 7);
+        assert_eq!(positions_in_sphere(center, -1.0).count(), 0);
+        assert_eq!(positions_in_sphere(center, -0.5).count(), 0);
+    }
+
+    #[test]
+    fn shell()
+    {
+        let center = vec3(-2, 0, 5);
+
+        for &radius in &[6.0f32, 12.0, 17.5]
+        {
+            let inside = positions_in_sphere(center, radius).collect::<HashSet<_>>();
+            let shell = sphere_shell(center, radius).collect::<HashSet<_>>();
+            let area = 4.0 * std::f32::consts::PI * radius.powi(2);
+
+            // About one block per column along the axis the surface faces the most,
+            // which works out to ~0.8 blocks per unit of area
+            assert!((shell.len() as f32 - 0.8 * area).abs() / (0.8 * area) < 0.1, "{} for r = {}", shell.len(), radius);
+            assert!(shell.is_subset(&inside));
+
+            // Watertight: what's left is only ever next to the shell
+            for pos in inside.difference(&shell)
+            {
+                assert!(Direction::ALL.iter().all(|dir| inside.contains(&(pos + dir.offset()))));
+            }
+        }
+        assert_eq!(sphere_shell(center, 0.0).count(), 1);
+        assert_eq!(sphere_shell(center, 1.0).count(), 6);
+    }
+
+    #[test]
+    fn spiral()
+    {
+        let center = vec3(4, 9, -3);
+
+        for radius in 0..6
+        {
+            let iter = spiral_xz(center, radius);
+            let positions = iter.clone().collect::<Vec<_>>();
+            let ring = |pos: &Vec3<i32>| (pos - center).map(i32::abs).reduce_max();
+
+            assert_eq!(iter.len(), ((2 * radius + 1) * (2 * radius + 1)) as usize);
+            assert_eq!(positions.len(), iter.len());
+            assert_eq!(positions[0], center);
+            // Nearest first, each once, covering the square
+            assert!(positions.windows(2).all(|w| ring(&w[0]) <= ring(&w[1])));
+            assert_eq!(positions.iter().collect::<HashSet<_>>().len(), positions.len());
+            assert!(positions.iter().all(|pos| pos.y == center.y && ring(pos) <= radius));
+            // Continuous within each ring
+            assert!(positions.windows(2).all(|w| ring(&w[0]) != ring(&w[1]) || (w[1] - w[0]).map(i32::abs).sum() == 1));
+        }
+        assert_eq!(spiral_xz(center, -1).count(), 0);
+        assert_eq!(spiral_xz(center, 1).collect::<Vec<_>>()[1..3], [vec3(5, 9, -3), vec3(5, 9, -2)]);
+        assert_eq!(spiral_xz(Vec3::zero(), 1000).len(), 2001 * 2001);
+    }
+}
@@ -1,4 +1,14 @@
+mod aabb;
+mod frustum;
+mod grid_ray;
+pub mod iter;
+pub mod int;
+pub mod morton;
+
 pub use vek::*;
+pub use aabb::Aabb;
+pub use frustum::Frustum;
+pub use grid_ray::GridRay;
 
 /// Creates a new 2-dimensional vector
 #[inline]
@@ -30,4 +40,387 @@ pub enum Direction
     Up,
     /// `-Y` Direction
     Down,
-}
\ No newline at end of file
+}
+
+impl Direction
+{
+    /// All six directions, in declaration order(ie. indexed by `Direction as usize`)
+    pub const ALL: [Direction; 6] =
+    [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+        Direction::Up,
+        Direction::Down,
+    ];
+
+    /// The four directions along the horizontal plane, in declaration order
+    pub const HORIZONTAL: [Direction; 4] =
+    [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+    ];
+
+    /// Get this direction's identifier, ie. `"north"`, as used in commands and
+    /// config files. See [Direction]'s `FromStr` for the inverse.
+    pub const fn as_str(self) -> &'static str
+    {
+        match self
+        {
+            Direction::North => "north",
+            Direction::South => "south",
+            Direction::East => "east",
+            Direction::West => "west",
+            Direction::Up => "up",
+            Direction::Down => "down",
+        }
+    }
+
+    /// Iterate over the identifiers of all six directions, in declaration order,
+    /// ie. to list them in help text
+    pub fn variants() -> impl Iterator<Item = &'static str>
+    {
+        Direction::ALL.iter().map(|dir| dir.as_str())
+    }
+
+    /// Get the unit vector pointing in this direction
+    #[inline]
+    pub const fn offset(self) -> Vec3<i32>
+    {
+        match self
+        {
+            Direction::North => Vec3 { x: 0, y: 0, z: -1 },
+            Direction::South => Vec3 { x: 0, y: 0, z: 1 },
+            Direction::East => Vec3 { x: 1, y: 0, z: 0 },
+            Direction::West => Vec3 { x: -1, y: 0, z: 0 },
+            Direction::Up => Vec3 { x: 0, y: 1, z: 0 },
+            Direction::Down => Vec3 { x: 0, y: -1, z: 0 },
+        }
+    }
+
+    /// Get the unit vector pointing in this direction, of any signed numeric type. See
+    /// [Direction::offset] for the `const` equivalent.
+    #[inline]
+    pub fn normal<T: From<i8>>(self) -> Vec3<T>
+    {
+        self.offset().map(|n| T::from(n as i8))
+    }
+
+    /// Get the direction whose [unit vector](Direction::offset) is exactly `normal`,
+    /// if any
+    #[inline]
+    pub fn from_normal(normal: Vec3<i32>) -> Option<Self>
+    {
+        Direction::ALL
+            .iter()
+            .copied()
+            .find(|dir| dir.offset() == normal)
+    }
+
+    /// Get the direction closest to `v`, ie. a look vector, which is along the axis
+    /// of its largest component. Ties go to `X`, then `Y`, then `Z`, and zero components
+    /// count as positive(so a zero vector is `East`).
+    pub fn nearest(v: Vec3<f32>) -> Self
+    {
+        let abs = v.map(f32::abs);
+
+        if abs.x >= abs.y && abs.x >= abs.z
+        {
+            Direction::along(Axis::X, v.x)
+        }
+        else if abs.y >= abs.z
+        {
+            Direction::along(Axis::Y, v.y)
+        }
+        else
+        {
+            Direction::along(Axis::Z, v.z)
+        }
+    }
+
+    /// [Direction::nearest], ignoring `v`'s `Y` component, ie. to face blocks
+    /// along a look vector's yaw. Ties go to `X`.
+    pub fn horizontal_nearest(v: Vec3<f32>) -> Self
+    {
+        match v.x.abs() >= v.z.abs()
+        {
+            true => Direction::along(Axis::X, v.x),
+            false => Direction::along(Axis::Z, v.z),
+        }
+    }
+
+    /// Get the direction along `axis`, positive unless `n` is negative
+    fn along(axis: Axis, n: f32) -> Self
+    {
+        match (axis, n < 0.0)
+        {
+            (Axis::X, false) => Direction::East,
+            (Axis::X, true) => Direction::West,
+            (Axis::Y, false) => Direction::Up,
+            (Axis::Y, true) => Direction::Down,
+            (Axis::Z, false) => Direction::South,
+            (Axis::Z, true) => Direction::North,
+        }
+    }
+
+    /// Get the axis this direction points along
+    #[inline]
+    pub const fn axis(self) -> Axis
+    {
+        match self
+        {
+            Direction::North | Direction::South => Axis::Z,
+            Direction::East | Direction::West => Axis::X,
+            Direction::Up | Direction::Down => Axis::Y,
+        }
+    }
+
+    /// Get the axis of this direction's [normal](Direction::normal), the same as
+    /// [Direction::axis]
+    #[inline]
+    pub const fn normal_axis(self) -> Axis
+    {
+        self.axis()
+    }
+
+    /// Get the two directions `(u, v)` along the face pointing in this direction, ie.
+    /// to lay out a quad's corners while meshing. They form a right-handed basis with
+    /// this direction(`u × v = normal`) so that quads wind consistently, with `v`
+    /// pointing `Up` on the four horizontal faces.
+    #[inline]
+    pub const fn tangents(self) -> (Direction, Direction)
+    {
+        match self
+        {
+            Direction::North => (Direction::West, Direction::Up),
+            Direction::South => (Direction::East, Direction::Up),
+            Direction::East => (Direction::North, Direction::Up),
+            Direction::West => (Direction::South, Direction::Up),
+            Direction::Up => (Direction::East, Direction::North),
+            Direction::Down => (Direction::East, Direction::South),
+        }
+    }
+
+    /// Get the direction pointing the other way
+    #[inline]
+    pub const fn opposite(self) -> Self
+    {
+        match self
+        {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+        }
+    }
+
+    /// Rotate this direction a quarter turn clockwise around the `Y` axis, as
+    /// seen from above. `Up` and `Down` are left as-is.
+    #[inline]
+    pub const fn rotate_y_cw(self) -> Self
+    {
+        match self
+        {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+            dir => dir,
+        }
+    }
+
+    /// Rotate this direction a quarter turn counter-clockwise around the `Y` axis,
+    /// as seen from above. `Up` and `Down` are left as-is.
+    #[inline]
+    pub const fn rotate_y_ccw(self) -> Self
+    {
+        match self
+        {
+            Direction::North => Direction::West,
+            Direction::West => Direction::South,
+            Direction::South => Direction::East,
+            Direction::East => Direction::North,
+            dir => dir,
+        }
+    }
+}
+
+impl std::fmt::Display for Direction
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{}", match self
+        {
+            Direction::North => "North",
+            Direction::South => "South",
+            Direction::East => "East",
+            Direction::West => "West",
+            Direction::Up => "Up",
+            Direction::Down => "Down",
+        })
+    }
+}
+
+crate::util::impl_prop_str!(Direction, ParseDirectionError, "direction");
+
+/// Enumerates over the three axes of 3D space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis
+{
+    X,
+    Y,
+    Z,
+}
+
+#[cfg(test)]
+mod test
+{
+    use super::{ Axis, Direction, ParseDirectionError, Vec3 };
+
+    #[test]
+    fn opposite()
+    {
+        for &dir in &Direction::ALL
+        {
+            assert_ne!(dir.opposite(), dir);
+            assert_eq!(dir.opposite().opposite(), dir);
+            assert_eq!(dir.opposite().axis(), dir.axis());
+        }
+    }
+
+    #[test]
+    fn rotate_y()
+    {
+        for &dir in &Direction::HORIZONTAL
+        {
+            assert_ne!(dir.rotate_y_cw(), dir);
+            assert_ne!(dir.rotate_y_cw().rotate_y_cw(), dir);
+            assert_eq!(dir.rotate_y_cw().rotate_y_cw(), dir.opposite());
+            assert_eq!(dir.rotate_y_cw().rotate_y_cw().rotate_y_cw().rotate_y_cw(), dir);
+            assert_eq!(dir.rotate_y_cw().rotate_y_ccw(), dir);
+            assert_eq!(dir.rotate_y_ccw().rotate_y_ccw().rotate_y_ccw().rotate_y_ccw(), dir);
+            assert_ne!(dir.rotate_y_cw().axis(), dir.axis());
+        }
+        assert_eq!(Direction::North.rotate_y_cw(), Direction::East);
+        assert_eq!(Direction::Up.rotate_y_cw(), Direction::Up);
+        assert_eq!(Direction::Down.rotate_y_ccw(), Direction::Down);
+    }
+
+    #[test]
+    fn normal()
+    {
+        for &dir in &Direction::ALL
+        {
+            let normal = dir.normal::<i32>();
+
+            assert_eq!(normal, dir.offset());
+            assert_eq!(normal.dot(dir.opposite().normal()), -1);
+            assert_eq!(normal.dot(normal), 1);
+            assert_eq!(dir.normal::<f32>(), normal.map(|n| n as f32));
+
+            for &other in &Direction::ALL
+            {
+                // Perpendicular to every direction along another axis
+                assert_eq!(normal.dot(other.normal()) == 0, other.axis() != dir.axis());
+            }
+        }
+        assert_eq!(Direction::Up.normal::<i64>(), Vec3::new(0, 1, 0));
+        assert_eq!(Direction::West.axis(), Axis::X);
+    }
+
+    #[test]
+    fn tangents()
+    {
+        for &dir in &Direction::ALL
+        {
+            let (u, v) = dir.tangents();
+            let (u, v, n) = (u.normal::<i32>(), v.normal::<i32>(), dir.normal::<i32>());
+
+            assert_eq!(u.dot(n), 0);
+            assert_eq!(v.dot(n), 0);
+            assert_eq!(u.dot(v), 0);
+            // right-handed
+            assert_eq!(u.cross(v), n);
+            assert_eq!(dir.normal_axis(), dir.axis());
+        }
+        for &dir in &Direction::HORIZONTAL
+        {
+            assert_eq!(dir.tangents().1, Direction::Up);
+        }
+    }
+
+    #[test]
+    fn from_normal()
+    {
+        for &dir in &Direction::ALL
+        {
+            assert_eq!(Direction::from_normal(dir.offset()), Some(dir));
+            assert_eq!(Direction::from_normal(dir.offset() * 2), None);
+        }
+        assert_eq!(Direction::from_normal(Vec3::zero()), None);
+        assert_eq!(Direction::from_normal(Vec3::new(1, 1, 0)), None);
+    }
+
+    #[test]
+    fn nearest()
+    {
+        for &dir in &Direction::ALL
+        {
+            assert_eq!(Direction::nearest(dir.normal()), dir);
+            assert_eq!(Direction::nearest(dir.normal::<f32>() * 0.01), dir);
+        }
+
+        // Just off the diagonals
+        assert_eq!(Direction::nearest(Vec3::new(0.71, 0.7, 0.0)), Direction::East);
+        assert_eq!(Direction::nearest(Vec3::new(0.7, 0.71, 0.0)), Direction::Up);
+        assert_eq!(Direction::nearest(Vec3::new(0.0, -0.71, 0.7)), Direction::Down);
+        assert_eq!(Direction::nearest(Vec3::new(0.3, -0.7, -0.71)), Direction::North);
+        assert_eq!(Direction::nearest(Vec3::new(-0.58, 0.57, 0.57)), Direction::West);
+
+        // Ties
+        assert_eq!(Direction::nearest(Vec3::new(-0.5, 0.5, 0.5)), Direction::West);
+        assert_eq!(Direction::nearest(Vec3::new(0.0, -0.5, 0.5)), Direction::Down);
+        assert_eq!(Direction::nearest(Vec3::zero()), Direction::East);
+    }
+
+    #[test]
+    fn horizontal_nearest()
+    {
+        for &dir in &Direction::HORIZONTAL
+        {
+            assert_eq!(Direction::horizontal_nearest(dir.normal()), dir);
+            // Looking steeply up or down
+            assert_eq!(Direction::horizontal_nearest(dir.normal() + Vec3::new(0.0, -5.0, 0.0)), dir);
+        }
+        assert_eq!(Direction::horizontal_nearest(Vec3::new(0.69, 0.9, -0.7)), Direction::North);
+        assert_eq!(Direction::horizontal_nearest(Vec3::new(-0.71, -0.9, 0.7)), Direction::West);
+        // Ties
+        assert_eq!(Direction::horizontal_nearest(Vec3::new(-0.5, 0.0, 0.5)), Direction::West);
+        assert_eq!(Direction::horizontal_nearest(Vec3::unit_y()), Direction::East);
+    }
+
+    #[test]
+    fn parse()
+    {
+        for &dir in &Direction::ALL
+        {
+            assert_eq!(dir.as_str().parse(), Ok(dir));
+            assert_eq!(dir.to_string().parse(), Ok(dir));
+            assert_eq!(format!("{:?}", dir).to_uppercase().parse(), Ok(dir));
+        }
+        assert_eq!(Direction::variants().collect::<Vec<_>>(), ["north", "south", "east", "west", "up", "down"]);
+
+        assert_eq!("nort".parse::<Direction>(), Err(ParseDirectionError("nort".to_owned())));
+        assert!("".parse::<Direction>().is_err());
+        assert!("north east".parse::<Direction>().is_err());
+        assert_eq!(
+            ParseDirectionError("sideways".to_owned()).to_string(),
+            "unknown direction `sideways`, expected one of: north, south, east, west, up, down",
+        );
+    }
+}
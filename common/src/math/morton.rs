@@ -0,0 +1,91 @@
+//! Morton(Z-order) codes, which interleave the bits of 3D coordinates so that
+//! positions close to each other in space tend to be close to each other in a
+//! flat array too.
+
+use crate::math::Vec3;
+
+/// Interleave the bits of `pos` into its Morton code, `x` being the least significant.
+/// Each component must be less than `1024`, whose upper bits are ignored.
+#[inline]
+pub const fn encode3(pos: Vec3<usize>) -> usize
+{
+    spread_bits(pos.x) | (spread_bits(pos.y) << 1) | (spread_bits(pos.z) << 2)
+}
+
+/// Inverse of [encode3]
+#[inline]
+pub const fn decode3(code: usize) -> Vec3<usize>
+{
+    Vec3 { x: compact_bits(code), y: compact_bits(code >> 1), z: compact_bits(code >> 2) }
+}
+
+/// Spread the lower 10 bits of `n` out to every third bit, starting from the least
+/// significant
+#[inline]
+const fn spread_bits(n: usize) -> usize
+{
+    let mut n = n & 0x0000_03ff;
+
+    n = (n | (n << 16)) & 0xff00_00ff;
+    n = (n | (n << 8)) & 0x0300_f00f;
+    n = (n | (n << 4)) & 0x030c_30c3;
+    n = (n | (n << 2)) & 0x0924_9249;
+    n
+}
+
+/// Extract every third bit of `n`, starting from the least significant. Inverse of
+/// [spread_bits]
+#[inline]
+const fn compact_bits(n: usize) -> usize
+{
+    let mut n = n & 0x0924_9249;
+
+    n = (n ^ (n >> 2)) & 0x030c_30c3;
+    n = (n ^ (n >> 4)) & 0x0300_f00f;
+    n = (n ^ (n >> 8)) & 0xff00_00ff;
+    n = (n ^ (n >> 16)) & 0x0000_03ff;
+    n
+}
+
+#[cfg(test)]
+mod test
+{
+    use super::{ encode3, decode3 };
+    use crate::math::vec3;
+
+    #[test]
+    fn round_trip()
+    {
+        let mut seen = vec![false; 32 * 32 * 32];
+
+        for x in 0..32
+        {
+            for y in 0..32
+            {
+                for z in 0..32
+                {
+                    let code = encode3(vec3(x, y, z));
+
+                    assert_eq!(decode3(code), vec3(x, y, z));
+                    assert!(!seen[code], "{} encoded twice", code);
+                    seen[code] = true;
+                }
+            }
+        }
+        for code in 0..32 * 32 * 32
+        {
+            assert_eq!(encode3(decode3(code)), code);
+        }
+    }
+
+    #[test]
+    fn order()
+    {
+        assert_eq!(encode3(vec3(1, 0, 0)), 0b001);
+        assert_eq!(encode3(vec3(0, 1, 0)), 0b010);
+        assert_eq!(encode3(vec3(0, 0, 1)), 0b100);
+        assert_eq!(encode3(vec3(2, 0, 0)), 0b001_000);
+        assert_eq!(encode3(vec3(1023, 1023, 1023)), (1 << 30) - 1);
+        assert_eq!(decode3(0b111_010), vec3(2, 3, 2));
+    }
+}
@@ -11,7 +11,7 @@
 /// bits.get::<4, 1>(); // error! won't compile
 /// bits.get::<4, 7>(); // error! won't compile
 /// ```
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Bits<const N: usize>(u8);
 
 impl<const N: usize> Bits<N> where Self: Valid
@@ -86,6 +86,97 @@ impl<const N: usize> Bits<N> where Self: Valid
         self.0 |= (val & mask) << shift;
     }
 
+    /// [Bits::get], but with a range only known at runtime. Returns `None` if
+    /// `start` >= `end`, or if `end` > `N`(length, in bits, of this bit array).
+    #[inline]
+    pub fn checked_get(&self, start: usize, end: usize) -> Option<u8>
+    {
+        if start >= end || end > N { return None }
+
+        // Same as `Bits::get`
+        Some((self.0 >> (N - end)) & (0xff >> (8 - (end - start))))
+    }
+
+    /// [Bits::set], but with a range only known at runtime. Does nothing if
+    /// `start` >= `end`, or if `end` > `N`(length, in bits, of this bit array).
+    pub fn checked_set(&mut self, start: usize, end: usize, val: u8)
+    {
+        if start >= end || end > N { return }
+
+        // Same as `Bits::set`
+        let mask = 0xff >> (8 - (end - start));
+        let shift = N - end;
+
+        self.0 &= !(mask << shift);
+        self.0 |= (val & mask) << shift;
+    }
+
+    /// Rotates the `N` bits of this bit array left by `n` bits, wrapping the
+    /// truncated bits to the end. Unlike `u8::rotate_left`, bits never leave
+    /// the `N`-bit window. `n` is reduced modulo `N`.
+    #[inline]
+    pub const fn rotate_left(self, n: u32) -> Self
+    {
+        let n = n as usize % N;
+
+        // Shifting a `u8` by 8 overflows
+        if n == 0 { return self }
+
+        // Let's look at a `Bits<6>` example, rotating left by 2:
+        // 0010_1101
+        //
+        // Shift left(n), upper bits are clipped by `new`:
+        // 1011_0100 -> 0011_0100
+        //
+        // Shift right(N - n) to get the bits that were pushed out:
+        // 0000_0010
+        //
+        // Bitwise OR:
+        // 0011_0110
+        Self::new((self.0 << n) | (self.0 >> (N - n)))
+    }
+
+    /// Rotates the `N` bits of this bit array right by `n` bits, wrapping the
+    /// truncated bits to the start. Unlike `u8::rotate_right`, bits never leave
+    /// the `N`-bit window. `n` is reduced modulo `N`.
+    #[inline]
+    pub const fn rotate_right(self, n: u32) -> Self
+    {
+        self.rotate_left((N - n as usize % N) as u32)
+    }
+
+    /// Create a new bit array from its individual bits, in the same order as
+    /// ranges in [Bits::get], ie. `bits[0]` is the most significant of the `N` bits.
+    #[inline]
+    pub const fn from_bools(bits: [bool; N]) -> Self
+    {
+        let mut val = 0;
+        let mut i = 0;
+
+        while i < N
+        {
+            val |= (bits[i] as u8) << (N - 1 - i);
+            i += 1;
+        }
+        Self::new(val)
+    }
+
+    /// Get the individual bits of this bit array, in the same order as ranges
+    /// in [Bits::get], ie. `bits[0]` is the most significant of the `N` bits.
+    #[inline]
+    pub const fn to_bools(self) -> [bool; N]
+    {
+        let mut bits = [false; N];
+        let mut i = 0;
+
+        while i < N
+        {
+            bits[i] = (self.0 >> (N - 1 - i)) & 1 == 1;
+            i += 1;
+        }
+        bits
+    }
+
     /// Get the byte this bit array wraps over
     #[inline]
     pub const fn inner(self) -> u8
@@ -94,6 +185,115 @@ impl<const N: usize> Bits<N> where Self: Valid
     }
 }
 
+impl<const N: usize> Bits<N>
+{
+    /// [Bits::new], but without the [Valid] bound so that it can be called where `N`
+    /// is generic, ie. to build packed blocks in `const` contexts. An `N` outside of
+    /// `1..=8` fails to evaluate rather than to compile.
+    #[inline]
+    pub const fn new_const(val: u8) -> Self
+    {
+        assert!(N > 0 && N <= 8, "Bits<N> must be 1 to 8 bits long");
+
+        Self(val & (0xff >> (8 - N)))
+    }
+}
+
+/// Pack `values` into a dense buffer of `N` bits each, with no padding between
+/// them, ie. for the index array of a paletted chunk. Values are laid out from the
+/// most significant bit of the first byte onwards, and the last byte is padded with
+/// zeros. See [unpack_bits] for the inverse.
+pub fn pack_bits<const N: usize>(values: &[Bits<N>]) -> Vec<u8> where Bits<N>: Valid
+{
+    let mut out = Vec::with_capacity((values.len() * N).div_ceil(8));
+    // Bits not yet written out, right-aligned
+    let mut acc = 0u16;
+    let mut len = 0;
+
+    for bits in values
+    {
+        acc = (acc << N) | bits.0 as u16;
+        len += N;
+
+        if len >= 8
+        {
+            len -= 8;
+            out.push((acc >> len) as u8);
+        }
+    }
+    if len > 0
+    {
+        out.push((acc << (8 - len)) as u8);
+    }
+    out
+}
+
+/// Unpack the first `len` values of `N` bits each from `bytes`, packed by [pack_bits].
+/// Returns `None` if `bytes` is too short to hold them.
+pub fn unpack_bits<const N: usize>(bytes: &[u8], len: usize) -> Option<Vec<Bits<N>>> where Bits<N>: Valid
+{
+    if bytes.len() < (len * N).div_ceil(8) { return None }
+
+    let mut out = Vec::with_capacity(len);
+    let mut bytes = bytes.iter();
+    // Bits not yet read, right-aligned
+    let mut acc = 0u16;
+    let mut avail = 0;
+
+    while out.len() < len
+    {
+        if avail < N
+        {
+            // Checked above
+            acc = (acc << 8) | *bytes.next()? as u16;
+            avail += 8;
+        }
+        avail -= N;
+        out.push(Bits::new((acc >> avail) as u8));
+    }
+    Some(out)
+}
+
+/// [pack_bits] for `Bits<4>`, two per byte with the first in the upper nibble
+pub fn pack_nibbles(values: &[Bits<4>]) -> Vec<u8>
+{
+    values
+        .chunks(2)
+        .map(|pair| pair[0].0 << 4 | pair.get(1).map_or(0, |b| b.0))
+        .collect()
+}
+
+/// [unpack_bits] for `Bits<4>`, the inverse of [pack_nibbles]. Returns `None` if
+/// `bytes` is too short to hold `len` values.
+pub fn unpack_nibbles(bytes: &[u8], len: usize) -> Option<Vec<Bits<4>>>
+{
+    if bytes.len() < len.div_ceil(2) { return None }
+
+    Some(bytes
+        .iter()
+        .flat_map(|&byte| [Bits(byte >> 4), Bits(byte & 0x0f)])
+        .take(len)
+        .collect())
+}
+
+/// Formats as all `N` bits, zero-padded, ie. `Bits<6>(001010)`
+impl<const N: usize> std::fmt::Debug for Bits<N>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "Bits<{}>({})", N, self)
+    }
+}
+
+/// Formats as all `N` bits, zero-padded, ie. `001010`
+impl<const N: usize> std::fmt::Display for Bits<N>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{:0width$b}", self.0, width = N)
+    }
+}
+
 /// Dummy trait that converts number literals(`0`, `1`, implemented for up to `7`)
 /// into concrete types
 pub struct Literal<const N: usize>;
@@ -165,7 +365,7 @@ impl Valid for Bits<8> { }
 #[cfg(test)]
 mod test
 {
-    use super::Bits;
+    use super::{ Bits, pack_bits, unpack_bits, pack_nibbles, unpack_nibbles };
 
     #[test]
     fn get_range()
@@ -197,6 +397,18 @@ mod test
         assert_eq!(bits.inner(), 0b0000_1100);
     }
 
+    #[test]
+    fn new_const()
+    {
+        const B: Bits<6> = Bits::new_const(0b111111);
+        const C: Bits<3> = Bits::new_const(0b1111_1010);
+        const D: Bits<8> = Bits::new_const(0b1111_1010);
+
+        assert_eq!(B, Bits::<6>::new(0b111111));
+        assert_eq!(C.inner(), 0b0000_0010);
+        assert_eq!(D.inner(), 0b1111_1010);
+    }
+
     #[test]
     fn set_range()
     {
@@ -213,4 +425,157 @@ mod test
         bits.set::<0, 6>(0xff);
         assert_eq!(bits.inner(), 0b0011_1111);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn checked_range()
+    {
+        let mut bits = Bits::<6>::new(0b0010_1010);
+
+        assert_eq!(bits.checked_get(0, 1), Some(bits.get::<0, 1>()));
+        assert_eq!(bits.checked_get(0, 6), Some(bits.get::<0, 6>()));
+        assert_eq!(bits.checked_get(2, 5), Some(bits.get::<2, 5>()));
+        assert_eq!(bits.checked_get(5, 6), Some(bits.get::<5, 6>()));
+
+        // invalid
+        assert_eq!(bits.checked_get(3, 3), None);
+        assert_eq!(bits.checked_get(4, 2), None);
+        assert_eq!(bits.checked_get(0, 7), None);
+        assert_eq!(bits.checked_get(6, 8), None);
+
+        bits.checked_set(2, 5, 0b0000_0101);
+        assert_eq!(bits.inner(), 0b0010_1010);
+        bits.checked_set(0, 2, 0b1111_1101);
+        assert_eq!(bits.inner(), 0b0001_1010);
+
+        let mut expected = bits;
+        expected.set::<3, 6>(0b0000_0111);
+        bits.checked_set(3, 6, 0b0000_0111);
+        assert_eq!(bits, expected);
+
+        // invalid
+        bits.checked_set(3, 3, 0xff);
+        bits.checked_set(5, 1, 0xff);
+        bits.checked_set(4, 7, 0xff);
+        assert_eq!(bits, expected);
+    }
+
+    #[test]
+    fn rotate()
+    {
+        let bits = Bits::<6>::new(0b0010_0000);
+
+        assert_eq!(bits.rotate_left(1), Bits::<6>::new(0b0000_0001));
+        assert_eq!(bits.rotate_right(5), Bits::<6>::new(0b0000_0001));
+        assert_eq!(bits.rotate_left(0), bits);
+        assert_eq!(bits.rotate_left(6), bits);
+        assert_eq!(bits.rotate_right(6), bits);
+
+        let bits = Bits::<6>::new(0b0010_1101);
+
+        assert_eq!(bits.rotate_left(2), Bits::<6>::new(0b0011_0110));
+        assert_eq!(bits.rotate_right(2), Bits::<6>::new(0b0001_1011));
+        assert_eq!(bits.rotate_left(2).rotate_right(2), bits);
+        // upper bits of the byte stay clear
+        assert_eq!(bits.rotate_left(3).inner() & 0b1100_0000, 0);
+    }
+
+    #[test]
+    fn rotate_wrap()
+    {
+        let bits = Bits::<6>::new(0b0000_0011);
+
+        // `n` > `N` reduces modulo `N`
+        assert_eq!(bits.rotate_left(7), bits.rotate_left(1));
+        assert_eq!(bits.rotate_left(13), bits.rotate_left(1));
+        assert_eq!(bits.rotate_right(8), bits.rotate_right(2));
+        assert_eq!(bits.rotate_right(1), Bits::<6>::new(0b0010_0001));
+
+        let bits = Bits::<8>::new(0b1000_0001);
+
+        assert_eq!(bits.rotate_left(1), Bits::<8>::new(0b0000_0011));
+        assert_eq!(bits.rotate_right(1), Bits::<8>::new(0b1100_0000));
+        assert_eq!(bits.rotate_left(8), bits);
+
+        let bits = Bits::<1>::new(1);
+
+        assert_eq!(bits.rotate_left(3), bits);
+        assert_eq!(bits.rotate_right(3), bits);
+    }
+
+    #[test]
+    fn bools()
+    {
+        let bits = Bits::<6>::from_bools([true, false, true, true, false, false]);
+
+        assert_eq!(bits.inner(), 0b0010_1100);
+        assert_eq!(bits.get::<0, 1>(), 1);
+        assert_eq!(bits.to_bools(), [true, false, true, true, false, false]);
+
+        assert_eq!(Bits::<6>::from_bools([true; 6]).inner(), 0b0011_1111);
+        assert_eq!(Bits::<6>::from_bools([false; 6]).inner(), 0);
+        assert_eq!(Bits::<6>::new(0xff).to_bools(), [true; 6]);
+        assert_eq!(Bits::<6>::new(0).to_bools(), [false; 6]);
+
+        // bits above `N` never leak in
+        assert_eq!(Bits::<6>::new(0b1100_0001).to_bools(), [false, false, false, false, false, true]);
+
+        let bits = Bits::<8>::from_bools([true, false, false, false, false, false, true, false]);
+
+        assert_eq!(bits.inner(), 0b1000_0010);
+        assert_eq!(bits.to_bools(), [true, false, false, false, false, false, true, false]);
+
+        assert_eq!(Bits::<8>::from_bools([true; 8]).inner(), 0xff);
+        assert_eq!(Bits::<8>::from_bools([false; 8]).inner(), 0);
+        assert_eq!(Bits::<8>::new(0xff).to_bools(), [true; 8]);
+        assert_eq!(Bits::<8>::new(0).to_bools(), [false; 8]);
+
+        for n in 0..=u8::MAX
+        {
+            assert_eq!(Bits::<8>::from_bools(Bits::<8>::new(n).to_bools()).inner(), n);
+            assert_eq!(Bits::<6>::from_bools(Bits::<6>::new(n).to_bools()).inner(), n & 0b0011_1111);
+        }
+    }
+
+    #[test]
+    fn fmt()
+    {
+        assert_eq!(Bits::<6>::new(0b1010).to_string(), "001010");
+        assert_eq!(Bits::<6>::new(0).to_string(), "000000");
+        assert_eq!(Bits::<1>::new(1).to_string(), "1");
+        assert_eq!(Bits::<8>::new(0xff).to_string(), "11111111");
+        assert_eq!(format!("{:?}", Bits::<6>::new(0b1010)), "Bits<6>(001010)");
+
+        assert_eq!(Bits::<3>::new(0).to_string().len(), 3);
+        assert_eq!(Bits::<5>::new(0b1_0000).to_string().len(), 5);
+        assert_eq!(Bits::<7>::new(0xff).to_string().len(), 7);
+    }
+
+    #[test]
+    fn pack()
+    {
+        let nibbles = (0..37u8).map(|n| Bits::<4>::new(n.wrapping_mul(7))).collect::<Vec<_>>();
+        let sixes = (0..37u8).map(|n| Bits::<6>::new(n.wrapping_mul(13))).collect::<Vec<_>>();
+
+        assert_eq!(pack_bits(&nibbles).len(), 19);
+        assert_eq!(pack_bits(&sixes).len(), 28);
+        assert_eq!(pack_nibbles(&nibbles), pack_bits(&nibbles));
+
+        for len in 0..=nibbles.len()
+        {
+            assert_eq!(unpack_bits::<4>(&pack_bits(&nibbles[..len]), len).unwrap(), &nibbles[..len]);
+            assert_eq!(unpack_nibbles(&pack_nibbles(&nibbles[..len]), len).unwrap(), &nibbles[..len]);
+            assert_eq!(unpack_bits::<6>(&pack_bits(&sixes[..len]), len).unwrap(), &sixes[..len]);
+        }
+
+        // dense, most significant bits first
+        let bits = [Bits::<6>::new(0b111111), Bits::new(0b000001), Bits::new(0b101010)];
+
+        assert_eq!(pack_bits(&bits), [0b1111_1100, 0b0001_1010, 0b1000_0000]);
+        assert_eq!(pack_nibbles(&[Bits::new(0xa), Bits::new(0x5), Bits::new(0xf)]), [0xa5, 0xf0]);
+
+        // too short
+        assert_eq!(unpack_bits::<6>(&[0xff, 0xff], 3), None);
+        assert_eq!(unpack_nibbles(&[0xff], 3), None);
+        assert_eq!(unpack_bits::<8>(&[], 0), Some(vec![]));
+    }
+}
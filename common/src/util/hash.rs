@@ -0,0 +1,54 @@
+/// 64-bit FNV-1a, a fast non-cryptographic hash. Unlike `std`'s hashers, it's
+/// guaranteed to be stable across runs and platforms, so its output can be saved.
+pub(crate) struct Fnv1a(u64);
+
+impl Fnv1a
+{
+    /// Feed `bytes` into the hash
+    pub fn write(&mut self, bytes: &[u8])
+    {
+        for &byte in bytes
+        {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x100_0000_01b3);
+        }
+    }
+
+    /// Get the hash of every byte written so far
+    pub fn finish(&self) -> u64
+    {
+        self.0
+    }
+}
+
+impl Default for Fnv1a
+{
+    fn default() -> Self
+    {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use super::Fnv1a;
+
+    #[test]
+    fn known_values()
+    {
+        let hash = |bytes: &[&[u8]]|
+        {
+            let mut hash = Fnv1a::default();
+
+            bytes.iter().for_each(|bytes| hash.write(bytes));
+            hash.finish()
+        };
+
+        assert_eq!(hash(&[]), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(&[b"a"]), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(hash(&[b"foobar"]), 0x8594_4171_f739_67e8);
+        // Streaming doesn't change the hash
+        assert_eq!(hash(&[b"foo", b"", b"bar"]), hash(&[b"foobar"]));
+    }
+}
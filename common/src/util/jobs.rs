@@ -0,0 +1,206 @@
+use std::sync::mpsc::{ self, Sender, Receiver };
+
+/// A queue of jobs run on the global thread pool(or its own, see [ThreadJobs::with_name]),
+/// whose results are pulled back as they complete. Results can be tagged with a key `K` to know which job they
+/// came from, see [ThreadJobs::push_keyed].
+///
+/// ```
+/// # use miners_common::util::ThreadJobs;
+/// # use miners_common::math::{ Vec3, vec3 };
+/// # fn generate(pos: Vec3<i32>) -> i32 { pos.x }
+/// let mut jobs = ThreadJobs::new();
+///
+/// jobs.push_keyed(vec3(0, 0, 0), || generate(vec3(0, 0, 0)));
+/// jobs.push_keyed(vec3(1, 0, 0), || generate(vec3(1, 0, 0)));
+///
+/// while jobs.pending() != 0
+/// {
+///     for (pos, chunk) in jobs.pull_keyed()
+///     {
+///         assert_eq!(chunk, generate(pos));
+///     }
+/// }
+/// ```
+pub struct ThreadJobs<T, K = ()>
+{
+    /// Cloned into every job to send its result back
+    tx: Sender<(K, T)>,
+    /// Receives the results of completed jobs
+    rx: Receiver<(K, T)>,
+    /// Number of jobs pushed whose result hasn't been pulled yet
+    pending: usize,
+    /// Threads running the jobs, or rayon's global pool if `None`
+    pool: Option<rayon::ThreadPool>,
+}
+
+impl<T: Send + 'static, K: Send + 'static> ThreadJobs<T, K>
+{
+    /// Create a new, empty job queue
+    pub fn new() -> Self
+    {
+        let (tx, rx) = mpsc::channel();
+
+        Self { tx, rx, pending: 0, pool: None }
+    }
+
+    /// Create a new, empty job queue whose jobs run on `threads` threads of its own(or
+    /// one per CPU if `0`), named `"{prefix}-{index}"`. ie. `"chunkgen-0"`, so that
+    /// they can be told apart in profilers and crash reports. A job panicking doesn't
+    /// take down the process, and it's left pending.
+    pub fn with_name(prefix: &str, threads: usize) -> Self
+    {
+        let prefix = prefix.to_owned();
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(move |i| format!("{}-{}", prefix, i))
+            // Panic message is already printed with the thread's name
+            .panic_handler(|_| ())
+            .build()
+            .expect("failed to spawn job threads");
+
+        Self { pool: Some(pool), ..Self::new() }
+    }
+
+    /// Run the job `f` on the thread pool, tagging its result with `key`
+    pub fn push_keyed(&mut self, key: K, f: impl FnOnce() -> T + Send + 'static)
+    {
+        let tx = self.tx.clone();
+
+        let job = move ||
+        {
+            // Receiver is only gone if this `ThreadJobs` was dropped, in which
+            // case nobody is interested in the result
+            let _ = tx.send((key, f()));
+        };
+
+        self.pending += 1;
+        match &self.pool
+        {
+            Some(pool) => pool.spawn(job),
+            None => rayon::spawn(job),
+        }
+    }
+
+    /// Get the results of all the jobs completed since the last pull, along with
+    /// the key they were pushed with, in order of completion. This is non-blocking.
+    pub fn pull_keyed(&mut self) -> impl Iterator<Item = (K, T)> + '_
+    {
+        let pending = &mut self.pending;
+
+        self.rx
+            .try_iter()
+            .inspect(move |_| *pending -= 1)
+    }
+
+    /// Get the number of jobs pushed whose result hasn't been pulled yet. Jobs that
+    /// panicked never have a result, and stay pending.
+    pub fn pending(&self) -> usize
+    {
+        self.pending
+    }
+}
+
+impl<T: Send + 'static> ThreadJobs<T>
+{
+    /// Run the job `f` on the thread pool
+    pub fn push(&mut self, f: impl FnOnce() -> T + Send + 'static)
+    {
+        self.push_keyed((), f)
+    }
+
+    /// Get the results of all the jobs completed since the last pull, in order of
+    /// completion. This is non-blocking.
+    pub fn pull(&mut self) -> impl Iterator<Item = T> + '_
+    {
+        self.pull_keyed().map(|(_, res)| res)
+    }
+}
+
+impl<T: Send + 'static, K: Send + 'static> Default for ThreadJobs<T, K>
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use super::ThreadJobs;
+
+    #[test]
+    fn keyed()
+    {
+        let mut jobs = ThreadJobs::new();
+
+        for i in 0..64u64
+        {
+            jobs.push_keyed(i, move ||
+            {
+                // finish out of order
+                std::thread::sleep(std::time::Duration::from_micros((64 - i) * 50));
+                i * i
+            });
+        }
+        assert_eq!(jobs.pending(), 64);
+
+        let mut results = Vec::new();
+        while jobs.pending() != 0
+        {
+            results.extend(jobs.pull_keyed());
+        }
+
+        assert_eq!(results.len(), 64);
+        for (key, res) in &results
+        {
+            assert_eq!(*res, key * key);
+        }
+
+        results.sort_unstable();
+        assert!(results.iter().map(|(key, _)| *key).eq(0..64));
+    }
+
+    #[test]
+    fn unkeyed()
+    {
+        let mut jobs = ThreadJobs::new();
+
+        jobs.push(|| "a");
+        jobs.push(|| "b");
+
+        let mut results = Vec::new();
+        while jobs.pending() != 0
+        {
+            results.extend(jobs.pull());
+        }
+        results.sort_unstable();
+
+        assert_eq!(results, vec!["a", "b"]);
+        assert_eq!(jobs.pull().count(), 0);
+    }
+
+    #[test]
+    fn named()
+    {
+        let mut jobs = ThreadJobs::with_name("chunkgen", 2);
+
+        for _ in 0..8
+        {
+            jobs.push(|| std::thread::current().name().map(str::to_owned));
+        }
+        jobs.push(|| panic!("job panicked on purpose"));
+
+        let mut names = Vec::new();
+        while names.len() != 8
+        {
+            names.extend(jobs.pull());
+        }
+        names.sort_unstable();
+        names.dedup();
+
+        assert!(names.iter().all(|name| matches!(name.as_deref(), Some("chunkgen-0") | Some("chunkgen-1"))));
+        // the panicked job never completes
+        assert_eq!(jobs.pending(), 1);
+    }
+}
@@ -1,5 +1,87 @@
 mod registry;
 mod bits;
+mod jobs;
+mod hash;
 
 pub use registry::Registry;
-pub use bits::Bits;
\ No newline at end of file
+pub use bits::{ Bits, pack_bits, unpack_bits, pack_nibbles, unpack_nibbles };
+pub use jobs::ThreadJobs;
+pub(crate) use hash::Fnv1a;
+
+/// Whether the identifier `name`, ie. `"dark_oak"`, matches `input` ignoring case
+/// and separators, such that `"Dark Oak"`, `"DarkOak"` and `"dark-oak"` all do
+pub(crate) fn ident_eq(input: &str, name: &str) -> bool
+{
+    fn chars(s: &str) -> impl Iterator<Item = char> + '_
+    {
+        s.chars()
+            .filter(|c| !matches!(c, '_' | ' ' | '-'))
+            .map(|c| c.to_ascii_lowercase())
+    }
+
+    chars(input).eq(chars(name))
+}
+
+/// Implement `FromStr` for the property enum `$ty`, and `Serialize`/`Deserialize`
+/// with the `serde` feature, in terms of its `ALL` variants and their `as_str`
+/// identifiers. Parsing goes through [ident_eq], and fails with the error type
+/// `$err` this also defines, describing `$ty` as `$what`, ie. `"direction"`.
+macro_rules! impl_prop_str
+{
+    ($ty:ident, $err:ident, $what:literal) =>
+    {
+        #[doc = concat!("Error parsing a [", stringify!($ty), "] from a string that names none of its variants,")]
+        #[doc = "holding that string"]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $err(pub String);
+
+        impl std::fmt::Display for $err
+        {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+            {
+                write!(f, concat!("unknown ", $what, " `{}`, expected one of: "), self.0)?;
+
+                for (i, variant) in $ty::variants().enumerate()
+                {
+                    write!(f, "{}{}", if i == 0 { "" } else { ", " }, variant)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl std::str::FromStr for $ty
+        {
+            type Err = $err;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err>
+            {
+                $ty::ALL
+                    .iter()
+                    .copied()
+                    .find(|variant| $crate::util::ident_eq(s, variant.as_str()))
+                    .ok_or_else(|| $err(s.to_owned()))
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $ty
+        {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $ty
+        {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+            {
+                String::deserialize(deserializer)?
+                    .parse()
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+pub(crate) use impl_prop_str;
@@ -21,8 +21,12 @@ impl<T> Registry<T>
     /// in the registry.
     pub fn register<K: 'static>(&mut self, meta: T)
     {
-        let type_id = TypeId::of::<K>();
+        self.register_type_id(TypeId::of::<K>(), meta)
+    }
 
+    /// [Registry::register], given the type's [TypeId]
+    pub fn register_type_id(&mut self, type_id: TypeId, meta: T)
+    {
         // Don't register duplicate types
         if !self.map.contains_key(&type_id)
         {
@@ -32,11 +36,25 @@ impl<T> Registry<T>
         }
     }
 
+    /// Take up the next ID with an entry that isn't looked up by its type, ie.
+    /// to keep the IDs that follow stable. The entry is still returned by
+    /// [Registry::get] and [Registry::iter].
+    pub fn reserve(&mut self, type_id: TypeId, meta: T)
+    {
+        self.rev.push((type_id, meta));
+    }
+
     /// Get the ID of the given type, if present in this map.
     pub fn id<K: 'static>(&self) -> Option<usize>
+    {
+        self.id_of(TypeId::of::<K>())
+    }
+
+    /// Get the ID of the type with the given [TypeId], if present in this map.
+    pub fn id_of(&self, type_id: TypeId) -> Option<usize>
     {
         self.map
-            .get(&TypeId::of::<K>())
+            .get(&type_id)
             .copied()
     }
 
@@ -47,6 +65,13 @@ impl<T> Registry<T>
         self.rev.get(id)
     }
 
+    /// Iterate over the type and meta data entries of this registry, in order
+    /// of their identifiers.
+    pub fn iter(&self) -> impl Iterator<Item = &(TypeId, T)>
+    {
+        self.rev.iter()
+    }
+
     /// [Registry::get] without bounds checking
     pub unsafe fn get_unchecked(&self, id: usize) -> &(TypeId, T)
     {
@@ -1,4 +1,7 @@
-use crate::world::blockdef;
+use crate::world::{ blockdef, block };
+use crate::vanilla::props::Direction;
+
+use super::{ BlockWater, BlockLava, flow };
 
 blockdef!
 {
@@ -7,4 +10,18 @@ blockdef!
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub struct BlockAir;
+
+    fn solid(&self) -> bool { false }
+
+    fn occludes(&self, _: Direction) -> bool { false }
+
+    fn hardness(&self) -> f32 { 0.0 }
+
+    // Fluids next to it may flow in
+    fn on_neighbor_changed(&self, ctx: &block::TickContext<'_>, _dir: Direction) -> Option<block::Choice>
+    {
+        flow::<BlockWater>(ctx)
+            .map(block::Choice::new)
+            .or_else(|| flow::<BlockLava>(ctx).map(block::Choice::new))
+    }
 }
\ No newline at end of file
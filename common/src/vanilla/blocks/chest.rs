@@ -1,9 +1,8 @@
 use std::borrow::Cow;
 
 use crate::world::blockdef;
-use crate::math::Direction;
-
-pub type Inventory = Vec<&'static str>;
+use crate::item::Inventory;
+use crate::vanilla::props::Direction;
 
 blockdef!
 {
@@ -27,4 +26,84 @@ blockdef!
         #[prop(!)]
         pub name: Option<String>,
     }
+
+    // Smaller than a full block
+    fn occludes(&self, _: Direction) -> bool { false }
+
+    // ```text
+    // facing: u8
+    // name: [len: u8, utf8: [u8; len]] // len = 0 if unnamed
+    // contents: Inventory
+    // ```
+    fn serialize(&self, out: &mut Vec<u8>)
+    {
+        out.push(Direction::ALL.iter().position(|&dir| dir == self.facing).unwrap() as u8);
+        match &self.name
+        {
+            Some(name) =>
+            {
+                // Names set directly may be too long, so cut them on a character
+                // boundary
+                let len = (0..=name.len().min(u8::MAX as usize))
+                    .rev()
+                    .find(|&i| name.is_char_boundary(i))
+                    .unwrap();
+
+                out.push(len as u8);
+                out.extend_from_slice(&name.as_bytes()[..len]);
+            },
+            None => out.push(0),
+        }
+        self.contents.serialize(out);
+    }
+
+    fn deserialize(bytes: &[u8]) -> Option<Self>
+    {
+        let (&facing, bytes) = bytes.split_first()?;
+        let facing = match Direction::ALL.get(facing as usize)?
+        {
+            dir @ (Direction::North | Direction::South | Direction::East | Direction::West) => *dir,
+            _ => return None,
+        };
+
+        let (&len, rest) = bytes.split_first()?;
+        if rest.len() < len as usize
+        {
+            return None
+        }
+        let (name, mut rest) = rest.split_at(len as usize);
+        let name = match len
+        {
+            0 => None,
+            _ => Some(String::from_utf8(name.to_vec()).ok()?),
+        };
+
+        let contents = Inventory::deserialize(&mut rest)?;
+        // Trailing bytes
+        if !rest.is_empty()
+        {
+            return None
+        }
+        Some(Self { contents, facing, name })
+    }
+
+    // Zeroed contents would have no slots at all
+    fn default_state() -> Self { Self::new(Direction::North) }
+}
+
+impl BlockChest
+{
+    /// Number of slots in a chest
+    pub const SLOTS: usize = 27;
+
+    /// Create an empty, unnamed chest
+    pub fn new(facing: Direction) -> Self
+    {
+        Self
+        {
+            contents: Inventory::new(Self::SLOTS),
+            facing,
+            name: None,
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,12 @@
+use crate::world::blockdef;
+
+blockdef!
+{
+    id: "dirt",
+    name: "Dirt",
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockDirt;
+
+    fn hardness(&self) -> f32 { 0.5 }
+}
\ No newline at end of file
@@ -0,0 +1,128 @@
+use crate::world::{ World, WorldAccessError, blockdef, block };
+use crate::math::{ Aabb, Vec3 };
+use crate::vanilla::props::Direction;
+
+use super::BlockAir;
+
+/// Side of a door its hinges are on, as seen from its front
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Hinge
+{
+    Left,
+    Right,
+}
+
+/// Which of the two cells of a door a [BlockDoor] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DoorHalf
+{
+    Lower,
+    Upper,
+}
+
+blockdef!
+{
+    id: "door",
+    name: "Door",
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockDoor
+    {
+        /// Side of the cell the door sits against when closed
+        #[prop(North | South | East | West)]
+        pub facing: Direction,
+        /// Side the door swings around
+        #[prop(Left | Right)]
+        pub hinge: Hinge,
+        /// Whether the door is swung open
+        #[prop(bool)]
+        pub open: bool,
+        /// Which of the door's two cells this is
+        #[prop(Lower | Upper)]
+        pub half: DoorHalf,
+    }
+
+    fn occludes(&self, _: Direction) -> bool { false }
+
+    fn collision_boxes(&self) -> Vec<Aabb<f32>>
+    {
+        /// Thickness of a door
+        const T: f32 = 3.0 / 16.0;
+
+        // Swings onto the side of its hinges
+        let side = match (self.open, self.hinge)
+        {
+            (false, _) => self.facing,
+            (true, Hinge::Left) => self.facing.rotate_y_ccw(),
+            (true, Hinge::Right) => self.facing.rotate_y_cw(),
+        };
+        let offset = side.offset().map(|n| n as f32);
+
+        vec![Aabb
+        {
+            min: offset.map(|n| if n > 0.0 { 1.0 - T } else { 0.0 }),
+            max: offset.map(|n| if n < 0.0 { T } else { 1.0 }),
+        }]
+    }
+
+    // Takes up the cell above too, which must be free
+    fn on_place(self, ctx: &block::PlaceContext<'_>) -> Option<Self>
+    {
+        if !ctx.neighbor(Direction::Up)?.is::<BlockAir>()
+        {
+            return None
+        }
+        ctx.also_set(ctx.pos + Direction::Up.offset(), Self { half: DoorHalf::Upper, ..self });
+
+        Some(Self { half: DoorHalf::Lower, ..self })
+    }
+
+    // Takes the other half with it
+    fn on_break(&self, ctx: &block::TickContext<'_>) -> Vec<(Vec3<i32>, block::Choice)>
+    {
+        let other = self.other_half(ctx.pos);
+
+        match ctx.block_at(other).and_then(|block| block.unpack::<BlockDoor>())
+        {
+            Some(door) if door.half != self.half => vec![(other, block::Choice::new(BlockAir))],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl BlockDoor
+{
+    /// Get the world coordinates of the other half of this door, if it's at `pos`
+    pub fn other_half(&self, pos: Vec3<i32>) -> Vec3<i32>
+    {
+        match self.half
+        {
+            DoorHalf::Lower => pos + Direction::Up.offset(),
+            DoorHalf::Upper => pos + Direction::Down.offset(),
+        }
+    }
+
+    /// Open the door at the world coordinates `pos` in `world` if it's closed, or
+    /// close it otherwise. Both its halves are toggled at once, even when they're
+    /// in different chunks(see [World::set_atomic]). Returns whether there was a
+    /// door to toggle. This is a non-blocking operation.
+    pub fn toggle_open(world: &World, pos: Vec3<i32>) -> Result<bool, WorldAccessError>
+    {
+        let door = match world.try_get(pos)?.cast::<BlockDoor>()
+        {
+            Some(door) => *door,
+            None => return Ok(false),
+        };
+        let other = door.other_half(pos);
+        let open = !door.open;
+
+        let mut edits = vec![(pos, block::Choice::new(Self { open, ..door }))];
+        if let Some(half) = world.try_get(other)?.cast::<BlockDoor>().filter(|half| half.half != door.half)
+        {
+            edits.push((other, block::Choice::new(Self { open, ..*half })));
+        }
+        world.set_atomic(edits)?;
+
+        Ok(true)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+use crate::world::{ blockdef, block };
+use crate::math::{ Aabb, Vec3 };
+use crate::vanilla::props::Direction;
+
+use super::{ BlockDirt, BlockWater, BlockWheat };
+
+blockdef!
+{
+    id: "farmland",
+    name: "Farmland",
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockFarmland
+    {
+        /// How wet this farmland is, from `0`(dry) to [BlockFarmland::MAX_MOISTURE]
+        #[prop(0..8)]
+        pub moisture: u8,
+    }
+
+    // Slightly shorter than a full block
+    fn occludes(&self, face: Direction) -> bool { face != Direction::Up }
+
+    fn collision_boxes(&self) -> Vec<Aabb<f32>>
+    {
+        vec![Aabb { min: Vec3::zero(), max: Vec3::new(1.0, 15.0 / 16.0, 1.0) }]
+    }
+
+    // Soaks up nearby water, or dries out and turns back into dirt if nothing's
+    // planted on it
+    fn random_tick(&self, ctx: &block::TickContext<'_>) -> Option<block::Choice>
+    {
+        let above = ctx.neighbor(Direction::Up);
+
+        if above.as_ref().is_some_and(|above| above.occludes[Direction::Down as usize])
+        {
+            return Some(block::Choice::new(BlockDirt))
+        }
+        let moisture = match self.hydrated(ctx)
+        {
+            true => Self::MAX_MOISTURE,
+            false if self.moisture > 0 => self.moisture - 1,
+            false if above.is_some_and(|above| above.is::<BlockWheat>()) => 0,
+            false => return Some(block::Choice::new(BlockDirt)),
+        };
+
+        match moisture != self.moisture
+        {
+            true => Some(block::Choice::new(Self { moisture })),
+            false => None,
+        }
+    }
+}
+
+impl BlockFarmland
+{
+    /// Wettest farmland can be
+    pub const MAX_MOISTURE: u8 = 7;
+
+    /// Horizontal distance from which farmland soaks up water
+    pub const WATER_RANGE: i32 = 4;
+
+    /// Whether there's water within [BlockFarmland::WATER_RANGE] blocks horizontally,
+    /// at the same level as this farmland or one above
+    fn hydrated(&self, ctx: &block::TickContext<'_>) -> bool
+    {
+        let r = Self::WATER_RANGE;
+
+        (-r..=r)
+            .flat_map(|x| (0..=1).flat_map(move |y| (-r..=r).map(move |z| Vec3::new(x, y, z))))
+            .any(|offset| ctx
+                .block_at(ctx.pos + offset)
+                .is_some_and(|block| block.is::<BlockWater>()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,166 @@
+use crate::world::{ Block, blockdef, block };
+use crate::vanilla::props::Direction;
+
+use super::BlockAir;
+
+/// Blocks that flow into the air around them, see [flow]
+pub trait Fluid: Block + Copy + PartialEq
+{
+    /// Level lost for every block flowed sideways
+    const DECAY: u8;
+
+    /// Thinnest level a fluid can flow to
+    const MAX_LEVEL: u8 = 7;
+
+    /// Create this fluid with the given level and falling flag
+    fn new(level: u8, falling: bool) -> Self;
+
+    /// Get this fluid's level, where `0` is full and [Fluid::MAX_LEVEL] is the
+    /// thinnest, and whether it's falling
+    fn level(&self) -> (u8, bool);
+
+    /// Whether this is a source, ie. full and not falling, which never drains
+    fn is_source(&self) -> bool
+    {
+        self.level() == (0, false)
+    }
+}
+
+blockdef!
+{
+    id: "water",
+    name: "Water",
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockWater
+    {
+        /// How far this water is from its source, see [Fluid::level]
+        #[prop(0..8)]
+        pub level: u8,
+        /// Whether this water is fed from above rather than from the side
+        #[prop(bool)]
+        pub falling: bool,
+    }
+
+    fn solid(&self) -> bool { false }
+
+    fn occludes(&self, _: Direction) -> bool { false }
+
+    fn light_attenuation(&self) -> u8 { 1 }
+
+    // Soaks up explosions
+    fn hardness(&self) -> f32 { 100.0 }
+
+    fn on_neighbor_changed(&self, ctx: &block::TickContext<'_>, _dir: Direction) -> Option<block::Choice>
+    {
+        reflow(self, ctx)
+    }
+}
+
+blockdef!
+{
+    id: "lava",
+    name: "Lava",
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockLava
+    {
+        /// How far this lava is from its source, see [Fluid::level]
+        #[prop(0..8)]
+        pub level: u8,
+        /// Whether this lava is fed from above rather than from the side
+        #[prop(bool)]
+        pub falling: bool,
+    }
+
+    fn solid(&self) -> bool { false }
+
+    fn occludes(&self, _: Direction) -> bool { false }
+
+    // Thick enough to be seen only by its own glow
+    fn light_attenuation(&self) -> u8 { 15 }
+
+    fn light_emission(&self) -> u8 { 15 }
+
+    fn hardness(&self) -> f32 { 100.0 }
+
+    fn on_neighbor_changed(&self, ctx: &block::TickContext<'_>, _dir: Direction) -> Option<block::Choice>
+    {
+        reflow(self, ctx)
+    }
+}
+
+impl Fluid for BlockWater
+{
+    const DECAY: u8 = 1;
+
+    fn new(level: u8, falling: bool) -> Self { Self { level, falling } }
+
+    fn level(&self) -> (u8, bool) { (self.level, self.falling) }
+}
+
+impl Fluid for BlockLava
+{
+    // Runs half as far as water
+    const DECAY: u8 = 2;
+
+    fn new(level: u8, falling: bool) -> Self { Self { level, falling } }
+
+    fn level(&self) -> (u8, bool) { (self.level, self.falling) }
+}
+
+/// Get the fluid of type `T` that flows into the cell of `ctx`, if any: falling
+/// when there's `T` right above, or one level thinner than the fullest `T` next
+/// to it otherwise. Sources and falling fluid spread sideways wherever they are,
+/// but thinner fluid only does on top of solid ground.
+pub fn flow<T: Fluid>(ctx: &block::TickContext<'_>) -> Option<T>
+{
+    let fluid = |dir: Direction| ctx
+        .neighbor(dir)
+        .and_then(|info| info.unpack::<T>());
+
+    if fluid(Direction::Up).is_some()
+    {
+        return Some(T::new(0, true))
+    }
+
+    let level = Direction::HORIZONTAL
+        .iter()
+        .filter_map(|&dir|
+        {
+            let (level, falling) = fluid(dir)?.level();
+            let supported = ctx
+                .block_at(ctx.pos + dir.offset() + Direction::Down.offset())
+                .is_some_and(|below| below.solid);
+
+            match level == 0 || falling || supported
+            {
+                true => Some(if falling { 0 } else { level }),
+                false => None,
+            }
+        })
+        .min()?
+        + T::DECAY;
+
+    match level <= T::MAX_LEVEL
+    {
+        true => Some(T::new(level, false)),
+        false => None,
+    }
+}
+
+/// [Block::on_neighbor_changed] of fluids, which recompute their level unless
+/// they're a source and dry up once nothing flows into them anymore
+fn reflow<T: Fluid>(fluid: &T, ctx: &block::TickContext<'_>) -> Option<block::Choice>
+{
+    if fluid.is_source()
+    {
+        return None
+    }
+    match flow::<T>(ctx)
+    {
+        Some(new) if new == *fluid => None,
+        Some(new) => Some(block::Choice::new(new)),
+        None => Some(block::Choice::new(BlockAir)),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,263 @@
+use crate::world::{ blockdef, block };
+use crate::item::ItemStack;
+use crate::vanilla::props::Direction;
+
+/// Items that can be smelted, along with what they smelt into
+const RECIPES: &[(&str, &str)] = &[
+    ("cobblestone", "stone"),
+    ("sand", "glass"),
+    ("iron_ore", "iron_ingot"),
+    ("gold_ore", "gold_ingot"),
+];
+
+/// Items that can fuel a furnace, along with how many ticks they burn for
+const FUELS: &[(&str, u32)] = &[
+    ("coal", 1600),
+    ("wooden_planks", 300),
+    ("stick", 100),
+];
+
+blockdef!
+{
+    id: "furnace",
+    name: match self.lit
+    {
+        true => "Lit Furnace",
+        false => "Furnace",
+    },
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct BlockFurnace
+    {
+        /// Which side the opening of this furnace is facing
+        #[prop(North | South | East | West)]
+        pub facing: Direction,
+        /// Whether this furnace is burning fuel
+        #[prop(bool)]
+        pub lit: bool,
+        /// Items waiting to be smelted
+        #[prop(!)]
+        pub input: Option<ItemStack>,
+        /// Items waiting to be burnt
+        #[prop(!)]
+        pub fuel: Option<ItemStack>,
+        /// Items that were smelted
+        #[prop(!)]
+        pub output: Option<ItemStack>,
+        /// Ticks left before the last fuel item burnt runs out
+        #[prop(!)]
+        pub burn_ticks: u32,
+        /// Ticks spent smelting the current input item, out of [BlockFurnace::SMELT_TICKS]
+        #[prop(!)]
+        pub progress: u32,
+    }
+
+    fn light_emission(&self) -> u8
+    {
+        match self.lit
+        {
+            true => 13,
+            false => 0,
+        }
+    }
+
+    // Burns and smelts for one tick, then keeps ticking for as long as it's lit
+    fn on_scheduled_tick(&self, ctx: &block::TickContext<'_>) -> Option<block::Choice>
+    {
+        let mut next = self.clone();
+
+        next.step();
+        if next.lit
+        {
+            ctx.schedule_tick(1);
+        }
+        match next != *self
+        {
+            true => Some(block::Choice::new(next)),
+            false => None,
+        }
+    }
+
+    // ```text
+    // facing: u8
+    // lit: u8
+    // input, fuel, output: [slot; 3] // see `ItemStack::serialize_slot`
+    // burn_ticks: u32
+    // progress: u32
+    // ```
+    fn serialize(&self, out: &mut Vec<u8>)
+    {
+        out.push(Direction::ALL.iter().position(|&dir| dir == self.facing).unwrap() as u8);
+        out.push(self.lit as u8);
+        for slot in [&self.input, &self.fuel, &self.output]
+        {
+            ItemStack::serialize_slot(slot, out);
+        }
+        out.extend_from_slice(&self.burn_ticks.to_le_bytes());
+        out.extend_from_slice(&self.progress.to_le_bytes());
+    }
+
+    fn deserialize(bytes: &[u8]) -> Option<Self>
+    {
+        let (&facing, bytes) = bytes.split_first()?;
+        let facing = match Direction::ALL.get(facing as usize)?
+        {
+            dir @ (Direction::North | Direction::South | Direction::East | Direction::West) => *dir,
+            _ => return None,
+        };
+        let (&lit, mut bytes) = bytes.split_first()?;
+        let lit = match lit
+        {
+            0 => false,
+            1 => true,
+            _ => return None,
+        };
+
+        let input = ItemStack::deserialize_slot(&mut bytes)?;
+        let fuel = ItemStack::deserialize_slot(&mut bytes)?;
+        let output = ItemStack::deserialize_slot(&mut bytes)?;
+
+        if bytes.len() != 8
+        {
+            return None
+        }
+        let (burn_ticks, progress) = bytes.split_at(4);
+
+        Some(Self
+        {
+            facing,
+            lit,
+            input,
+            fuel,
+            output,
+            burn_ticks: u32::from_le_bytes([burn_ticks[0], burn_ticks[1], burn_ticks[2], burn_ticks[3]]),
+            progress: u32::from_le_bytes([progress[0], progress[1], progress[2], progress[3]]),
+        })
+    }
+}
+
+impl BlockFurnace
+{
+    /// Number of ticks it takes to smelt one item
+    pub const SMELT_TICKS: u32 = 200;
+
+    /// Create an empty, unlit furnace
+    pub fn new(facing: Direction) -> Self
+    {
+        Self
+        {
+            facing,
+            lit: false,
+            input: None,
+            fuel: None,
+            output: None,
+            burn_ticks: 0,
+            progress: 0,
+        }
+    }
+
+    /// Add `stack` to the items waiting to be smelted, returning whatever didn't
+    /// fit. Items that can't be smelted don't fit. The furnace only starts burning
+    /// on its next scheduled tick, see [World::schedule_tick](crate::world::World::schedule_tick).
+    pub fn insert_input(&mut self, stack: ItemStack) -> Option<ItemStack>
+    {
+        match smelt(stack.id.as_str())
+        {
+            Some(_) => ItemStack::insert_into(&mut self.input, stack),
+            None => Some(stack),
+        }
+    }
+
+    /// Add `stack` to the items waiting to be burnt, returning whatever didn't
+    /// fit. Items that can't be burnt don't fit. See [BlockFurnace::insert_input].
+    pub fn insert_fuel(&mut self, stack: ItemStack) -> Option<ItemStack>
+    {
+        match burn_time(stack.id.as_str())
+        {
+            Some(_) => ItemStack::insert_into(&mut self.fuel, stack),
+            None => Some(stack),
+        }
+    }
+
+    /// Take all the smelted items out of this furnace
+    pub fn take_output(&mut self) -> Option<ItemStack>
+    {
+        self.output.take()
+    }
+
+    /// Whether the input item can be smelted, with room for its result
+    fn smeltable(&self) -> bool
+    {
+        match (self.input.as_ref().and_then(|input| smelt(input.id.as_str())), &self.output)
+        {
+            (Some(_), None) => true,
+            (Some(result), Some(output)) => output.id.as_str() == result && output.count < ItemStack::MAX_COUNT,
+            (None, _) => false,
+        }
+    }
+
+    /// Burn a fuel item if the last one ran out and there's something to smelt
+    fn refuel(&mut self)
+    {
+        if self.burn_ticks != 0 || !self.smeltable()
+        {
+            return
+        }
+        if let Some(ticks) = self.fuel.as_ref().and_then(|fuel| burn_time(fuel.id.as_str()))
+        {
+            self.burn_ticks = ticks;
+            ItemStack::take_one(&mut self.fuel);
+        }
+    }
+
+    /// Advance this furnace by one tick
+    fn step(&mut self)
+    {
+        self.refuel();
+        if self.burn_ticks == 0 || !self.smeltable()
+        {
+            self.progress = 0;
+        }
+        if self.burn_ticks != 0
+        {
+            self.burn_ticks -= 1;
+            if self.smeltable()
+            {
+                self.progress += 1;
+            }
+        }
+        if self.progress == Self::SMELT_TICKS
+        {
+            let result = smelt(self.input.as_ref().unwrap().id.as_str()).unwrap();
+
+            ItemStack::take_one(&mut self.input);
+            match &mut self.output
+            {
+                Some(output) => output.count += 1,
+                None => self.output = Some(ItemStack::new(result, 1)),
+            }
+            self.progress = 0;
+        }
+        // Keeps burning without a break if there's more to smelt
+        self.refuel();
+        self.lit = self.burn_ticks != 0;
+    }
+}
+
+/// Get what the item `id` smelts into, if it can be smelted
+fn smelt(id: &str) -> Option<&'static str>
+{
+    RECIPES
+        .iter()
+        .find(|&&(input, _)| input == id)
+        .map(|&(_, output)| output)
+}
+
+/// Get how many ticks the item `id` burns for, if it can be burnt
+fn burn_time(id: &str) -> Option<u32>
+{
+    FUELS
+        .iter()
+        .find(|&&(fuel, _)| fuel == id)
+        .map(|&(_, ticks)| ticks)
+}
\ No newline at end of file
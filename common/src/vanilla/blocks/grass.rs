@@ -0,0 +1,28 @@
+use crate::world::{ blockdef, block };
+use crate::vanilla::props::Direction;
+
+use super::BlockDirt;
+
+blockdef!
+{
+    id: "grass",
+    name: "Grass Block",
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockGrass
+    {
+        /// Whether this grass is covered in snow
+        #[prop(bool)]
+        pub snowy: bool,
+    }
+
+    // Dies out when smothered by the block above
+    fn random_tick(&self, ctx: &block::TickContext<'_>) -> Option<block::Choice>
+    {
+        match ctx.neighbor(Direction::Up)
+        {
+            Some(above) if above.occludes[Direction::Down as usize] => Some(block::Choice::new(BlockDirt)),
+            _ => None,
+        }
+    }
+}
\ No newline at end of file
@@ -1,7 +1,68 @@
 mod air;
 mod wood;
 mod chest;
+mod sign;
+mod stone;
+mod dirt;
+mod grass;
+mod stairs;
+mod fluid;
+mod torch;
+mod furnace;
+mod door;
+mod farmland;
+mod wheat;
+mod wool;
+mod redstone;
+mod obsidian;
+mod tnt;
 
 pub use air::*;
 pub use wood::*;
-pub use chest::*;
\ No newline at end of file
+pub use chest::*;
+pub use sign::*;
+pub use stone::*;
+pub use dirt::*;
+pub use grass::*;
+pub use stairs::*;
+pub use fluid::*;
+pub use torch::*;
+pub use furnace::*;
+pub use door::*;
+pub use farmland::*;
+pub use wheat::*;
+pub use wool::*;
+pub use redstone::*;
+pub use obsidian::*;
+pub use tnt::*;
+
+use crate::world::block;
+
+/// Register every vanilla block type to `registry`, [BlockAir] first
+pub fn register_all(registry: &mut block::Registry)
+{
+    registry.register::<BlockAir>();
+    registry.register::<BlockWoodenPlanks>();
+    registry.register::<BlockWoodenSlab>();
+    registry.register::<BlockChest>();
+    registry.register::<BlockSign>();
+    registry.register::<BlockStone>();
+    registry.register::<BlockDirt>();
+    registry.register::<BlockGrass>();
+    registry.register::<BlockStairs>();
+    registry.register::<BlockWater>();
+    registry.register::<BlockLava>();
+    registry.register::<BlockTorch>();
+    registry.register::<BlockFurnace>();
+    registry.register::<BlockDoor>();
+    registry.register::<BlockFarmland>();
+    registry.register::<BlockWheat>();
+    registry.register::<BlockLog>();
+    registry.register::<BlockLeaves>();
+    registry.register::<BlockWool>();
+    registry.register::<BlockRedstoneDust>();
+    registry.register::<BlockRedstoneTorch>();
+    registry.register::<BlockLever>();
+    registry.register::<BlockObsidian>();
+    registry.register::<BlockTnt>();
+}
\ No newline at end of file
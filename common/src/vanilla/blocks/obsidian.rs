@@ -0,0 +1,13 @@
+use crate::world::blockdef;
+
+blockdef!
+{
+    id: "obsidian",
+    name: "Obsidian",
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockObsidian;
+
+    // Withstands any explosion
+    fn hardness(&self) -> f32 { 50.0 }
+}
\ No newline at end of file
@@ -0,0 +1,104 @@
+use crate::world::{ World, blockdef, block };
+use crate::math::{ Aabb, Vec3 };
+use crate::vanilla::props::Direction;
+
+use super::{ BlockRedstoneTorch, BlockLever };
+
+blockdef!
+{
+    id: "redstone_dust",
+    name: "Redstone Dust",
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockRedstoneDust
+    {
+        /// Strength of the signal carried, up to [BlockRedstoneDust::MAX_POWER]
+        #[prop(0..16)]
+        pub power: u8,
+    }
+
+    fn occludes(&self, _: Direction) -> bool { false }
+
+    // Flat on the ground
+    fn collision_boxes(&self) -> Vec<Aabb<f32>> { Vec::new() }
+
+    // Picks up the signal around it right away
+    fn on_place(self, ctx: &block::PlaceContext<'_>) -> Option<Self>
+    {
+        Some(Self { power: Self::strongest(|dir| ctx.neighbor(dir)) })
+    }
+
+    // Carries the strongest signal around, weakened by one. Since it strictly
+    // weakens, changes always die out through the update queue, even in loops.
+    fn on_neighbor_changed(&self, ctx: &block::TickContext<'_>, _dir: Direction) -> Option<block::Choice>
+    {
+        let power = Self::strongest(|dir| ctx.neighbor(dir));
+
+        match power != self.power
+        {
+            true => Some(block::Choice::new(Self { power })),
+            false => None,
+        }
+    }
+}
+
+impl BlockRedstoneDust
+{
+    /// Strength of the signal given off by power sources
+    pub const MAX_POWER: u8 = 15;
+
+    /// Get the power given off by the summarized block if it's a source of power,
+    /// ie. a [BlockRedstoneTorch], or `0` otherwise
+    pub fn source_power(block: &block::Info) -> u8
+    {
+        let powered = block.is::<BlockRedstoneTorch>()
+            || block.unpack::<BlockLever>().is_some_and(|lever| lever.powered);
+
+        match powered
+        {
+            true => Self::MAX_POWER,
+            false => 0,
+        }
+    }
+
+    /// Get the power dust would carry given its `neighbor`s: that of the strongest
+    /// source of power, or of the strongest dust weakened by one
+    fn strongest(neighbor: impl Fn(Direction) -> Option<block::Info>) -> u8
+    {
+        Direction::ALL
+            .iter()
+            .filter_map(|&dir| neighbor(dir))
+            .map(|block| match block.unpack::<BlockRedstoneDust>()
+            {
+                Some(dust) => dust.power.saturating_sub(1),
+                None => Self::source_power(&block),
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Get the horizontal sides the dust at the world coordinates `pos` in `world`
+    /// visually connects to, for rendering. That's every side with dust or a source
+    /// of power, extended into a straight line if there's only one, and none for a
+    /// lone dot.
+    pub fn connections(world: &World, pos: Vec3<i32>) -> Vec<Direction>
+    {
+        let sides = Direction::HORIZONTAL
+            .iter()
+            .copied()
+            .filter(|&dir| world
+                .block_at(pos + dir.offset())
+                .is_some_and(|block| block.is::<BlockRedstoneDust>() || block.is::<BlockRedstoneTorch>() || block.is::<BlockLever>()))
+            .collect::<Vec<_>>();
+
+        match sides[..]
+        {
+            [side] => Direction::HORIZONTAL
+                .iter()
+                .copied()
+                .filter(|&dir| dir.axis() == side.axis())
+                .collect(),
+            _ => sides,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,48 @@
+use crate::world::{ World, WorldAccessError, blockdef, block };
+use crate::math::{ Aabb, Vec3 };
+use crate::vanilla::props::Direction;
+
+blockdef!
+{
+    id: "lever",
+    name: "Lever",
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockLever
+    {
+        /// Face of the block this lever is attached to
+        #[prop(North | South | East | West | Up | Down)]
+        pub attached: Direction,
+        /// Whether this lever is flipped on, powering its neighbors
+        #[prop(bool)]
+        pub powered: bool,
+    }
+
+    fn occludes(&self, _: Direction) -> bool { false }
+
+    fn collision_boxes(&self) -> Vec<Aabb<f32>> { Vec::new() }
+
+    // Attached to the face that was clicked, off
+    fn on_place(self, ctx: &block::PlaceContext<'_>) -> Option<Self>
+    {
+        Some(Self { attached: ctx.face, powered: false })
+    }
+}
+
+impl BlockLever
+{
+    /// Flip the lever at the world coordinates `pos` in `world` on if it's off, or
+    /// off otherwise. Returns whether there was a lever to flip. This is a
+    /// non-blocking operation.
+    pub fn toggle(world: &World, pos: Vec3<i32>) -> Result<bool, WorldAccessError>
+    {
+        let lever = match world.try_get(pos)?.cast::<BlockLever>()
+        {
+            Some(lever) => *lever,
+            None => return Ok(false),
+        };
+        world.try_set(pos, Self { powered: !lever.powered, ..lever })?;
+
+        Ok(true)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+mod dust;
+mod torch;
+mod lever;
+
+pub use dust::*;
+pub use torch::*;
+pub use lever::*;
\ No newline at end of file
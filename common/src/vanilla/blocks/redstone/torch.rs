@@ -0,0 +1,45 @@
+use crate::world::{ blockdef, block };
+use crate::math::Aabb;
+use crate::vanilla::props::Direction;
+
+use super::super::BlockAir;
+
+blockdef!
+{
+    id: "redstone_torch",
+    name: "Redstone Torch",
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockRedstoneTorch
+    {
+        /// Face of the block this torch is attached to, see [BlockTorch](super::super::BlockTorch)
+        #[prop(Up | North | South | East | West)]
+        pub attached: Direction,
+    }
+
+    fn occludes(&self, _: Direction) -> bool { false }
+
+    fn light_emission(&self) -> u8 { 7 }
+
+    fn collision_boxes(&self) -> Vec<Aabb<f32>> { Vec::new() }
+
+    fn on_place(self, ctx: &block::PlaceContext<'_>) -> Option<Self>
+    {
+        match ctx.face
+        {
+            Direction::Down => None,
+            attached => Some(Self { attached }),
+        }
+    }
+
+    fn on_neighbor_changed(&self, ctx: &block::TickContext<'_>, dir: Direction) -> Option<block::Choice>
+    {
+        let support = self.attached.opposite();
+
+        match dir == support && ctx.neighbor(support).is_some_and(|block| block.is::<BlockAir>())
+        {
+            true => Some(block::Choice::new(BlockAir)),
+            false => None,
+        }
+    }
+}
\ No newline at end of file
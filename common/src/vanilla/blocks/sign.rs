@@ -0,0 +1,96 @@
+use std::borrow::Cow;
+
+use crate::world::blockdef;
+use crate::vanilla::props::Direction;
+
+blockdef!
+{
+    id: "sign",
+    name: match self.lines[0].as_str()
+    {
+        "" => Cow::Borrowed("Sign"),
+        line => Cow::Owned(format!("Sign ({})", line)),
+    },
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct BlockSign
+    {
+        /// Which side the text of this sign is facing
+        #[prop(North | South | East | West)]
+        pub facing: Direction,
+        /// Text written on this sign, from top to bottom
+        #[prop(!)]
+        pub lines: [String; 4],
+    }
+
+    // ```text
+    // facing: u8
+    // lines: [len: u8, utf8: [u8; len]; 4]
+    // ```
+    fn serialize(&self, out: &mut Vec<u8>)
+    {
+        out.push(Direction::ALL.iter().position(|&dir| dir == self.facing).unwrap() as u8);
+        for line in &self.lines
+        {
+            // Lines set directly(not through `set_line`) may be too long, so
+            // cut them on a character boundary
+            let len = (0..=line.len().min(u8::MAX as usize))
+                .rev()
+                .find(|&i| line.is_char_boundary(i))
+                .unwrap();
+
+            out.push(len as u8);
+            out.extend_from_slice(&line.as_bytes()[..len]);
+        }
+    }
+
+    fn deserialize(bytes: &[u8]) -> Option<Self>
+    {
+        let (&facing, mut bytes) = bytes.split_first()?;
+        let facing = match Direction::ALL.get(facing as usize)?
+        {
+            dir @ (Direction::North | Direction::South | Direction::East | Direction::West) => *dir,
+            _ => return None,
+        };
+
+        let mut lines = <[String; 4]>::default();
+        for line in &mut lines
+        {
+            let (&len, rest) = bytes.split_first()?;
+            if rest.len() < len as usize
+            {
+                return None
+            }
+            let (text, rest) = rest.split_at(len as usize);
+
+            *line = String::from_utf8(text.to_vec()).ok()?;
+            bytes = rest;
+        }
+        // Trailing bytes
+        if !bytes.is_empty()
+        {
+            return None
+        }
+        Some(Self { facing, lines })
+    }
+}
+
+impl BlockSign
+{
+    /// Maximum number of characters on a line of a sign
+    pub const MAX_LINE_LEN: usize = 32;
+
+    /// Set the `idx`th line of this sign(from the top) to `text`, truncated to
+    /// [BlockSign::MAX_LINE_LEN] characters. Does nothing if `idx` is out of
+    /// bounds.
+    pub fn set_line(&mut self, idx: usize, text: &str)
+    {
+        if let Some(line) = self.lines.get_mut(idx)
+        {
+            *line = text
+                .chars()
+                .take(Self::MAX_LINE_LEN)
+                .collect();
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,163 @@
+use crate::world::{ blockdef, block };
+use crate::math::{ Aabb, Vec3 };
+use crate::vanilla::props::Direction;
+
+/// Which half of its cell a block sits in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Half
+{
+    Bottom,
+    Top,
+}
+
+/// How stairs connect to the stairs next to them, as seen from their front.
+/// Stairs turn into corners when placed against the side of other stairs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StairsShape
+{
+    Straight,
+    /// Extra step on the left
+    InnerLeft,
+    /// Extra step on the right
+    InnerRight,
+    /// Only the left half of the step
+    OuterLeft,
+    /// Only the right half of the step
+    OuterRight,
+}
+
+blockdef!
+{
+    id: "stairs",
+    name: "Stairs",
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockStairs
+    {
+        /// Side of the cell the step is on, ie. the direction walked when going
+        /// up these stairs
+        #[prop(North | South | East | West)]
+        pub facing: Direction,
+        /// Half of the cell filled entirely, with the step in the other
+        #[prop(Bottom | Top)]
+        pub half: Half,
+        /// Depends on the neighboring stairs, see [BlockStairs::connect]
+        #[prop(Straight | InnerLeft | InnerRight | OuterLeft | OuterRight)]
+        pub shape: StairsShape,
+    }
+
+    fn occludes(&self, face: Direction) -> bool
+    {
+        let full = match self.half
+        {
+            Half::Bottom => Direction::Down,
+            Half::Top => Direction::Up,
+        };
+        let back = match self.shape
+        {
+            StairsShape::OuterLeft | StairsShape::OuterRight => false,
+            _ => face == self.facing,
+        };
+        let side = match self.shape
+        {
+            StairsShape::InnerLeft => face == self.facing.rotate_y_ccw(),
+            StairsShape::InnerRight => face == self.facing.rotate_y_cw(),
+            _ => false,
+        };
+
+        face == full || back || side
+    }
+
+    fn collision_boxes(&self) -> Vec<Aabb<f32>>
+    {
+        // Step boxes for stairs facing north, in `(x, z)` ranges
+        let steps: &[([f32; 2], [f32; 2])] = match self.shape
+        {
+            StairsShape::Straight => &[([0.0, 1.0], [0.0, 0.5])],
+            StairsShape::InnerLeft => &[([0.0, 1.0], [0.0, 0.5]), ([0.0, 0.5], [0.5, 1.0])],
+            StairsShape::InnerRight => &[([0.0, 1.0], [0.0, 0.5]), ([0.5, 1.0], [0.5, 1.0])],
+            StairsShape::OuterLeft => &[([0.0, 0.5], [0.0, 0.5])],
+            StairsShape::OuterRight => &[([0.5, 1.0], [0.0, 0.5])],
+        };
+        let (slab, step) = match self.half
+        {
+            Half::Bottom => ([0.0, 0.5], [0.5, 1.0]),
+            Half::Top => ([0.5, 1.0], [0.0, 0.5]),
+        };
+
+        let mut boxes = vec![Aabb { min: Vec3::new(0.0, slab[0], 0.0), max: Vec3::new(1.0, slab[1], 1.0) }];
+        for (x, z) in steps
+        {
+            let a = self.rotate(Vec3::new(x[0], step[0], z[0]));
+            let b = self.rotate(Vec3::new(x[1], step[1], z[1]));
+
+            boxes.push(Aabb { min: Vec3::partial_min(a, b), max: Vec3::partial_max(a, b) });
+        }
+        boxes
+    }
+
+    fn on_neighbor_changed(&self, ctx: &block::TickContext<'_>, _dir: Direction) -> Option<block::Choice>
+    {
+        let shape = self.connect(ctx);
+
+        if shape == self.shape
+        {
+            return None
+        }
+        Some(block::Choice::new(Self { shape, ..*self }))
+    }
+}
+
+impl BlockStairs
+{
+    /// Get the shape these stairs take given the stairs around them: a corner
+    /// when their back or front is against the side of other stairs of the same
+    /// half, unless that would break a line of stairs alongside them.
+    pub fn connect(&self, ctx: &block::TickContext<'_>) -> StairsShape
+    {
+        let stairs = |dir: Direction| ctx
+            .neighbor(dir)
+            .and_then(|info| info.unpack::<BlockStairs>())
+            .filter(|stairs| stairs.half == self.half);
+        // Whether the stairs in direction `dir` aren't lined up with these
+        let free = |dir: Direction| stairs(dir).is_none_or(|stairs| stairs.facing != self.facing);
+
+        // Outer corner
+        if let Some(back) = stairs(self.facing)
+        {
+            if back.facing != self.facing && back.facing != self.facing.opposite() && free(back.facing.opposite())
+            {
+                return match back.facing == self.facing.rotate_y_ccw()
+                {
+                    true => StairsShape::OuterLeft,
+                    false => StairsShape::OuterRight,
+                }
+            }
+        }
+        // Inner corner
+        if let Some(front) = stairs(self.facing.opposite())
+        {
+            if front.facing != self.facing && front.facing != self.facing.opposite() && free(front.facing)
+            {
+                return match front.facing == self.facing.rotate_y_ccw()
+                {
+                    true => StairsShape::InnerLeft,
+                    false => StairsShape::InnerRight,
+                }
+            }
+        }
+        StairsShape::Straight
+    }
+
+    /// Rotate a point in this block's cell from facing north to facing `self.facing`
+    fn rotate(&self, p: Vec3<f32>) -> Vec3<f32>
+    {
+        match self.facing
+        {
+            Direction::East => Vec3::new(1.0 - p.z, p.y, p.x),
+            Direction::South => Vec3::new(1.0 - p.x, p.y, 1.0 - p.z),
+            Direction::West => Vec3::new(p.z, p.y, 1.0 - p.x),
+            _ => p,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,12 @@
+use crate::world::blockdef;
+
+blockdef!
+{
+    id: "stone",
+    name: "Stone",
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockStone;
+
+    fn hardness(&self) -> f32 { 1.5 }
+}
\ No newline at end of file
@@ -0,0 +1,110 @@
+use crate::world::{ World, WorldAccessError, FillReport, blockdef, block };
+use crate::math::Vec3;
+use crate::vanilla::props::Direction;
+
+use super::{ BlockAir, BlockRedstoneDust };
+
+blockdef!
+{
+    id: "tnt",
+    name: "TNT",
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockTnt
+    {
+        /// Whether this TNT's fuse is lit, see [BlockTnt::prime]
+        #[prop(bool)]
+        pub primed: bool,
+    }
+
+    fn hardness(&self) -> f32 { 0.0 }
+
+    // Lit by redstone power
+    fn on_neighbor_changed(&self, ctx: &block::TickContext<'_>, _dir: Direction) -> Option<block::Choice>
+    {
+        let powered = Direction::ALL
+            .iter()
+            .filter_map(|&dir| ctx.neighbor(dir))
+            .any(|block| BlockRedstoneDust::source_power(&block) > 0
+                || block.unpack::<BlockRedstoneDust>().is_some_and(|dust| dust.power > 0));
+
+        if self.primed || !powered
+        {
+            return None
+        }
+        ctx.schedule_tick(Self::FUSE_TICKS);
+
+        Some(block::Choice::new(Self { primed: true }))
+    }
+
+    // Blows up once the fuse runs out, after its own cell is cleared
+    fn on_scheduled_tick(&self, ctx: &block::TickContext<'_>) -> Option<block::Choice>
+    {
+        if !self.primed
+        {
+            return None
+        }
+        let pos = ctx.pos;
+        ctx.defer(move |world| { Self::explode(world, pos); });
+
+        Some(block::Choice::new(BlockAir))
+    }
+}
+
+impl BlockTnt
+{
+    /// Number of ticks between priming TNT and it exploding
+    pub const FUSE_TICKS: u64 = 80;
+    /// Number of ticks between TNT being primed by another explosion and it
+    /// exploding, see [BlockTnt::explode]
+    pub const CHAIN_FUSE_TICKS: u64 = 20;
+    /// Radius of an explosion, which blocks closer than this by more than their
+    /// [hardness](crate::world::Block::hardness) don't survive
+    pub const POWER: f32 = 4.0;
+
+    /// Light the fuse of the TNT at the world coordinates `pos` in `world`, which
+    /// explodes [BlockTnt::FUSE_TICKS] ticks from now. Returns whether there was
+    /// unprimed TNT to prime. This is a non-blocking operation.
+    pub fn prime(world: &World, pos: Vec3<i32>) -> Result<bool, WorldAccessError>
+    {
+        let primed = world.compare_and_set(pos, Self { primed: false }, Self { primed: true })?;
+
+        if primed
+        {
+            world.schedule_tick(pos, Self::FUSE_TICKS);
+        }
+        Ok(primed)
+    }
+
+    /// Blow up the world coordinates `center` in `world`, clearing every block which
+    /// isn't hard enough to withstand the explosion(see [BlockTnt::POWER]). Other TNT
+    /// in range is primed instead, exploding [BlockTnt::CHAIN_FUSE_TICKS] ticks from
+    /// now. This is a non-blocking operation, see [World::edit_sphere].
+    pub fn explode(world: &World, center: Vec3<i32>) -> FillReport
+    {
+        let mut chained = Vec::new();
+        let report = world.edit_sphere(center, Self::POWER, |pos, distance, block|
+        {
+            match block.cast::<BlockTnt>().map(|tnt| tnt.primed)
+            {
+                Some(false) =>
+                {
+                    chained.push(pos);
+                    Some(block::Choice::new(Self { primed: true }))
+                },
+                Some(true) => None,
+                None if !block.is::<BlockAir>() && block.hardness() < Self::POWER - distance =>
+                {
+                    Some(block::Choice::new(BlockAir))
+                },
+                None => None,
+            }
+        });
+
+        for pos in chained
+        {
+            world.schedule_tick(pos, Self::CHAIN_FUSE_TICKS);
+        }
+        report
+    }
+}
\ No newline at end of file
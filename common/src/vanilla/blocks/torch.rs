@@ -0,0 +1,49 @@
+use crate::world::{ blockdef, block };
+use crate::math::Aabb;
+use crate::vanilla::props::Direction;
+
+use super::BlockAir;
+
+blockdef!
+{
+    id: "torch",
+    name: "Torch",
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockTorch
+    {
+        /// Face of the block this torch is attached to, where `Up` means it's
+        /// standing on the block below
+        #[prop(Up | North | South | East | West)]
+        pub attached: Direction,
+    }
+
+    fn occludes(&self, _: Direction) -> bool { false }
+
+    fn light_emission(&self) -> u8 { 14 }
+
+    // Can be hit, but not bumped into
+    fn collision_boxes(&self) -> Vec<Aabb<f32>> { Vec::new() }
+
+    // Attached to the face that was clicked, but never to ceilings
+    fn on_place(self, ctx: &block::PlaceContext<'_>) -> Option<Self>
+    {
+        match ctx.face
+        {
+            Direction::Down => None,
+            attached => Some(Self { attached }),
+        }
+    }
+
+    // Pops off once there's nothing left to hold it
+    fn on_neighbor_changed(&self, ctx: &block::TickContext<'_>, dir: Direction) -> Option<block::Choice>
+    {
+        let support = self.attached.opposite();
+
+        match dir == support && ctx.neighbor(support).is_some_and(|block| block.is::<BlockAir>())
+        {
+            true => Some(block::Choice::new(BlockAir)),
+            false => None,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,71 @@
+use crate::world::{ blockdef, block };
+use crate::math::Aabb;
+use crate::item::ItemStack;
+use crate::vanilla::props::Direction;
+
+use super::{ BlockAir, BlockFarmland };
+
+blockdef!
+{
+    id: "wheat",
+    name: "Wheat",
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockWheat
+    {
+        /// How grown this wheat is, up to [BlockWheat::MAX_AGE]
+        #[prop(0..8)]
+        pub age: u8,
+    }
+
+    fn solid(&self) -> bool { false }
+
+    fn occludes(&self, _: Direction) -> bool { false }
+
+    fn collision_boxes(&self) -> Vec<Aabb<f32>> { Vec::new() }
+
+    // Only grows when planted on farmland
+    fn random_tick(&self, ctx: &block::TickContext<'_>) -> Option<block::Choice>
+    {
+        let planted = ctx
+            .neighbor(Direction::Down)
+            .is_some_and(|below| below.is::<BlockFarmland>());
+
+        match planted && self.age < Self::MAX_AGE
+        {
+            true => Some(block::Choice::new(Self { age: self.age + 1 })),
+            false => None,
+        }
+    }
+
+    // Pops off once the farmland below is gone
+    fn on_neighbor_changed(&self, ctx: &block::TickContext<'_>, dir: Direction) -> Option<block::Choice>
+    {
+        let planted = ctx
+            .neighbor(Direction::Down)
+            .map(|below| below.is::<BlockFarmland>());
+
+        match (dir, planted)
+        {
+            (Direction::Down, Some(false)) => Some(block::Choice::new(BlockAir)),
+            _ => None,
+        }
+    }
+}
+
+impl BlockWheat
+{
+    /// Age of fully grown wheat
+    pub const MAX_AGE: u8 = 7;
+
+    /// Items this wheat would drop when broken: seeds back, and wheat too when it's
+    /// fully grown. Nothing spawns them yet.
+    pub fn drops(&self) -> Vec<ItemStack>
+    {
+        match self.age
+        {
+            Self::MAX_AGE => vec![ItemStack::new("wheat", 1), ItemStack::new("wheat_seeds", 2)],
+            _ => vec![ItemStack::new("wheat_seeds", 1)],
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,96 @@
+use crate::world::{ blockdef, block };
+use crate::vanilla::props::{ Direction, WoodVariant };
+
+use super::BlockLog;
+use super::super::BlockAir;
+
+blockdef!
+{
+    id: "leaves",
+    name: format!("{} Leaves", self.variant),
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockLeaves
+    {
+        /// The type of leaves
+        #[prop(Oak | Spruce | Birch | Jungle | Acacia | DarkOak)]
+        pub variant: WoodVariant,
+        /// Number of blocks to the nearest log through other leaves, up to
+        /// [BlockLeaves::MAX_DISTANCE]
+        #[prop(0..8)]
+        pub distance: u8,
+        /// Whether these leaves were placed by hand, and never decay
+        #[prop(bool)]
+        pub persistent: bool,
+    }
+
+    // Can be seen through
+    fn occludes(&self, _: Direction) -> bool { false }
+
+    fn light_attenuation(&self) -> u8 { 1 }
+
+    // Leaves are one further from a log than their closest neighbor, and spread
+    // changes to their own neighbors through the update queue
+    fn on_neighbor_changed(&self, ctx: &block::TickContext<'_>, _dir: Direction) -> Option<block::Choice>
+    {
+        let distance = Direction::ALL
+            .iter()
+            .map(|&dir| ctx.pos + dir.offset())
+            .filter_map(|pos| match ctx.block_at(pos)?.is::<BlockLog>()
+            {
+                true => Some(0),
+                false => ctx.block_as::<BlockLeaves>(pos).map(|leaves| leaves.distance),
+            })
+            .min()
+            .map_or(Self::MAX_DISTANCE, |n| (n + 1).min(Self::MAX_DISTANCE));
+
+        match distance != self.distance
+        {
+            true => Some(block::Choice::new(Self { distance, ..*self })),
+            false => None,
+        }
+    }
+
+    // Decays once too far from any log
+    fn random_tick(&self, _ctx: &block::TickContext<'_>) -> Option<block::Choice>
+    {
+        match !self.persistent && self.distance == Self::MAX_DISTANCE
+        {
+            true => Some(block::Choice::new(BlockAir)),
+            false => None,
+        }
+    }
+
+    // Too many props to be packed
+    // ```text
+    // variant: u8
+    // distance: u8
+    // persistent: u8
+    // ```
+    fn serialize(&self, out: &mut Vec<u8>)
+    {
+        out.push(WoodVariant::ALL.iter().position(|&variant| variant == self.variant).unwrap() as u8);
+        out.push(self.distance);
+        out.push(self.persistent as u8);
+    }
+
+    fn deserialize(bytes: &[u8]) -> Option<Self>
+    {
+        match *bytes
+        {
+            [variant, distance, persistent] if distance <= Self::MAX_DISTANCE && persistent <= 1 => Some(Self
+            {
+                variant: *WoodVariant::ALL.get(variant as usize)?,
+                distance,
+                persistent: persistent == 1,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl BlockLeaves
+{
+    /// Distance of leaves that aren't connected to any log
+    pub const MAX_DISTANCE: u8 = 7;
+}
\ No newline at end of file
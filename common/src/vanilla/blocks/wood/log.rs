@@ -0,0 +1,17 @@
+use crate::world::blockdef;
+use crate::vanilla::props::WoodVariant;
+
+
+blockdef!
+{
+    id: "log",
+    name: format!("{} Log", self.variant),
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockLog
+    {
+        /// The type of log
+        #[prop(Oak | Spruce | Birch | Jungle | Acacia | DarkOak)]
+        pub variant: WoodVariant
+    }
+}
\ No newline at end of file
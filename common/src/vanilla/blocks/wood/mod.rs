@@ -1,33 +1,13 @@
 mod planks;
 mod slabs;
+mod log;
+mod leaves;
 
 pub use planks::*;
 pub use slabs::*;
+pub use log::*;
+pub use leaves::*;
 
-/// The variants of wood in the game
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum WoodVariant
-{
-    Oak,
-    Spruce,
-    Birch,
-    Jungle,
-    Acacia,
-    DarkOak,
-}
-
-impl std::fmt::Display for WoodVariant
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
-    {
-        write!(f, "{}", match self
-        {
-            WoodVariant::Oak => "Oak",
-            WoodVariant::Spruce => "Spruce",
-            WoodVariant::Birch => "Birch",
-            WoodVariant::Jungle => "Jungle",
-            WoodVariant::Acacia => "Acacia",
-            WoodVariant::DarkOak => "Dark Oak",
-        })
-    }
-}
\ No newline at end of file
+/// Deprecated: moved to [props](crate::vanilla::props) along with every other
+/// property type, and only re-exported here for existing imports
+pub use crate::vanilla::props::WoodVariant;
\ No newline at end of file
@@ -1,6 +1,6 @@
 use crate::world::blockdef;
+use crate::vanilla::props::WoodVariant;
 
-use super::WoodVariant;
 
 blockdef!
 {
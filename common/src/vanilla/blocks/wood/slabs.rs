@@ -1,8 +1,9 @@
 // use crate::world::blockdef;
-use crate::world::blockdef;
-use crate::math::Direction;
+use crate::world::{ blockdef, block };
+use crate::math::{ Aabb, Vec3 };
+use crate::vanilla::props::{ Direction, WoodVariant };
 
-use super::WoodVariant;
+use super::BlockWoodenPlanks;
 
 blockdef!
 {
@@ -17,9 +18,46 @@ blockdef!
         /// with its largest face touching the north side of the block
         /// boundary.
         #[prop(North | South | East | West | Up | Down)]
-        facing: Direction,
+        pub facing: Direction,
         /// The type wooden slab
         #[prop(Oak | Spruce | Birch | Jungle | Acacia | DarkOak)]
-        variant: WoodVariant,
+        pub variant: WoodVariant,
+    }
+
+    // Only the half touching the block boundary is covered
+    fn occludes(&self, face: Direction) -> bool { face == self.facing }
+
+    // The half of the cell on the side it faces
+    fn collision_boxes(&self) -> Vec<Aabb<f32>>
+    {
+        let normal = self.facing.normal::<f32>();
+
+        vec![Aabb
+        {
+            min: normal.map(|n| if n > 0.0 { 0.5 } else { 0.0 }),
+            max: normal.map(|n| if n < 0.0 { 0.5 } else { 1.0 }),
+        }]
+    }
+
+    // Merges with a half-slab filling the other half of the cell, either the one
+    // clicked on or the one being placed into
+    fn on_place(self, ctx: &block::PlaceContext<'_>) -> Option<Self>
+    {
+        let clicked = ctx.pos - ctx.face.offset();
+        let merge = |pos: Vec3<i32>, facing: Direction| ctx
+            .block_at(pos)
+            .and_then(|block| block.unpack::<BlockWoodenSlab>())
+            .filter(|slab| slab.variant == self.variant && slab.facing == facing)
+            .is_some();
+
+        if merge(clicked, ctx.face.opposite())
+        {
+            ctx.substitute(clicked, BlockWoodenPlanks { variant: self.variant });
+        }
+        else if merge(ctx.pos, self.facing.opposite())
+        {
+            ctx.substitute(ctx.pos, BlockWoodenPlanks { variant: self.variant });
+        }
+        Some(self)
     }
 }
\ No newline at end of file
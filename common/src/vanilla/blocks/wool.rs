@@ -0,0 +1,21 @@
+use crate::world::blockdef;
+use crate::vanilla::props::DyeColor;
+
+blockdef!
+{
+    id: "wool",
+    name: format!("{} Wool", self.color),
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BlockWool
+    {
+        /// The color this wool was dyed
+        #[prop(White | Orange | Magenta | LightBlue | Yellow | Lime | Pink | Gray | LightGray | Cyan | Purple | Blue | Brown | Green | Red | Black)]
+        pub color: DyeColor,
+    }
+
+    fn looks(&self) -> std::borrow::Cow<'static, str>
+    {
+        format!("{}_wool", self.color.as_str()).into()
+    }
+}
\ No newline at end of file
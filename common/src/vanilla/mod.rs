@@ -1 +1,2 @@
-pub mod blocks;
\ No newline at end of file
+pub mod blocks;
+pub mod props;
\ No newline at end of file
@@ -0,0 +1,255 @@
+use crate::math::Rgb;
+
+/// Re-exported so that every property type used by block definitions can be
+/// imported from one place. Defined alongside the rest of the math, as the
+/// world itself is built around it.
+pub use crate::math::{ Direction, ParseDirectionError };
+
+/// The sixteen colors of dye, shared by every dyed block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DyeColor
+{
+    White,
+    Orange,
+    Magenta,
+    LightBlue,
+    Yellow,
+    Lime,
+    Pink,
+    Gray,
+    LightGray,
+    Cyan,
+    Purple,
+    Blue,
+    Brown,
+    Green,
+    Red,
+    Black,
+}
+
+impl DyeColor
+{
+    /// All sixteen colors, in declaration order
+    pub const ALL: [DyeColor; 16] =
+    [
+        DyeColor::White,
+        DyeColor::Orange,
+        DyeColor::Magenta,
+        DyeColor::LightBlue,
+        DyeColor::Yellow,
+        DyeColor::Lime,
+        DyeColor::Pink,
+        DyeColor::Gray,
+        DyeColor::LightGray,
+        DyeColor::Cyan,
+        DyeColor::Purple,
+        DyeColor::Blue,
+        DyeColor::Brown,
+        DyeColor::Green,
+        DyeColor::Red,
+        DyeColor::Black,
+    ];
+
+    /// Get this color's identifier, ie. `"light_blue"`, as used in identifiers
+    /// and texture keys
+    pub const fn as_str(self) -> &'static str
+    {
+        match self
+        {
+            DyeColor::White => "white",
+            DyeColor::Orange => "orange",
+            DyeColor::Magenta => "magenta",
+            DyeColor::LightBlue => "light_blue",
+            DyeColor::Yellow => "yellow",
+            DyeColor::Lime => "lime",
+            DyeColor::Pink => "pink",
+            DyeColor::Gray => "gray",
+            DyeColor::LightGray => "light_gray",
+            DyeColor::Cyan => "cyan",
+            DyeColor::Purple => "purple",
+            DyeColor::Blue => "blue",
+            DyeColor::Brown => "brown",
+            DyeColor::Green => "green",
+            DyeColor::Red => "red",
+            DyeColor::Black => "black",
+        }
+    }
+
+    /// Iterate over the identifiers of all sixteen colors, in declaration order,
+    /// ie. to list them in help text
+    pub fn variants() -> impl Iterator<Item = &'static str>
+    {
+        DyeColor::ALL.iter().map(|color| color.as_str())
+    }
+
+    /// Get the color this dye tints things with
+    pub const fn to_rgb(self) -> Rgb<u8>
+    {
+        let hex: u32 = match self
+        {
+            DyeColor::White => 0xF9FFFE,
+            DyeColor::Orange => 0xF9801D,
+            DyeColor::Magenta => 0xC74EBD,
+            DyeColor::LightBlue => 0x3AB3DA,
+            DyeColor::Yellow => 0xFED83D,
+            DyeColor::Lime => 0x80C71F,
+            DyeColor::Pink => 0xF38BAA,
+            DyeColor::Gray => 0x474F52,
+            DyeColor::LightGray => 0x9D9D97,
+            DyeColor::Cyan => 0x169C9C,
+            DyeColor::Purple => 0x8932B8,
+            DyeColor::Blue => 0x3C44AA,
+            DyeColor::Brown => 0x835432,
+            DyeColor::Green => 0x5E7C16,
+            DyeColor::Red => 0xB02E26,
+            DyeColor::Black => 0x1D1D21,
+        };
+
+        Rgb { r: (hex >> 16) as u8, g: (hex >> 8) as u8, b: hex as u8 }
+    }
+}
+
+impl std::fmt::Display for DyeColor
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{}", match self
+        {
+            DyeColor::White => "White",
+            DyeColor::Orange => "Orange",
+            DyeColor::Magenta => "Magenta",
+            DyeColor::LightBlue => "Light Blue",
+            DyeColor::Yellow => "Yellow",
+            DyeColor::Lime => "Lime",
+            DyeColor::Pink => "Pink",
+            DyeColor::Gray => "Gray",
+            DyeColor::LightGray => "Light Gray",
+            DyeColor::Cyan => "Cyan",
+            DyeColor::Purple => "Purple",
+            DyeColor::Blue => "Blue",
+            DyeColor::Brown => "Brown",
+            DyeColor::Green => "Green",
+            DyeColor::Red => "Red",
+            DyeColor::Black => "Black",
+        })
+    }
+}
+
+/// The variants of wood in the game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WoodVariant
+{
+    Oak,
+    Spruce,
+    Birch,
+    Jungle,
+    Acacia,
+    DarkOak,
+}
+
+impl WoodVariant
+{
+    /// All six variants, in declaration order
+    pub const ALL: [WoodVariant; 6] =
+    [
+        WoodVariant::Oak,
+        WoodVariant::Spruce,
+        WoodVariant::Birch,
+        WoodVariant::Jungle,
+        WoodVariant::Acacia,
+        WoodVariant::DarkOak,
+    ];
+
+    /// Get this variant's identifier, ie. `"dark_oak"`, as used in identifiers
+    /// and texture keys
+    pub const fn as_str(self) -> &'static str
+    {
+        match self
+        {
+            WoodVariant::Oak => "oak",
+            WoodVariant::Spruce => "spruce",
+            WoodVariant::Birch => "birch",
+            WoodVariant::Jungle => "jungle",
+            WoodVariant::Acacia => "acacia",
+            WoodVariant::DarkOak => "dark_oak",
+        }
+    }
+
+    /// Iterate over the identifiers of all six variants, in declaration order,
+    /// ie. to list them in help text
+    pub fn variants() -> impl Iterator<Item = &'static str>
+    {
+        WoodVariant::ALL.iter().map(|variant| variant.as_str())
+    }
+}
+
+crate::util::impl_prop_str!(DyeColor, ParseDyeColorError, "dye color");
+
+impl std::fmt::Display for WoodVariant
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{}", match self
+        {
+            WoodVariant::Oak => "Oak",
+            WoodVariant::Spruce => "Spruce",
+            WoodVariant::Birch => "Birch",
+            WoodVariant::Jungle => "Jungle",
+            WoodVariant::Acacia => "Acacia",
+            WoodVariant::DarkOak => "Dark Oak",
+        })
+    }
+}
+
+crate::util::impl_prop_str!(WoodVariant, ParseWoodVariantError, "wood variant");
+
+#[cfg(test)]
+mod test
+{
+    use super::{ DyeColor, ParseDyeColorError, ParseWoodVariantError, WoodVariant };
+
+    #[test]
+    fn parse()
+    {
+        for &color in &DyeColor::ALL
+        {
+            assert_eq!(color.as_str().parse(), Ok(color));
+            assert_eq!(color.to_string().parse(), Ok(color));
+            assert_eq!(format!("{:?}", color).parse(), Ok(color));
+        }
+        for &variant in &WoodVariant::ALL
+        {
+            assert_eq!(variant.as_str().parse(), Ok(variant));
+            assert_eq!(variant.to_string().parse(), Ok(variant));
+            assert_eq!(format!("{:?}", variant).parse(), Ok(variant));
+        }
+        assert_eq!("LIGHT-BLUE".parse(), Ok(DyeColor::LightBlue));
+        assert_eq!(DyeColor::variants().count(), 16);
+        assert_eq!(WoodVariant::variants().last(), Some("dark_oak"));
+
+        assert_eq!("teal".parse::<DyeColor>(), Err(ParseDyeColorError("teal".to_owned())));
+        assert_eq!("dark".parse::<WoodVariant>(), Err(ParseWoodVariantError("dark".to_owned())));
+        assert!("oak!".parse::<WoodVariant>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde()
+    {
+        use serde::{ Deserialize, de::{ IntoDeserializer, value::Error } };
+        use super::Direction;
+
+        fn de<'a, T: Deserialize<'a>>(s: &'a str) -> Result<T, Error>
+        {
+            T::deserialize(s.into_deserializer())
+        }
+
+        for &dir in &Direction::ALL
+        {
+            assert_eq!(de::<Direction>(dir.as_str()).unwrap(), dir);
+        }
+        assert_eq!(de::<DyeColor>("Light Gray").unwrap(), DyeColor::LightGray);
+        assert_eq!(de::<WoodVariant>("dark_oak").unwrap(), WoodVariant::DarkOak);
+        assert!(de::<Direction>("sideways").is_err());
+    }
+}
@@ -30,6 +30,9 @@ pub enum Attribute
     /// `#[prop(0..16)]`
     /// Indicate this field's valid integer range
     Range(LitRange),
+    /// `#[prop(bool)]`
+    /// Marks this field as a `bool` flag
+    Bool,
     /// `#[prop(Foo | Bar | Baz)]`
     /// Indicates this field's valid `enum` variants
     Enum(Vec<Variant>),
@@ -103,7 +106,12 @@ impl Field
     /// Parse a `block::State` implementor field from a generic field
     fn try_new<T: Into<Member>>(ident: T, field: syn::Field) -> syn::Result<Self>
     {
-        let span = field.ident.span();
+        // Positional fields have no identifier to point to
+        let span = match &field.ident
+        {
+            Some(ident) => ident.span(),
+            None => field.ty.span(),
+        };
 
         if let Some(attr) = field.attrs
             .into_iter()
@@ -144,6 +152,11 @@ impl TryFrom<syn::Attribute> for Attribute
         {
             Ok(Self::Range(range))
         }
+        // `#[prop(bool)]`
+        else if matches!(attr.parse_args::<Ident>(), Ok(ident) if ident == "bool")
+        {
+            Ok(Self::Bool)
+        }
         // `#[prop(Foo | Bar | Baz)]`
         else if let Ok(variants) = attr.parse_args_with(|parse: &ParseBuffer|
             parse.parse_terminated::<Variant, Token!(|)>(Variant::parse))
@@ -153,9 +166,10 @@ impl TryFrom<syn::Attribute> for Attribute
         // `#[prop(???)]`
         else
         {
-            emit_error!(&attr.path, format!("Expected one of:\n{}\n{}\n{}",
+            emit_error!(&attr.path, format!("Expected one of:\n{}\n{}\n{}\n{}",
                 "`#[prop(!)]`           - Field isn't sized or too large",
                 "`#[prop(0..16)]`       - Field is an integer range",
+                "`#[prop(bool)]`        - Field is a `bool` flag",
                 "`#[prop(Foo | Bar)]`   - Field accepts these `enum` variants",
             ))
         }
@@ -164,13 +178,15 @@ impl TryFrom<syn::Attribute> for Attribute
 
 impl Attribute
 {
+    /// Number of bits needed to pack this field, or `None` if it can't be
     pub fn bit_size(&self) -> Option<usize>
     {
         match self
         {
             Attribute::Never => None,
-            Attribute::Range(range) => Some(range.range().len()),
-            Attribute::Enum(variants) => Some(variants.len()),
+            Attribute::Range(range) => Some(bits_for(range.range().len())),
+            Attribute::Bool => Some(1),
+            Attribute::Enum(variants) => Some(bits_for(variants.len())),
         }
     }
 }
@@ -192,7 +208,7 @@ impl Parse for LitRange
         let range = match limits
         {
             RangeLimits::HalfOpen(_) => from.base10_parse()?..to.base10_parse()?,
-            RangeLimits::Closed(_) => from.base10_parse()?..(to.base10_parse::<i32>()? - 1),
+            RangeLimits::Closed(_) => from.base10_parse()?..(to.base10_parse::<i32>()? + 1),
         };
 
         Ok(Self { limits, from, to, range })
@@ -208,6 +224,15 @@ impl LitRange
 
     pub fn range_inclusive(&self) -> RangeInclusive<i32>
     {
-        self.range.start..=(self.range.end + 1)
+        self.range.start..=(self.range.end - 1)
     }
+}
+
+/// Number of bits needed to tell apart `n` values, which is at least one so
+/// that every field has a range of bits
+fn bits_for(n: usize) -> usize
+{
+    let bits = usize::BITS - n.saturating_sub(1).leading_zeros();
+
+    bits.max(1) as usize
 }
\ No newline at end of file
@@ -8,6 +8,8 @@ pub struct MacroInput
     pub name: syn::Expr,
     /// The concrete structure implementing `block::State`
     pub ty: syn::ItemStruct,
+    /// Overriden `Block` trait items, ie. hooks
+    pub items: Vec<syn::ImplItem>,
 }
 
 /// Short-hand for returning spanned errors
@@ -43,13 +45,22 @@ impl Parse for MacroInput
         // #[derive(Debug, Copy, Clone, PartialEq, Eq)]
         // pub struct BlockWoodenPlanks { -- snip -- }
         // ```
+        // or, with positional or no fields,
+        // ```
+        // pub struct BlockSnowLayer(#[prop(0..4)] pub u8);
+        // pub struct BlockAir;
+        // ```
         let ty = input.parse::<syn::ItemStruct>()?;
 
-        if !input.is_empty()
+        // ```
+        // fn serialize(&self, out: &mut Vec<u8>) { -- snip -- }
+        // ```
+        let mut items = Vec::new();
+        while !input.is_empty()
         {
-            panic!("Unexpected left over tokens")
+            items.push(input.parse::<syn::ImplItem>()?);
         }
 
-        Ok(Self { id, name, ty })
+        Ok(Self { id, name, ty, items })
     }
 }
\ No newline at end of file
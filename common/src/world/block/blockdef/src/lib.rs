@@ -14,6 +14,7 @@ pub fn blockdef(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 
     let id = input.id;
     let name = input.name;
+    let items = input.items;
 
     let expanded = quote::quote!
     {
@@ -28,6 +29,8 @@ pub fn blockdef(input: proc_macro::TokenStream) -> proc_macro::TokenStream
             {
                 { #name }.into()
             }
+
+            #(#items)*
         }
     };
     expanded.into()
@@ -80,6 +83,13 @@ pub fn derive_block_state(input: proc_macro::TokenStream) -> proc_macro::TokenSt
         quote::quote! { Repr::Ptr }
     };
 
+    let zeroed = input.fields
+        .iter()
+        .map(impl_zeroed);
+    let properties = input.fields
+        .iter()
+        .filter_map(impl_property);
+
     let expanded = quote::quote!
     {
         #[automatically_derived]
@@ -87,6 +97,16 @@ pub fn derive_block_state(input: proc_macro::TokenStream) -> proc_macro::TokenSt
         {
             // temporary
             const REPR: #block_path::Repr<Self> = #block_path::#repr;
+
+            fn zeroed() -> Self
+            {
+                Self { #(#zeroed),* }
+            }
+
+            fn properties(&self) -> Vec<(&'static str, String)>
+            {
+                vec![#(#properties),*]
+            }
         }
     };
 
@@ -125,6 +145,14 @@ fn impl_into_packed(field: &block_state::Field, offset: &mut usize) -> proc_macr
                 });
             }}
         },
+        block_state::Attribute::Bool =>
+        {
+            // buf.set<0, 1>(self.foo as u8)
+            quote::quote!
+            {
+                buf.set::<#offset, { #offset + #size }>(this.#name as u8);
+            }
+        },
         block_state::Attribute::Enum(variants) =>
         {
             // Branches of match block below
@@ -178,6 +206,14 @@ fn impl_from_packed(field: &block_state::Field, offset: &mut usize) -> proc_macr
                 #name: packed.get::<#offset, { #offset + #size }>() as #ty + #from as #ty
             }
         },
+        block_state::Attribute::Bool =>
+        {
+            // foo: buf.get<0, 1>() != 0
+            quote::quote!
+            {
+                #name: packed.get::<#offset, { #offset + #size }>() != 0
+            }
+        },
         block_state::Attribute::Enum(variants) =>
         {
             // Branches of match below
@@ -211,4 +247,72 @@ fn impl_from_packed(field: &block_state::Field, offset: &mut usize) -> proc_macr
     };
     *offset += size;
     out
+}
+
+/// Implementation of `block::State::zeroed` for a field
+fn impl_zeroed(field: &block_state::Field) -> proc_macro2::TokenStream
+{
+    let name = &field.ident;
+    let ty = &field.ty;
+
+    match &field.attr
+    {
+        block_state::Attribute::Never =>
+        {
+            // foo: Default::default()
+            quote::quote!
+            {
+                #name: <#ty as Default>::default()
+            }
+        },
+        block_state::Attribute::Range(range) =>
+        {
+            let from = *range.range_inclusive().start();
+
+            // foo: 4 as i32 // <-- from
+            quote::quote!
+            {
+                #name: #from as #ty
+            }
+        },
+        block_state::Attribute::Bool =>
+        {
+            // foo: false
+            quote::quote!
+            {
+                #name: false
+            }
+        },
+        block_state::Attribute::Enum(variants) =>
+        {
+            let first = &variants[0];
+
+            // foo: Foo::Bar
+            quote::quote!
+            {
+                #name: <#ty>::#first
+            }
+        },
+    }
+}
+
+/// Entry of `block::State::properties` for a field, or `None` if it isn't packed
+fn impl_property(field: &block_state::Field) -> Option<proc_macro2::TokenStream>
+{
+    if let block_state::Attribute::Never = field.attr
+    {
+        return None
+    }
+    let name = &field.ident;
+    let key = match name
+    {
+        syn::Member::Named(ident) => ident.to_string(),
+        syn::Member::Unnamed(index) => index.index.to_string(),
+    };
+
+    // ("foo", format!("{:?}", self.foo))
+    Some(quote::quote!
+    {
+        (#key, format!("{:?}", self.#name))
+    })
 }
\ No newline at end of file
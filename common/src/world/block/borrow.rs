@@ -1,7 +1,7 @@
 use std::ops::{ Deref, DerefMut, Drop };
 use std::cmp::{ PartialEq, Eq };
 use std::marker::PhantomData;
-use std::mem::MaybeUninit;
+use std::mem::{ MaybeUninit, ManuallyDrop };
 use std::any::TypeId;
 
 use crate::world::block::{ Block, self };
@@ -135,6 +135,38 @@ impl<'a, T: Block> DerefMut for RefMut<'a, T>
     }
 }
 
+impl<'a, T: Block> RefMut<'a, T>
+{
+    /// Take ownership of the borrowed block, leaving air in its place. This is
+    /// the typed counterpart to [Chunk::take](crate::world::Chunk::take).
+    ///
+    /// Only works on [block::Repr::Val] blocks, whose unpacked state this reference
+    /// already owns: instead of re-packing it into the chunk when dropped, air is
+    /// packed there and the block is returned. [block::Repr::Ptr] blocks are owned
+    /// by the chunk, which this reference can't reach to remove them from, so they're
+    /// left in place and `None` is returned; use [Chunk::take_as](crate::world::Chunk::take_as)
+    /// for those.
+    pub fn take(self) -> Option<T>
+    {
+        // Skip the re-packing
+        let this = ManuallyDrop::new(self);
+
+        // SAFETY:
+        // `this` is never used nor dropped after being read out of
+        match unsafe { std::ptr::read(&this.0) }
+        {
+            RefMutPriv::Val(block, packed) =>
+            {
+                // SAFETY:
+                // Packed air is always a "value" block
+                *packed = unsafe { block::Packed::zeroed().val };
+                Some(block)
+            },
+            RefMutPriv::Ptr(_) => None,
+        }
+    }
+}
+
 /// In case of mutation, [block::RefMut] needs to re-pack its state
 /// into the [block::Packed] it's borrowing
 impl<'a, T: Block> Drop for RefMut<'a, T>
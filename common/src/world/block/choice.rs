@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use crate::world::{ Block, Chunk, block };
+use crate::math::Vec3;
+
+/// A type-erased [Block] that can be placed any number of times, ie. to
+/// configure which blocks a generator fills terrain with without it being
+/// generic over every block type.
+#[derive(Clone)]
+pub struct Choice
+{
+    /// The block being placed
+    block: Arc<dyn block::Object>,
+    /// Places a copy of `block`, which is known to be of the concrete type
+    /// this `Choice` was created with
+    place: fn(&dyn block::Object, &mut Chunk, Vec3<usize>),
+}
+
+impl Choice
+{
+    /// Create a new choice which places copies of `block`
+    pub fn new<T: Block + Clone>(block: T) -> Self
+    {
+        fn place<T: Block + Clone>(block: &dyn block::Object, chunk: &mut Chunk, pos: Vec3<usize>)
+        {
+            // `Choice::block` is always a `T`
+            if let Some(block) = block.cast::<T>()
+            {
+                chunk.set(pos, T::clone(&block));
+            }
+        }
+
+        Self
+        {
+            block: Arc::new(block),
+            place: place::<T>,
+        }
+    }
+
+    /// Place a copy of this block at the given position in chunk-space, or do
+    /// nothing if the position is out of the chunk's bounds.
+    #[inline]
+    pub fn place(&self, chunk: &mut Chunk, pos: Vec3<usize>)
+    {
+        (self.place)(&*self.block, chunk, pos)
+    }
+
+    /// Get the block placed by this choice
+    #[inline]
+    pub fn block(&self) -> &dyn block::Object
+    {
+        &*self.block
+    }
+}
+
+impl std::fmt::Debug for Choice
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.debug_tuple("Choice")
+            .field(&self.block)
+            .finish()
+    }
+}
@@ -0,0 +1,139 @@
+use std::cell::RefCell;
+
+use crate::world::{ World, Block, block };
+use crate::math::{ Direction, Vec3 };
+
+/// A block to set at some world coordinates
+type Edit = (Vec3<i32>, block::Choice);
+/// An action to run on the world later, see [TickContext::defer]
+type Deferred<'a> = Box<dyn FnOnce(&World) + 'a>;
+
+/// What a block can see of the [World] around it during a [Block::random_tick](crate::world::Block::random_tick),
+/// [Block::on_neighbor_changed](crate::world::Block::on_neighbor_changed) or [Block::on_scheduled_tick](crate::world::Block::on_scheduled_tick)
+pub struct TickContext<'a>
+{
+    /// World coordinates of the block being ticked
+    pub pos: Vec3<i32>,
+    /// The world the block is in
+    world: &'a World,
+    /// Actions to run once the hook returns, see [TickContext::defer]
+    deferred: RefCell<Vec<Deferred<'a>>>,
+}
+
+impl<'a> TickContext<'a>
+{
+    /// Create the context for ticking the block at the world coordinates `pos`
+    pub(in crate::world) fn new(world: &'a World, pos: Vec3<i32>) -> Self
+    {
+        Self { pos, world, deferred: Default::default() }
+    }
+
+    /// Run the actions deferred by the hook, see [TickContext::defer]. The chunk of
+    /// the block ticked must be unlocked by now.
+    pub(in crate::world) fn run_deferred(self)
+    {
+        for action in self.deferred.into_inner()
+        {
+            action(self.world);
+        }
+    }
+
+    /// Get a summary of the block adjacent to the one being ticked in the given
+    /// direction, or `None` if its chunk isn't loaded or is locked. This is a
+    /// non-blocking operation, see [World::block_at].
+    pub fn neighbor(&self, dir: Direction) -> Option<block::Info>
+    {
+        self.world.block_at(self.pos + dir.offset())
+    }
+
+    /// Get a summary of the block at the world coordinates `pos`, see [World::block_at]
+    pub fn block_at(&self, pos: Vec3<i32>) -> Option<block::Info>
+    {
+        self.world.block_at(pos)
+    }
+
+    /// Get a copy of the block at the world coordinates `pos` if it's of type `T`.
+    /// Unlike [TickContext::block_at], this also sees the state of [block::Repr::Ptr]
+    /// blocks. This is a non-blocking operation, see [World::try_get].
+    pub fn block_as<T: Block + Clone>(&self, pos: Vec3<i32>) -> Option<T>
+    {
+        self.world.try_get(pos).ok()?.cast::<T>().map(|block| (*block).clone())
+    }
+
+    /// Run `action` on the world once the hook returns and the chunk of the block
+    /// ticked is unlocked, ie. for edits reaching further than the hook's return
+    /// value can. Actions run in the order they were deferred.
+    pub fn defer(&self, action: impl FnOnce(&World) + 'a)
+    {
+        self.deferred.borrow_mut().push(Box::new(action));
+    }
+
+    /// Schedule a tick for the block being ticked `delay` ticks from now, see
+    /// [World::schedule_tick]
+    pub fn schedule_tick(&self, delay: u64)
+    {
+        self.world.schedule_tick(self.pos, delay);
+    }
+}
+
+
+/// Where a block is being placed, as given to [Block::on_place](crate::world::Block::on_place)
+pub struct PlaceContext<'a>
+{
+    /// World coordinates the block is being placed at
+    pub pos: Vec3<i32>,
+    /// Face of the block clicked to place this one, which is also the direction
+    /// from that block to `pos`
+    pub face: Direction,
+    /// The world the block is placed in
+    world: &'a World,
+    /// Other blocks to place along with this one, see [PlaceContext::also_set]
+    edits: RefCell<Vec<Edit>>,
+    /// Block to set instead of this one, see [PlaceContext::substitute]
+    substitute: RefCell<Option<Edit>>,
+}
+
+impl<'a> PlaceContext<'a>
+{
+    /// Create the context for placing a block at the world coordinates `pos` by
+    /// clicking on `face`
+    pub(in crate::world) fn new(world: &'a World, pos: Vec3<i32>, face: Direction) -> Self
+    {
+        Self { pos, face, world, edits: Default::default(), substitute: Default::default() }
+    }
+
+    /// Take the block to set instead of this one, if any, and the blocks to place
+    /// along with it. See [PlaceContext::substitute] and [PlaceContext::also_set]
+    pub(in crate::world) fn into_edits(self) -> (Option<Edit>, Vec<Edit>)
+    {
+        (self.substitute.into_inner(), self.edits.into_inner())
+    }
+
+    /// See [TickContext::neighbor]
+    pub fn neighbor(&self, dir: Direction) -> Option<block::Info>
+    {
+        self.world.block_at(self.pos + dir.offset())
+    }
+
+    /// See [TickContext::block_at]
+    pub fn block_at(&self, pos: Vec3<i32>) -> Option<block::Info>
+    {
+        self.world.block_at(pos)
+    }
+
+    /// Also set the block at the world coordinates `pos` if the block being placed
+    /// is, ie. for blocks spanning multiple cells. All of them are set at once, or
+    /// none are, see [World::set_atomic].
+    pub fn also_set<T: Block + Clone>(&self, pos: Vec3<i32>, block: T)
+    {
+        self.edits.borrow_mut().push((pos, block::Choice::new(block)));
+    }
+
+    /// Cancel placing this block, and set `block` at the world coordinates `pos`
+    /// instead, ie. to merge into the block it's placed against. This takes over
+    /// whatever the hook returns, and replaces any earlier substitute.
+    pub fn substitute<T: Block + Clone>(&self, pos: Vec3<i32>, block: T)
+    {
+        *self.substitute.borrow_mut() = Some((pos, block::Choice::new(block)));
+    }
+}
\ No newline at end of file
@@ -1,10 +1,14 @@
 use std::marker::PhantomData;
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::any::TypeId;
+use std::convert::TryFrom;
 
 use ptr_meta::{ DynMetadata, pointee };
 
 use crate::world::block::{ Block, self };
+use crate::util::{ Bits, Fnv1a };
+use crate::math::{ Aabb, Direction, Vec3 };
 
 /// The [Block] trait, made object-safe
 #[pointee]
@@ -15,6 +19,68 @@ pub trait Object: block::ObjectPriv
 
     /// See [Block::name]
     fn name(&self) -> Cow<'static, str>;
+
+    /// See [Block::looks]
+    fn looks(&self) -> Cow<'static, str>;
+
+    /// See [block::State::properties]
+    fn properties(&self) -> Vec<(&'static str, String)>;
+
+    /// See [Block::solid]
+    fn solid(&self) -> bool;
+
+    /// See [Block::occludes]
+    fn occludes(&self, face: Direction) -> bool;
+
+    /// See [Block::light_attenuation]
+    fn light_attenuation(&self) -> u8;
+
+    /// See [Block::light_emission]
+    fn light_emission(&self) -> u8;
+
+    /// See [Block::hardness]
+    fn hardness(&self) -> f32;
+
+    /// See [Block::random_tick]
+    fn random_tick(&self, ctx: &block::TickContext<'_>) -> Option<block::Choice>;
+
+    /// See [Block::on_neighbor_changed]
+    fn on_neighbor_changed(&self, ctx: &block::TickContext<'_>, dir: Direction) -> Option<block::Choice>;
+
+    /// See [Block::on_scheduled_tick]
+    fn on_scheduled_tick(&self, ctx: &block::TickContext<'_>) -> Option<block::Choice>;
+
+    /// See [Block::on_break]
+    fn on_break(&self, ctx: &block::TickContext<'_>) -> Vec<(Vec3<i32>, block::Choice)>;
+
+    /// See [Block::collision_boxes]
+    fn collision_boxes(&self) -> Vec<Aabb<f32>>;
+
+    /// Compare this block to another type-erased block, which are equal only if
+    /// they're of the same type and have the same packed state, or for [block::Repr::Ptr]
+    /// blocks, the same [Block::serialize]d bytes.
+    fn dyn_eq(&self, other: &dyn block::Object) -> bool
+    {
+        if self.inner_type_id() != other.inner_type_id()
+        {
+            return false;
+        }
+        match (self.packed_state(), other.packed_state())
+        {
+            (Some(a), Some(b)) => a == b,
+            _ =>
+            {
+                let (mut a, mut b) = (Vec::new(), Vec::new());
+
+                self.serialize(&mut a);
+                other.serialize(&mut b);
+                a == b
+            },
+        }
+    }
+
+    /// See [Block::serialize]
+    fn serialize(&self, out: &mut Vec<u8>);
 }
 
 mod private
@@ -41,16 +107,61 @@ mod private
 
         /// Same as [unpack_into], but with a [block::RefMutPriv]
         unsafe fn unpack_into_mut<'a>(&'a mut self, into: *mut ());
+
+        /// Get this block's state packed in 6 bits if it's a [block::Repr::Val] block,
+        /// or `None` if it's a [block::Repr::Ptr] block
+        fn packed_state(&self) -> Option<Bits<6>>;
     }
 }
 // `borrow` module needs access to this
 pub(super) use private::ObjectPriv;
 
+impl PartialEq for dyn block::Object
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool
+    {
+        self.dyn_eq(other)
+    }
+}
+
+impl std::fmt::Display for dyn block::Object
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.write_str(&self.name())
+    }
+}
+
+impl std::fmt::Debug for dyn block::Object
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.debug_struct("Object")
+            .field("id", &self.id())
+            .field("name", &self.name())
+            .finish()
+    }
+}
+
 /// Blanket implementation for every `Block` type
 impl<T: Block> block::Object for T
 {
     fn id(&self) -> &'static str { <T as Block>::ID }
     fn name(&self) -> Cow<'static, str> { <T as Block>::name(self) }
+    fn looks(&self) -> Cow<'static, str> { <T as Block>::looks(self) }
+    fn properties(&self) -> Vec<(&'static str, String)> { <T as block::State>::properties(self) }
+    fn solid(&self) -> bool { <T as Block>::solid(self) }
+    fn occludes(&self, face: Direction) -> bool { <T as Block>::occludes(self, face) }
+    fn light_attenuation(&self) -> u8 { <T as Block>::light_attenuation(self) }
+    fn light_emission(&self) -> u8 { <T as Block>::light_emission(self) }
+    fn hardness(&self) -> f32 { <T as Block>::hardness(self) }
+    fn random_tick(&self, ctx: &block::TickContext<'_>) -> Option<block::Choice> { <T as Block>::random_tick(self, ctx) }
+    fn on_neighbor_changed(&self, ctx: &block::TickContext<'_>, dir: Direction) -> Option<block::Choice> { <T as Block>::on_neighbor_changed(self, ctx, dir) }
+    fn on_scheduled_tick(&self, ctx: &block::TickContext<'_>) -> Option<block::Choice> { <T as Block>::on_scheduled_tick(self, ctx) }
+    fn on_break(&self, ctx: &block::TickContext<'_>) -> Vec<(Vec3<i32>, block::Choice)> { <T as Block>::on_break(self, ctx) }
+    fn collision_boxes(&self) -> Vec<Aabb<f32>> { <T as Block>::collision_boxes(self) }
+    fn serialize(&self, out: &mut Vec<u8>) { <T as Block>::serialize(self, out) }
 }
 
 impl<T: Block> private::ObjectPriv for T
@@ -59,15 +170,20 @@ impl<T: Block> private::ObjectPriv for T
     
     unsafe fn unpack_into<'a>(&'a self, into: *mut ())
     {
-        let out = &mut *(into as *mut block::Ref<'a, T>);
-
-        *out = block::Ref::Ptr(self);
+        // `into` may be uninit, so it mustn't be dropped by assignment
+        (into as *mut block::Ref<'a, T>).write(block::Ref::Ptr(self));
     }
     unsafe fn unpack_into_mut<'a>(&'a mut self, into: *mut ())
     {
-        let out = &mut *(into as *mut block::RefMutPriv<'a, T>);
-
-        *out = block::RefMutPriv::Ptr(self);
+        (into as *mut block::RefMutPriv<'a, T>).write(block::RefMutPriv::Ptr(self));
+    }
+    fn packed_state(&self) -> Option<Bits<6>>
+    {
+        match T::REPR
+        {
+            block::Repr::Val { into_packed, .. } => Some(into_packed(self)),
+            block::Repr::Ptr => None,
+        }
     }
 }
 
@@ -79,7 +195,25 @@ impl<T: Block> private::ObjectPriv for T
 /// main instance of a [block::Registry], and after game startup it will remain
 /// immutable.
 #[derive(Debug, Clone)]
-pub struct Registry(crate::util::Registry<DynMetadata<dyn block::Object>>);
+pub struct Registry(crate::util::Registry<Meta>);
+
+/// Meta data about a type of [Block], stored in the [block::Registry]
+#[derive(Debug, Clone, Copy)]
+struct Meta
+{
+    /// See [vtable_of]
+    vtable: DynMetadata<dyn block::Object>,
+    /// See [Block::ID]
+    id: &'static str,
+    /// Whether the block is packed as a "value" or "pointer"
+    repr: block::packed::Repr,
+    /// [Block::deserialize], boxed
+    deserialize: fn(&[u8]) -> Option<Box<dyn block::Object>>,
+    /// Unpacks a "value" block's state, boxed. Returns `None` for "pointer" blocks
+    unpack: fn(Bits<6>) -> Option<Box<dyn block::Object>>,
+    /// [Block::default_state], boxed
+    default_state: fn() -> Box<dyn block::Object>,
+}
 
 /// Get the vtable for a type of [Block].
 /// Note that the type-erased data fed into functions of this vtable aren't necesarilly
@@ -123,6 +257,19 @@ fn vtable_of<B: Block>() -> DynMetadata<dyn block::Object>
             {
                 fn id(&self) -> &'static str { <T as Block>::ID }
                 fn name(&self) -> Cow<'static, str> { <T as Block>::name(&self.unpack()) }
+                fn looks(&self) -> Cow<'static, str> { <T as Block>::looks(&self.unpack()) }
+                fn properties(&self) -> Vec<(&'static str, String)> { <T as block::State>::properties(&self.unpack()) }
+                fn solid(&self) -> bool { <T as Block>::solid(&self.unpack()) }
+                fn occludes(&self, face: Direction) -> bool { <T as Block>::occludes(&self.unpack(), face) }
+                fn light_attenuation(&self) -> u8 { <T as Block>::light_attenuation(&self.unpack()) }
+                fn light_emission(&self) -> u8 { <T as Block>::light_emission(&self.unpack()) }
+                fn hardness(&self) -> f32 { <T as Block>::hardness(&self.unpack()) }
+                fn random_tick(&self, ctx: &block::TickContext<'_>) -> Option<block::Choice> { <T as Block>::random_tick(&self.unpack(), ctx) }
+                fn on_neighbor_changed(&self, ctx: &block::TickContext<'_>, dir: Direction) -> Option<block::Choice> { <T as Block>::on_neighbor_changed(&self.unpack(), ctx, dir) }
+                fn on_scheduled_tick(&self, ctx: &block::TickContext<'_>) -> Option<block::Choice> { <T as Block>::on_scheduled_tick(&self.unpack(), ctx) }
+                fn on_break(&self, ctx: &block::TickContext<'_>) -> Vec<(Vec3<i32>, block::Choice)> { <T as Block>::on_break(&self.unpack(), ctx) }
+                fn collision_boxes(&self) -> Vec<Aabb<f32>> { <T as Block>::collision_boxes(&self.unpack()) }
+                fn serialize(&self, out: &mut Vec<u8>) { <T as Block>::serialize(&self.unpack(), out) }
             }
             impl<T: Block> private::ObjectPriv for Typed<T>
             {
@@ -133,16 +280,13 @@ fn vtable_of<B: Block>() -> DynMetadata<dyn block::Object>
                 // Important distinction that `into` isn't a Ref<Typed<T>>
                 unsafe fn unpack_into<'a>(&'a self, into: *mut ())
                 {
-                    let out = &mut *(into as *mut block::Ref<'a, T>);
-
-                    *out = block::Ref::Val(self.unpack(), PhantomData);
+                    (into as *mut block::Ref<'a, T>).write(block::Ref::Val(self.unpack(), PhantomData));
                 }
                 unsafe fn unpack_into_mut<'a>(&'a mut self, into: *mut ())
                 {
-                    let out = &mut *(into as *mut block::RefMutPriv<'a, T>);
-
-                    *out = block::RefMutPriv::Val(self.unpack(), &mut self.0)
-                }   
+                    (into as *mut block::RefMutPriv<'a, T>).write(block::RefMutPriv::Val(self.unpack(), &mut self.0))
+                }
+                fn packed_state(&self) -> Option<Bits<6>> { Some(self.0.state()) }
             }
 
             // vtable is over a packed block that "owns" a `B`
@@ -161,7 +305,174 @@ impl Registry
     /// Adds a [Block] to this registry, if not already present.
     pub fn register<T: Block>(&mut self)
     {
-        self.0.register::<T>(vtable_of::<T>());
+        fn deserialize<T: Block>(bytes: &[u8]) -> Option<Box<dyn block::Object>>
+        {
+            T::deserialize(bytes).map(|block| Box::new(block) as _)
+        }
+        fn unpack<T: Block>(state: Bits<6>) -> Option<Box<dyn block::Object>>
+        {
+            match T::REPR
+            {
+                block::Repr::Val { from_packed, .. } => Some(Box::new(from_packed(state))),
+                block::Repr::Ptr => None,
+            }
+        }
+        fn default_state<T: Block>() -> Box<dyn block::Object>
+        {
+            Box::new(T::default_state())
+        }
+
+        self.0.register::<T>(Meta
+        {
+            vtable: vtable_of::<T>(),
+            id: T::ID,
+            repr: match T::REPR
+            {
+                block::Repr::Val { .. } => block::packed::Repr::Val,
+                block::Repr::Ptr => block::packed::Repr::Ptr,
+            },
+            deserialize: deserialize::<T>,
+            unpack: unpack::<T>,
+            default_state: default_state::<T>,
+        });
+    }
+
+    /// Build a registry with the [Block]s of `blocks`, but keeping the numeric
+    /// [block::Id]s of this one for blocks present in both, matched by their string
+    /// identifier, ie. to swap a block's implementation during development without
+    /// invalidating loaded chunks. Blocks only in `blocks` get new IDs.
+    ///
+    /// Blocks only in this registry leave their ID taken up by air, so that no other
+    /// block reuses it. Returns the remap of those removed IDs to air's, which chunks
+    /// holding them should be rewritten with.
+    pub fn rebuild_from(&self, blocks: &Registry) -> (Registry, HashMap<block::Id, block::Id>)
+    {
+        // Air always has ID `0`, see `Registry::default`
+        let air = *blocks.0.get(0).unwrap();
+
+        let mut registry = crate::util::Registry::default();
+        let mut removed = HashMap::new();
+
+        for (id, (_, meta)) in self.0.iter().enumerate()
+        {
+            let (type_id, meta) = match blocks.id_of(meta.id)
+            {
+                Some(new) => *blocks.0.get(new.into()).unwrap(),
+                None =>
+                {
+                    removed.insert(block::Id::try_from(id).unwrap(), block::Id::try_from(0).unwrap());
+                    air
+                },
+            };
+            // Placeholders, from removed blocks or earlier rebuilds
+            if registry.id_of(type_id).is_some()
+            {
+                registry.reserve(type_id, meta);
+            }
+            else
+            {
+                registry.register_type_id(type_id, meta);
+            }
+        }
+        for &(type_id, meta) in blocks.0.iter()
+        {
+            if self.id_of(meta.id).is_none()
+            {
+                registry.register_type_id(type_id, meta);
+            }
+        }
+        (Self(registry), removed)
+    }
+
+    /// Get the numeric [block::Id] of the [Block] with the given string identifier,
+    /// if present in the registry.
+    pub fn id_of(&self, id: &str) -> Option<block::Id>
+    {
+        self.0
+            .iter()
+            .position(|(_, meta)| meta.id == id)
+            .and_then(|id| block::Id::try_from(id).ok())
+    }
+
+    /// Get the string identifier of the [Block] with the given numeric [block::Id],
+    /// if present in the registry.
+    pub fn str_id(&self, id: block::Id) -> Option<&'static str>
+    {
+        self.0
+            .get(id.into())
+            .map(|(_, meta)| meta.id)
+    }
+
+    /// Iterate over the string identifiers of all the [Block]s in this registry,
+    /// in order of their numeric [block::Id]s.
+    pub fn str_ids(&self) -> impl Iterator<Item = &'static str> + '_
+    {
+        self.0
+            .iter()
+            .map(|(_, meta)| meta.id)
+    }
+
+    /// Get a hash of the [Block] types in this registry, ie. their string identifiers
+    /// and whether they're packed as a "value" or "pointer". Registries with the same
+    /// blocks, regardless of the order they were registered in, have the same
+    /// fingerprint. This is stable across runs and platforms, so it can be saved.
+    pub fn fingerprint(&self) -> u64
+    {
+        let mut blocks = self.0
+            .iter()
+            .map(|(_, meta)| (meta.id, meta.repr as u8))
+            .collect::<Vec<_>>();
+        blocks.sort_unstable();
+        // Removed blocks are left as air, see `Registry::rebuild_from`
+        blocks.dedup();
+
+        let mut hash = Fnv1a::default();
+        for (id, repr) in blocks
+        {
+            // Length prefix so that ids can't run into each other
+            hash.write(&(id.len() as u32).to_le_bytes());
+            hash.write(id.as_bytes());
+            hash.write(&[repr]);
+        }
+        hash.finish()
+    }
+
+    /// Get whether the [Block] with the given numeric [block::Id] is packed as a
+    /// "value" or "pointer", if present in the registry.
+    pub(in crate::world) fn repr(&self, id: block::Id) -> Option<block::packed::Repr>
+    {
+        self.0
+            .get(id.into())
+            .map(|(_, meta)| meta.repr)
+    }
+
+    /// Recreate a [Block] from the output of its [Block::serialize], given its
+    /// numeric [block::Id]. Returns `None` if it isn't registered or if the data
+    /// is invalid.
+    pub(in crate::world) fn deserialize(&self, id: block::Id, bytes: &[u8]) -> Option<Box<dyn block::Object>>
+    {
+        (self.0.get(id.into())?.1.deserialize)(bytes)
+    }
+
+    /// Create the [Block::default_state] of the [Block] with the given numeric
+    /// [block::Id], ie. to place it from the hotbar. Returns `None` if it isn't
+    /// registered.
+    pub fn default_boxed(&self, id: block::Id) -> Option<Box<dyn block::Object>>
+    {
+        Some((self.0.get(id.into())?.1.default_state)())
+    }
+
+    /// Iterate over every [Block] in this registry along with its numeric [block::Id],
+    /// as created by [Registry::default_boxed], ie. to list them in a creative menu.
+    /// IDs left taken up by blocks removed by [Registry::rebuild_from] are skipped.
+    pub fn default_instances(&self) -> impl Iterator<Item = (block::Id, Box<dyn block::Object>)> + '_
+    {
+        self.0
+            .iter()
+            .enumerate()
+            // Placeholders share their type with the block registered first
+            .filter(move |(id, (type_id, _))| self.0.id_of(*type_id) == Some(*id))
+            .filter_map(|(id, (_, meta))| Some((block::Id::try_from(id).ok()?, (meta.default_state)())))
     }
 
     /// Get the numeric [block::Id] of a concrete [Block] type, if present
@@ -170,7 +481,16 @@ impl Registry
     {
         self.0
             .id::<T>()
-            .map(|id| block::Id(id as _))
+            .and_then(|id| block::Id::try_from(id).ok())
+    }
+
+    /// Get the numeric [block::Id] of a type-erased [Block]'s concrete type, if
+    /// present in the registry.
+    pub fn id_of_object(&self, block: &dyn block::Object) -> Option<block::Id>
+    {
+        self.0
+            .id_of(block.inner_type_id())
+            .and_then(|id| block::Id::try_from(id).ok())
     }
 
     /// Create an immutable, dynamic reference to a [block::Object] given its
@@ -179,7 +499,7 @@ impl Registry
     pub(in crate::world) unsafe fn create_ref<'a>(&self, packed: &'a block::packed::Val) -> &'a dyn block::Object
     {
         // Get vtable from registry
-        let vtable = self.0.get_unchecked(packed.id().0 as _).1;
+        let vtable = self.0.get_unchecked(packed.id().into()).1.vtable;
         // Erase type of data
         let data = packed as *const block::packed::Val as *const ();
         
@@ -193,23 +513,280 @@ impl Registry
     pub(in crate::world) unsafe fn create_ref_mut<'a>(&self, packed: &'a mut block::packed::Val) -> &'a mut dyn block::Object
     {
         // Get vtable from registry
-        let vtable = self.0.get_unchecked(packed.id().0 as _).1;
+        let vtable = self.0.get_unchecked(packed.id().into()).1.vtable;
         // Erase type of data
         let data = packed as *mut block::packed::Val as *mut ();
         
         // Recreate dyn reference
         &mut *ptr_meta::from_raw_parts_mut(data, vtable)
     }
+
+    /// Create an owned [block::Object] given its packed representation, as the
+    /// [Block] type itself rather than a wrapper over the packed state. The block
+    /// MUST be registered, otherwise UB may occur
+    pub(in crate::world) unsafe fn create_boxed(&self, packed: block::packed::Val) -> Box<dyn block::Object>
+    {
+        let unpack = self.0.get_unchecked(packed.id().into()).1.unpack;
+
+        // Only "value" blocks are ever packed as such
+        unpack(packed.state()).unwrap()
+    }
 }
 
 impl Default for Registry
 {
-    /// Creates a new registry with just `vanilla:air` registered.
+    /// Creates a new registry with just [BlockAir](crate::vanilla::blocks::BlockAir)
+    /// registered, which always gets ID `0` as chunks are filled with it.
     fn default() -> Self
     {
-        let /*mut*/ registry = Self(crate::util::Registry::default());
+        let mut registry = Self(crate::util::Registry::default());
 
-        //registry.register::<crate::vanilla::blocks::BlockAir>();
+        registry.register::<crate::vanilla::blocks::BlockAir>();
         registry
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test
+{
+    use crate::world::block::{ self, blockdef };
+    use crate::vanilla::blocks::*;
+    use crate::vanilla::props::WoodVariant;
+
+    blockdef!
+    {
+        id: "stone",
+        name: "Smooth Stone",
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct BlockSmoothStone;
+    }
+
+    #[test]
+    fn fmt_object()
+    {
+        let planks: Box<dyn block::Object> = Box::new(BlockWoodenPlanks { variant: WoodVariant::Birch });
+        let air: &dyn block::Object = &BlockAir;
+
+        assert_eq!(format!("{}", planks), "Birch Planks");
+        assert_eq!(format!("{:?}", planks), r#"Object { id: "wooden_planks", name: "Birch Planks" }"#);
+        assert_eq!(format!("{}", air), "Air");
+        assert_eq!(format!("{:?}", air), r#"Object { id: "air", name: "Air" }"#);
+    }
+
+    #[test]
+    fn eq_object()
+    {
+        let oak: Box<dyn block::Object> = Box::new(BlockWoodenPlanks { variant: WoodVariant::Oak });
+        let birch: Box<dyn block::Object> = Box::new(BlockWoodenPlanks { variant: WoodVariant::Birch });
+        let air: &dyn block::Object = &BlockAir;
+
+        assert!(*oak == *oak);
+        assert!(*oak != *birch);
+        assert!(*oak != *air);
+    }
+
+    #[test]
+    fn occludes()
+    {
+        use crate::math::Direction::{ self, * };
+
+        for &facing in &Direction::ALL
+        {
+            let slab: &dyn block::Object = &BlockWoodenSlab { facing, variant: WoodVariant::Oak };
+
+            for &face in &Direction::ALL
+            {
+                assert_eq!(slab.occludes(face), face == facing, "{:?} slab, {:?} face", facing, face);
+            }
+        }
+
+        let planks: &dyn block::Object = &BlockWoodenPlanks { variant: WoodVariant::Oak };
+        let chest: &dyn block::Object = &BlockChest::new(North);
+        let air: &dyn block::Object = &BlockAir;
+
+        assert!(Direction::ALL.iter().all(|&face| planks.occludes(face)));
+        assert!(Direction::ALL.iter().all(|&face| !chest.occludes(face)));
+        assert!(Direction::ALL.iter().all(|&face| !air.occludes(face)));
+    }
+
+    #[test]
+    fn collision_boxes()
+    {
+        use crate::math::{ Aabb, Direction, Vec3 };
+
+        let planks: &dyn block::Object = &BlockWoodenPlanks { variant: WoodVariant::Oak };
+        let air: &dyn block::Object = &BlockAir;
+        let slab = |facing| Box::new(BlockWoodenSlab { facing, variant: WoodVariant::Oak }) as Box<dyn block::Object>;
+        let aabb = |min: (f32, f32, f32), max: (f32, f32, f32)| Aabb { min: Vec3::from(min), max: Vec3::from(max) };
+
+        assert_eq!(planks.collision_boxes(), vec![aabb((0.0, 0.0, 0.0), (1.0, 1.0, 1.0))]);
+        assert!(air.collision_boxes().is_empty());
+
+        assert_eq!(slab(Direction::Down).collision_boxes(), vec![aabb((0.0, 0.0, 0.0), (1.0, 0.5, 1.0))]);
+        assert_eq!(slab(Direction::Up).collision_boxes(), vec![aabb((0.0, 0.5, 0.0), (1.0, 1.0, 1.0))]);
+        assert_eq!(slab(Direction::North).collision_boxes(), vec![aabb((0.0, 0.0, 0.0), (1.0, 1.0, 0.5))]);
+        assert_eq!(slab(Direction::East).collision_boxes(), vec![aabb((0.5, 0.0, 0.0), (1.0, 1.0, 1.0))]);
+    }
+
+    #[test]
+    fn default_state()
+    {
+        use std::convert::TryFrom;
+
+        use crate::world::Block;
+        use crate::math::Direction;
+
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenSlab>();
+        registry.register::<BlockChest>();
+
+        // zeroed props
+        assert_eq!(BlockWoodenSlab::default_state(), BlockWoodenSlab { facing: Direction::North, variant: WoodVariant::Oak });
+        assert_eq!(BlockWheat::default_state(), BlockWheat { age: 0 });
+
+        let slab = registry.default_boxed(registry.id::<BlockWoodenSlab>().unwrap()).unwrap();
+        assert_eq!(slab.cast::<BlockWoodenSlab>().as_deref(), Some(&BlockWoodenSlab::default_state()));
+
+        // overridden
+        let chest = registry.default_boxed(registry.id::<BlockChest>().unwrap()).unwrap();
+        assert_eq!(chest.cast::<BlockChest>().as_deref(), Some(&BlockChest::new(Direction::North)));
+
+        assert!(registry.default_boxed(block::Id::try_from(100).unwrap()).is_none());
+    }
+
+    #[test]
+    fn default_instances()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenSlab>();
+        registry.register::<BlockChest>();
+        registry.register::<BlockWheat>();
+
+        let instances = registry.default_instances().collect::<Vec<_>>();
+
+        assert_eq!(instances.len(), 4);
+        assert_eq!(instances.len(), registry.str_ids().count());
+        for (id, block) in &instances
+        {
+            assert_eq!(registry.id_of_object(&**block), Some(*id));
+            assert!(block.dyn_eq(&*registry.default_boxed(*id).unwrap()));
+        }
+        assert!(instances[0].1.is::<BlockAir>());
+
+        // without the slab, which leaves its ID to air
+        let mut blocks = block::Registry::default();
+
+        blocks.register::<BlockChest>();
+        blocks.register::<BlockWheat>();
+
+        let (rebuilt, _) = registry.rebuild_from(&blocks);
+        let ids = rebuilt
+            .default_instances()
+            .map(|(id, block)| (usize::from(id), block.id()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, vec![(0, "air"), (2, "chest"), (3, "wheat")]);
+    }
+
+    #[test]
+    fn describe()
+    {
+        use crate::world::block::{ State, Repr };
+        use crate::math::Direction;
+
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenSlab>();
+
+        let slab = BlockWoodenSlab { facing: Direction::North, variant: WoodVariant::DarkOak };
+        let state = match BlockWoodenSlab::REPR
+        {
+            Repr::Val { into_packed, .. } => into_packed(&slab),
+            Repr::Ptr => panic!("slabs should be `Repr::Val`"),
+        };
+        let packed = block::Packed::from_val(registry.id::<BlockWoodenSlab>().unwrap(), state);
+
+        assert_eq!(slab.properties(), vec![("facing", "North".to_owned()), ("variant", "DarkOak".to_owned())]);
+        assert_eq!(packed.describe(&registry), "wooden_slab { facing: North, variant: DarkOak }");
+        assert_eq!(block::Packed::zeroed().describe(&registry), "air");
+        assert_eq!(block::Packed::from_ptr(12).describe(&registry), "Ptr { slot: 12 }");
+    }
+
+    #[test]
+    fn fingerprint()
+    {
+        let mut a = block::Registry::default();
+
+        a.register::<BlockWoodenPlanks>();
+        a.register::<BlockChest>();
+
+        let mut b = block::Registry::default();
+
+        b.register::<BlockChest>();
+        b.register::<BlockWoodenPlanks>();
+
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        assert_eq!(a.fingerprint(), a.clone().fingerprint());
+
+        let mut c = block::Registry::default();
+
+        c.register::<BlockWoodenPlanks>();
+
+        assert_ne!(a.fingerprint(), c.fingerprint());
+        assert_ne!(c.fingerprint(), block::Registry::default().fingerprint());
+
+        c.register::<BlockWoodenSlab>();
+
+        assert_ne!(a.fingerprint(), c.fingerprint());
+    }
+
+    #[test]
+    fn default_has_air()
+    {
+        let registry = block::Registry::default();
+
+        assert_eq!(registry.id::<BlockAir>().map(usize::from), Some(0));
+        assert_eq!(registry.str_ids().collect::<Vec<_>>(), vec!["air"]);
+    }
+
+    #[test]
+    fn rebuild_from()
+    {
+        let mut old = block::Registry::default();
+
+        old.register::<BlockStone>();
+        old.register::<BlockDirt>();
+        old.register::<BlockGrass>();
+
+        let mut new = block::Registry::default();
+
+        new.register::<BlockGrass>();
+        new.register::<BlockObsidian>();
+        new.register::<BlockSmoothStone>();
+
+        let (rebuilt, removed) = old.rebuild_from(&new);
+        let dirt = old.id::<BlockDirt>().unwrap();
+
+        // Swapped implementation keeps its ID
+        assert_eq!(rebuilt.id::<BlockSmoothStone>(), old.id::<BlockStone>());
+        assert_eq!(rebuilt.id::<BlockStone>(), None);
+        assert_eq!(rebuilt.id::<BlockGrass>(), old.id::<BlockGrass>());
+        // Removed blocks are left as air
+        assert_eq!(removed.into_iter().collect::<Vec<_>>(), vec![(dirt, rebuilt.id::<BlockAir>().unwrap())]);
+        assert_eq!(rebuilt.str_id(dirt), Some("air"));
+        assert_eq!(rebuilt.id::<BlockDirt>(), None);
+        // New blocks come after
+        assert_eq!(rebuilt.id::<BlockObsidian>().map(usize::from), Some(4));
+        assert_eq!(rebuilt.fingerprint(), new.fingerprint());
+
+        // Placeholders are kept across rebuilds
+        let (again, removed) = rebuilt.rebuild_from(&new);
+
+        assert!(removed.is_empty());
+        assert_eq!(again.str_ids().collect::<Vec<_>>(), rebuilt.str_ids().collect::<Vec<_>>());
+        assert_eq!(again.id::<BlockObsidian>(), rebuilt.id::<BlockObsidian>());
+    }
+}
@@ -0,0 +1,65 @@
+use crate::world::{ Block, block };
+use crate::util::Bits;
+use crate::math::Direction;
+
+/// An owned summary of a block, which can be held onto without borrowing the
+/// chunk it's in. See [`Chunk::info`](crate::world::Chunk::info).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Info
+{
+    /// See [Block::ID]
+    pub id: &'static str,
+    /// The packed state of [block::Repr::Val] blocks, or `None` for [block::Repr::Ptr]
+    /// blocks whose state doesn't fit in a copy
+    pub state: Option<Bits<6>>,
+    /// See [Block::solid]
+    pub solid: bool,
+    /// See [Block::occludes], indexed by `Direction as usize`
+    pub occludes: [bool; 6],
+}
+
+impl Info
+{
+    /// Summarize the block `block`, whose packed representation is `packed`
+    pub(in crate::world) fn new(block: &dyn block::Object, packed: block::Packed) -> Self
+    {
+        let state = match packed.tag()
+        {
+            // SAFETY:
+            // Just checked the tag
+            block::packed::Repr::Val => Some(unsafe { packed.val }.state()),
+            block::packed::Repr::Ptr => None,
+        };
+        let mut occludes = [false; 6];
+        for (occludes, &face) in occludes.iter_mut().zip(Direction::ALL.iter())
+        {
+            *occludes = block.occludes(face);
+        }
+
+        Self
+        {
+            id: block.id(),
+            state,
+            solid: block.solid(),
+            occludes,
+        }
+    }
+
+    /// Is this a summary of a block of type `T`?
+    #[inline]
+    pub fn is<T: Block>(&self) -> bool
+    {
+        self.id == T::ID
+    }
+
+    /// Get back the block summarized if it's a [block::Repr::Val] block of type `T`,
+    /// whose packed state holds all of it
+    pub fn unpack<T: Block>(&self) -> Option<T>
+    {
+        match (T::REPR, self.state)
+        {
+            (block::Repr::Val { from_packed, .. }, Some(state)) if self.is::<T>() => Some(from_packed(state)),
+            _ => None,
+        }
+    }
+}
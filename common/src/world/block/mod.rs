@@ -1,29 +1,40 @@
 pub mod packed;
 mod dynamic;
 mod borrow;
+mod choice;
+mod info;
+mod context;
 
 pub use blockdef::{ State, blockdef };
 
 pub use dynamic::{ Object, Registry };
-pub use borrow::{ Ref };
+pub use borrow::{ Ref, RefMut };
 pub use packed::Packed;
+pub use choice::Choice;
+pub use info::Info;
+pub use context::{ TickContext, PlaceContext };
 
 use dynamic::ObjectPriv;
 use borrow::RefMutPriv;
 
+use std::convert::TryFrom;
+
 use crate::util::Bits;
+use crate::math::{ Aabb, Direction, Vec3 };
 
-/// Trait for all block types.
+/// Trait for all block types. This is the one and only block trait; everything
+/// storing blocks(`Chunk`, `World`, [block::Registry]) is built around it.
 ///
 /// This shouldn't, by all means, by implemented manually. Incorrect implemtations may
-/// result in runtime panics, or worse, UB. Rather, use the [blockdef] macro:
+/// result in runtime panics, or worse, UB. Rather, use the [blockdef] macro, which
+/// also derives [block::State]:
 /// ```
 /// blockdef!
 /// {
 ///     id: "wooden_slab",
 ///     name: format!("{} Slab", self.variant),
 ///     
-///     #[derive(block::State, Debug, Clone, Copy, PartialEq, Eq)]
+///     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 ///     pub struct BlockWoodenSlab
 ///     {
 ///         #[prop(North | South | East | West | Up | Down)]
@@ -40,6 +51,91 @@ pub trait Block: State + Object
     
     /// Display name for this instance of a block
     fn name(&self) -> std::borrow::Cow<'static, str>;
+
+    /// Key of the texture this instance of a block is drawn with, ie. in the client's
+    /// texture atlas. Defaults to [Block::ID], for blocks that look the same in
+    /// every state.
+    fn looks(&self) -> std::borrow::Cow<'static, str> { Self::ID.into() }
+
+    /// Whether this instance of a block can be hit, ie. by raycasts. Defaults
+    /// to `true`.
+    fn solid(&self) -> bool { true }
+
+    /// Whether this instance of a block entirely covers the given face of its
+    /// cell, hiding whatever is behind it. Defaults to `true`, as for a full cube.
+    fn occludes(&self, _face: Direction) -> bool { true }
+
+    /// How much light is dimmed going through this instance of a block, from `0`
+    /// (not at all) to `15`(entirely). Defaults to `15` for blocks that
+    /// [occlude](Block::occludes) every face, and `0` otherwise.
+    fn light_attenuation(&self) -> u8
+    {
+        match Direction::ALL.iter().all(|&face| Block::occludes(self, face))
+        {
+            true => 15,
+            false => 0,
+        }
+    }
+
+    /// How hard this instance of a block is to break, ie. how much of an explosion's
+    /// power it absorbs. Defaults to `1.0`, and [f32::INFINITY] for blocks that
+    /// should never break.
+    fn hardness(&self) -> f32 { 1.0 }
+
+    /// How much light this instance of a block gives off, from `0` to `15`.
+    /// Defaults to `0`.
+    fn light_emission(&self) -> u8 { 0 }
+
+    /// Called on a block about to be placed, see [World::place](crate::world::World::place).
+    /// Returns the block to place instead, ie. oriented after where it's placed, or
+    /// `None` if it can't be placed there. Defaults to placing it as-is.
+    fn on_place(self, _ctx: &PlaceContext<'_>) -> Option<Self> where Self: Sized { Some(self) }
+
+    /// Called on blocks picked at random, see [World::random_tick](crate::world::World::random_tick).
+    /// Returns the block to replace this one with, if any. Defaults to doing nothing.
+    fn random_tick(&self, _ctx: &TickContext<'_>) -> Option<Choice> { None }
+
+    /// Called when the block adjacent to this one in the direction `dir` was replaced,
+    /// see [World::process_updates](crate::world::World::process_updates). Returns the
+    /// block to replace this one with, if any. Defaults to doing nothing.
+    fn on_neighbor_changed(&self, _ctx: &TickContext<'_>, _dir: Direction) -> Option<Choice> { None }
+
+    /// Called when a tick scheduled for this block comes due, see [World::schedule_tick](crate::world::World::schedule_tick).
+    /// Returns the block to replace this one with, if any. Defaults to doing nothing.
+    fn on_scheduled_tick(&self, _ctx: &TickContext<'_>) -> Option<Choice> { None }
+
+    /// Called when this block is broken, see [World::break_block](crate::world::World::break_block).
+    /// Returns other blocks to replace along with this one, ie. the rest of a block
+    /// spanning multiple cells. Defaults to nothing.
+    fn on_break(&self, _ctx: &TickContext<'_>) -> Vec<(Vec3<i32>, Choice)> { Vec::new() }
+
+    /// Boxes this instance of a block collides with, in its cell's space(`0..1` on
+    /// every axis). Defaults to the whole cell for [solid](Block::solid) blocks, and
+    /// nothing otherwise.
+    fn collision_boxes(&self) -> Vec<Aabb<f32>>
+    {
+        match Block::solid(self)
+        {
+            true => vec![Aabb { min: Vec3::zero(), max: Vec3::one() }],
+            false => Vec::new(),
+        }
+    }
+
+    /// Write this block's state to `out`, so that it can be saved. Only
+    /// [block::Repr::Ptr] blocks need to implement this, as the packed state
+    /// of [block::Repr::Val] blocks is saved as-is.
+    ///
+    /// `Ptr` blocks that don't implement this(and [Block::deserialize]) are
+    /// lost when saved, and come back as air.
+    fn serialize(&self, _out: &mut Vec<u8>) { }
+
+    /// Read this block's state from what was written by [Block::serialize],
+    /// returning `None` if it's invalid.
+    fn deserialize(_bytes: &[u8]) -> Option<Self> where Self: Sized { None }
+
+    /// A sensible instance of this type of block, ie. when placed from the hotbar.
+    /// Defaults to all of its props zeroed, see [block::State::zeroed].
+    fn default_state() -> Self where Self: Sized { <Self as State>::zeroed() }
 }
 
 /// Part of the [Block], which can be derived on its own(see [Block]'s doc), but
@@ -49,6 +145,15 @@ pub trait State: Sized
     /// Whether instances of this type of [Block] can (de)serialize their state
     /// in 6 bits.
     const REPR: Repr<Self>;
+
+    /// This state with all of its props zeroed, ie. the first of an `enum`'s variants,
+    /// the start of a range, `false` for flags and `Default::default()` for unpacked
+    /// props.
+    fn zeroed() -> Self;
+
+    /// The name and value of each of this state's packed props, in declaration
+    /// order, ie. `[("facing", "North")]`. Unpacked(`#[prop(!)]`) props are left out.
+    fn properties(&self) -> Vec<(&'static str, String)>;
 }
 
 /// Unique identifier for a type of [Block], assigned at runtime by
@@ -56,6 +161,36 @@ pub trait State: Sized
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Id(u16);
 
+/// Error converting a number too large to fit in the 9 bits of a [block::Id]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdOutOfRange(pub usize);
+
+impl Id
+{
+    /// Number of distinct [block::Id]s, as they're packed into 9 bits
+    pub const COUNT: usize = 1 << 9;
+}
+
+impl From<Id> for usize
+{
+    #[inline]
+    fn from(id: Id) -> Self
+    {
+        id.0 as _
+    }
+}
+
+impl TryFrom<usize> for Id
+{
+    type Error = IdOutOfRange;
+
+    #[inline]
+    fn try_from(id: usize) -> Result<Self, Self::Error>
+    {
+        if id < Id::COUNT { Ok(Id(id as _)) } else { Err(IdOutOfRange(id)) }
+    }
+}
+
 /// Represents the two ways [Block]'s state can be packed. This must be known statically,
 /// but deriving the [Block] trait takes care of that.
 #[derive(Clone, Copy)]
@@ -106,4 +241,184 @@ pub enum Repr<T: State + Sized>
     ///
     /// } // 16-bits
     Ptr,
+}
+
+#[cfg(test)]
+mod test
+{
+    use std::convert::TryFrom;
+
+    use crate::world::block::{ self, IdOutOfRange, Block, State, Repr, blockdef };
+    use crate::util::Bits;
+
+    blockdef!
+    {
+        id: "marker",
+        name: "Marker",
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct BlockMarker;
+    }
+
+    blockdef!
+    {
+        id: "snow_layer",
+        name: format!("Snow Layer ({})", self.0 + 1),
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct BlockSnowLayer(#[prop(0..4)] pub u8);
+    }
+
+    blockdef!
+    {
+        id: "lamp",
+        name: "Lamp",
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct BlockLamp
+        {
+            #[prop(bool)]
+            pub lit: bool,
+            #[prop(0..4)]
+            pub color: u8,
+        }
+    }
+
+    #[test]
+    fn id_conversion()
+    {
+        assert_eq!(block::Id::try_from(0).map(usize::from), Ok(0));
+        assert_eq!(block::Id::try_from(42).map(usize::from), Ok(42));
+        assert_eq!(block::Id::try_from(511).map(usize::from), Ok(511));
+        assert_eq!(block::Id::try_from(512), Err(IdOutOfRange(512)));
+        assert_eq!(block::Id::try_from(usize::MAX), Err(IdOutOfRange(usize::MAX)));
+    }
+
+    #[test]
+    fn unit_block()
+    {
+        match BlockMarker::REPR
+        {
+            Repr::Val { into_packed, from_packed } =>
+            {
+                assert_eq!(into_packed(&BlockMarker), Bits::default());
+                assert_eq!(from_packed(Bits::new(0b101010)), BlockMarker);
+            },
+            Repr::Ptr => panic!("unit block should be `Repr::Val`"),
+        }
+        assert_eq!(BlockMarker::ID, "marker");
+        assert_eq!(BlockMarker.name(), "Marker");
+    }
+
+    #[test]
+    fn tuple_block()
+    {
+        match BlockSnowLayer::REPR
+        {
+            Repr::Val { into_packed, from_packed } =>
+            {
+                for n in 0..4
+                {
+                    let layer = BlockSnowLayer(n);
+
+                    assert_eq!(from_packed(into_packed(&layer)), layer);
+                }
+            },
+            Repr::Ptr => panic!("tuple block should be `Repr::Val`"),
+        }
+        assert_eq!(BlockSnowLayer::ID, "snow_layer");
+        assert_eq!(BlockSnowLayer(2).name(), "Snow Layer (3)");
+    }
+
+    #[test]
+    fn bool_prop()
+    {
+        match BlockLamp::REPR
+        {
+            Repr::Val { into_packed, from_packed } =>
+            {
+                for &lit in &[false, true]
+                {
+                    for color in 0..4
+                    {
+                        let lamp = BlockLamp { lit, color };
+
+                        assert_eq!(from_packed(into_packed(&lamp)), lamp);
+                    }
+                }
+                assert_eq!(into_packed(&BlockLamp { lit: true, color: 0 }).get::<0, 1>(), 1);
+            },
+            Repr::Ptr => panic!("bool block should be `Repr::Val`"),
+        }
+    }
+
+    #[test]
+    fn stairs_states()
+    {
+        use crate::vanilla::blocks::{ BlockStairs, Half, StairsShape };
+        use crate::math::Direction;
+
+        let (into_packed, from_packed) = match BlockStairs::REPR
+        {
+            Repr::Val { into_packed, from_packed } => (into_packed, from_packed),
+            Repr::Ptr => panic!("stairs should be `Repr::Val`"),
+        };
+        let shapes = [
+            StairsShape::Straight,
+            StairsShape::InnerLeft,
+            StairsShape::InnerRight,
+            StairsShape::OuterLeft,
+            StairsShape::OuterRight,
+        ];
+
+        let mut packed = Vec::new();
+        for &facing in &Direction::HORIZONTAL
+        {
+            for &half in &[Half::Bottom, Half::Top]
+            {
+                for &shape in &shapes
+                {
+                    let stairs = BlockStairs { facing, half, shape };
+
+                    assert_eq!(from_packed(into_packed(&stairs)), stairs);
+                    packed.push(into_packed(&stairs));
+                }
+            }
+        }
+        // every state is distinct
+        packed.sort_unstable_by_key(|bits| bits.inner());
+        packed.dedup();
+        assert_eq!(packed.len(), 4 * 2 * 5);
+    }
+
+    #[test]
+    fn wool_states()
+    {
+        use std::collections::HashSet;
+
+        use crate::vanilla::blocks::BlockWool;
+        use crate::vanilla::props::DyeColor;
+        use crate::world::block::Object;
+
+        let (into_packed, from_packed) = match BlockWool::REPR
+        {
+            Repr::Val { into_packed, from_packed } => (into_packed, from_packed),
+            Repr::Ptr => panic!("wool should be `Repr::Val`"),
+        };
+
+        let mut looks = HashSet::new();
+        for &color in &DyeColor::ALL
+        {
+            let wool = BlockWool { color };
+
+            assert_eq!(from_packed(into_packed(&wool)), wool);
+            // 16 colors fit in 4 bits
+            assert_eq!(into_packed(&wool).get::<4, 6>(), 0);
+            assert!(looks.insert(Object::looks(&wool)));
+        }
+        assert_eq!(looks.len(), 16);
+        assert!(looks.contains("light_blue_wool"));
+        assert_eq!(Object::name(&BlockWool { color: DyeColor::LightBlue }), "Light Blue Wool");
+        assert_eq!(Object::looks(&BlockMarker), "marker");
+    }
 }
\ No newline at end of file
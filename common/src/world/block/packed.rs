@@ -97,6 +97,46 @@ impl Packed
     {
         Self { val: Val(0) }
     }
+
+    /// Describe this packed block in a human-readable way, ie. for logs and tools,
+    /// by resolving its ID and state through `registry`:
+    /// ```text
+    /// wooden_slab { facing: North, variant: Oak }
+    /// ```
+    /// "Pointer" blocks only know their slot without their chunk, so they're
+    /// described as such.
+    pub fn describe(self, registry: &block::Registry) -> String
+    {
+        let val = match self.tag()
+        {
+            // SAFETY:
+            // Tag just checked
+            Repr::Val => unsafe { self.val },
+            Repr::Ptr => return format!("{:?}", self),
+        };
+        let id = match registry.str_id(val.id())
+        {
+            Some(id) => id,
+            None => return format!("{:?}", self),
+        };
+        // SAFETY:
+        // Just checked that the block is registered
+        let properties = unsafe { registry.create_ref(&val) }.properties();
+
+        match properties.is_empty()
+        {
+            true => id.to_owned(),
+            false =>
+            {
+                let properties = properties
+                    .iter()
+                    .map(|(name, value)| format!("{}: {}", name, value))
+                    .collect::<Vec<_>>();
+
+                format!("{} {{ {} }}", id, properties.join(", "))
+            },
+        }
+    }
 }
 
 impl Val
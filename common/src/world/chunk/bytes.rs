@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::world::{ Chunk, block };
+use crate::util::{ Bits, Fnv1a };
+use crate::math::Vec3;
+
+/// Version of the chunk byte format written by [Chunk::to_bytes]
+const VERSION: u8 = 2;
+
+/// Oldest version of the chunk byte format still read by [Chunk::from_bytes],
+/// which had no checksum
+const MIN_VERSION: u8 = 1;
+
+/// Tag of a cell referring to the `Ptr` block list rather than the palette
+const PTR_TAG: u16 = 1 << 15;
+
+impl<const SIZE: usize> Chunk<SIZE>
+{
+    /// Serialize this chunk's blocks into a self-describing byte format, which
+    /// doesn't depend on the numeric [block::Id]s of the current registry:
+    /// ```text
+    /// struct Chunk
+    /// {
+    ///     version: u8,
+    ///     size: u16,
+    ///     // string identifiers of every type of block in this chunk
+    ///     palette: [u8 len, [u8; len]; u16 len],
+    ///     // `Val` blocks: palette index(9 bits) + state(6 bits)
+    ///     // `Ptr` blocks: tag(1 bit) + index into `ptrs`(15 bits)
+    ///     cells: [u16; VOLUME],
+    ///     // output of `Block::serialize` for every `Ptr` block
+    ///     ptrs: [u16 palette index, u32 len, [u8; len]; u16 len],
+    ///     // see `Chunk::checksum`
+    ///     checksum: u64,
+    /// } // little endian
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8>
+    {
+        let mut palette = Vec::<&'static str>::new();
+        let mut lookup = HashMap::<&'static str, u16>::new();
+        let mut index = |id: &'static str| *lookup
+            .entry(id)
+            .or_insert_with(||
+            {
+                palette.push(id);
+                (palette.len() - 1) as u16
+            });
+
+        let mut cells = Vec::with_capacity(Self::VOLUME * 2);
+        let mut ptrs = Vec::new();
+        let mut num_ptrs = 0u16;
+
+        // Cells are always written in canonical order, so the format doesn't depend
+        // on the `morton-chunks` feature
+        for i in (0..Self::VOLUME).map(|i| Self::flatten_idx(Self::canonical_pos(i)))
+        {
+            let packed = &self.blocks[i];
+            let cell = match packed.tag()
+            {
+                block::packed::Repr::Val =>
+                {
+                    // SAFETY:
+                    // Just checked the tag
+                    let val = unsafe { packed.val };
+                    // Only registered blocks make it into a chunk
+                    let id = self.registry
+                        .str_id(val.id())
+                        .expect("block in chunk isn't registered");
+
+                    (index(id) << 6) | val.state().inner() as u16
+                },
+                block::packed::Repr::Ptr =>
+                {
+                    // SAFETY:
+                    // `i` is within `self.blocks`
+                    let block = unsafe { self.get_unchecked_flat(i) };
+                    let mut data = Vec::new();
+
+                    block.serialize(&mut data);
+
+                    ptrs.extend_from_slice(&index(block.id()).to_le_bytes());
+                    ptrs.extend_from_slice(&(data.len() as u32).to_le_bytes());
+                    ptrs.extend_from_slice(&data);
+                    num_ptrs += 1;
+
+                    PTR_TAG | (num_ptrs - 1)
+                },
+            };
+            cells.extend_from_slice(&cell.to_le_bytes());
+        }
+
+        let mut out = vec![VERSION];
+
+        out.extend_from_slice(&(SIZE as u16).to_le_bytes());
+        out.extend_from_slice(&(palette.len() as u16).to_le_bytes());
+        for id in palette
+        {
+            out.push(id.len() as u8);
+            out.extend_from_slice(id.as_bytes());
+        }
+        out.extend_from_slice(&cells);
+        out.extend_from_slice(&num_ptrs.to_le_bytes());
+        out.extend_from_slice(&ptrs);
+        out.extend_from_slice(&self.checksum().to_le_bytes());
+        out
+    }
+
+    /// Hash this chunk's contents, ie. to detect corruption in saved chunks. This
+    /// is content-based: equal chunks have the same checksum regardless of their
+    /// registry's numeric [block::Id]s or where their `Ptr` blocks are stored.
+    pub fn checksum(&self) -> u64
+    {
+        let mut hash = Fnv1a::default();
+
+        for i in (0..Self::VOLUME).map(|i| Self::flatten_idx(Self::canonical_pos(i)))
+        {
+            let packed = &self.blocks[i];
+            // SAFETY:
+            // `i` is within `self.blocks`
+            let block = unsafe { self.get_unchecked_flat(i) };
+
+            hash.write(&[block.id().len() as u8]);
+            hash.write(block.id().as_bytes());
+            match packed.tag()
+            {
+                block::packed::Repr::Val =>
+                {
+                    // SAFETY:
+                    // Just checked the tag
+                    hash.write(&[unsafe { packed.val }.state().inner()]);
+                },
+                block::packed::Repr::Ptr =>
+                {
+                    let mut data = Vec::new();
+
+                    block.serialize(&mut data);
+                    hash.write(&(data.len() as u32).to_le_bytes());
+                    hash.write(&data);
+                },
+            }
+        }
+        hash.finish()
+    }
+
+    /// Deserialize a chunk at the given chunk position from the output of
+    /// [Chunk::to_bytes]. Returns `None` if the bytes are malformed, of a
+    /// different version/size, or don't match their [checksum](Chunk::checksum).
+    ///
+    /// Blocks that aren't in the registry, have changed representation since,
+    /// or whose data [Block::deserialize](block::Block::deserialize) rejects
+    /// are replaced with air. The checksum can't be verified then.
+    pub fn from_bytes(pos: Vec3<i32>, registry: &Arc<block::Registry>, bytes: &[u8]) -> Option<Self>
+    {
+        let mut reader = Reader(bytes);
+
+        let version = reader.u8()?;
+        if !(MIN_VERSION..=VERSION).contains(&version) || reader.u16()? as usize != SIZE
+        {
+            return None
+        }
+
+        // Map palette indices to this registry's IDs
+        let palette = (0..reader.u16()?)
+            .map(|_|
+            {
+                let len = reader.u8()? as usize;
+                let id = std::str::from_utf8(reader.bytes(len)?).ok()?;
+
+                Some(registry.id_of(id))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let cells = reader.bytes(Self::VOLUME * 2)?;
+
+        let mut chunk = Self::new(pos, registry);
+        let mut ptr_cells = Vec::new();
+        // Whether any block was replaced with air
+        let mut lossy = false;
+
+        for (i, cell) in cells.chunks_exact(2).enumerate()
+        {
+            let i = Self::flatten_idx(Self::canonical_pos(i));
+            let cell = u16::from_le_bytes([cell[0], cell[1]]);
+
+            if cell & PTR_TAG != 0
+            {
+                // Resolved once the `Ptr` blocks are read
+                ptr_cells.push((i, (cell & !PTR_TAG) as usize));
+                continue;
+            }
+            let id = match palette.get((cell >> 6) as usize)?
+            {
+                Some(id) if registry.repr(*id) == Some(block::packed::Repr::Val) => *id,
+                // Leave as air
+                _ =>
+                {
+                    lossy = true;
+                    continue
+                },
+            };
+            chunk.blocks[i] = block::Packed::from_val(id, Bits::new(cell as u8));
+        }
+
+        let mut ptrs = (0..reader.u16()?)
+            .map(|_|
+            {
+                let id = *palette.get(reader.u16()? as usize)?;
+                let len = reader.u32()? as usize;
+                let data = reader.bytes(len)?;
+
+                Some(id
+                    .filter(|id| registry.repr(*id) == Some(block::packed::Repr::Ptr))
+                    .and_then(|id| registry.deserialize(id, data)))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        for (i, ptr) in ptr_cells
+        {
+            // Each `Ptr` block is referred to by exactly one cell
+            match ptrs.get_mut(ptr)?.take()
+            {
+                Some(block) =>
+                {
+                    let slot = chunk.addr_blocks.insert(block);
+
+                    chunk.blocks[i] = block::Packed::from_ptr(slot);
+                },
+                None => lossy = true,
+            }
+        }
+
+        if version >= 2
+        {
+            let checksum = reader.u64()?;
+
+            if !lossy && checksum != chunk.checksum()
+            {
+                return None
+            }
+        }
+        Some(chunk)
+    }
+}
+
+/// Reads little endian values off the front of a byte slice
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a>
+{
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]>
+    {
+        if self.0.len() < len { return None }
+
+        let (bytes, rest) = self.0.split_at(len);
+
+        self.0 = rest;
+        Some(bytes)
+    }
+
+    fn u8(&mut self) -> Option<u8>
+    {
+        self.bytes(1).map(|b| b[0])
+    }
+
+    fn u16(&mut self) -> Option<u16>
+    {
+        self.bytes(2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Option<u32>
+    {
+        self.bytes(4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn u64(&mut self) -> Option<u64>
+    {
+        self.bytes(8).map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+    }
+}
@@ -0,0 +1,94 @@
+use crate::world::block;
+use crate::world::Chunk;
+use crate::math::{ Direction, Vec3 };
+
+/// Get the position adjacent to `pos` in the given direction, if it's within the
+/// bounds of a chunk
+fn step<const SIZE: usize>(pos: Vec3<usize>, dir: Direction) -> Option<Vec3<usize>>
+{
+    let normal = dir.normal::<isize>();
+    let out = Vec3::new
+    (
+        pos.x.checked_add_signed(normal.x)?,
+        pos.y.checked_add_signed(normal.y)?,
+        pos.z.checked_add_signed(normal.z)?,
+    );
+    match Chunk::<SIZE>::in_bounds(out)
+    {
+        true => Some(out),
+        false => None,
+    }
+}
+
+impl<const SIZE: usize> Chunk<SIZE>
+{
+    /// Get the six blocks adjacent to the block at the given position, in chunk-space,
+    /// indexed by `Direction as usize`. Neighbors that would be outside of this chunk's
+    /// bounds are `None`.
+    pub fn neighbors(&self, pos: Vec3<usize>) -> [Option<&dyn block::Object>; 6]
+    {
+        let mut out = [None; 6];
+
+        for (neighbor, &dir) in out.iter_mut().zip(Direction::ALL.iter())
+        {
+            *neighbor = step::<SIZE>(pos, dir).and_then(|pos| self.get(pos));
+        }
+        out
+    }
+
+    /// Calls `f` for every face of every non-air block in this chunk that borders
+    /// air or the chunk's bounds, along with the block's position in chunk-space
+    /// and the direction the face is pointing.
+    ///
+    /// This doesn't allocate, and is meant to be the building block of meshing,
+    /// occlusion and surface area calculations.
+    pub fn visit_exposed_faces(&self, mut f: impl FnMut(Vec3<usize>, Direction, &dyn block::Object))
+    {
+        for (pos, block) in self
+        {
+            if self.is_air(pos) { continue }
+
+            for &dir in &Direction::ALL
+            {
+                if self.is_exposed(pos, dir)
+                {
+                    f(pos, dir, block);
+                }
+            }
+        }
+    }
+
+    /// Iterate over the position, in chunk-space, of every non-air block in this
+    /// chunk with at least one face bordering air or the chunk's bounds, ie. its
+    /// visible surface, in canonical order. Blocks enclosed on all six sides are
+    /// skipped without being looked up.
+    pub fn surface_cells(&self) -> impl Iterator<Item = Vec3<usize>> + '_
+    {
+        (0..Self::VOLUME)
+            .map(Self::canonical_pos)
+            .filter(move |&pos| !self.is_air(pos) && Direction::ALL
+                .iter()
+                .any(|&dir| self.is_exposed(pos, dir)))
+    }
+
+    /// Whether the block at `pos`, in chunk-space, is air
+    #[inline]
+    fn is_air(&self, pos: Vec3<usize>) -> bool
+    {
+        // Air blocks are always packed as all 0s
+        self.blocks[Self::flatten_idx(pos)] == block::Packed::zeroed()
+    }
+
+    /// Whether the face pointing towards `dir` of the block at `pos`, in chunk-space,
+    /// borders air or the chunk's bounds
+    #[inline]
+    fn is_exposed(&self, pos: Vec3<usize>, dir: Direction) -> bool
+    {
+        match step::<SIZE>(pos, dir)
+        {
+            Some(neighbor) => self.is_air(neighbor),
+            // Chunk edge
+            None => true,
+        }
+    }
+}
@@ -2,28 +2,45 @@ use std::ops::{ Index, IndexMut };
 
 use crate::world::block::{ Block, self };
 use crate::world::Chunk;
-use crate::math::Vec3;
+use crate::math::{ Vec3, morton };
 
-impl Chunk
+impl<const SIZE: usize> Chunk<SIZE>
 {
     /// Is the given coordinate, in chunk-space, within the bounds
     /// of the chunk?
     #[inline]
     pub const fn in_bounds(Vec3 { x, y, z }: Vec3<usize>) -> bool
     {
-        x < Chunk::SIZE && y < Chunk::SIZE && z < Chunk::SIZE
+        x < SIZE && y < SIZE && z < SIZE
     }
 
-    /// Flatten a 3D chunk-space position to an index array
+    /// Flatten a 3D chunk-space position to an index into this chunk's storage. That's
+    /// its [canonical index](Chunk::canonical_pos), unless the `morton-chunks` feature
+    /// is enabled and blocks are stored in [Morton order](morton) instead.
     #[inline]
-    fn flatten_idx(Vec3 { x, y, z }: Vec3<usize>) -> usize
+    pub(super) fn flatten_idx(pos: Vec3<usize>) -> usize
     {
-        x + Chunk::SIZE * (y + Chunk::SIZE * z)
+        match cfg!(feature = "morton-chunks")
+        {
+            true => morton::encode3(pos),
+            false => pos.x + SIZE * (pos.y + SIZE * pos.z),
+        }
+    }
+
+    /// Get the chunk-space position of the `idx`th block in canonical order, with `x`
+    /// changing the fastest, then `y`, then `z`. This is the order blocks are iterated
+    /// and serialized in, regardless of how they're stored.
+    #[inline]
+    pub(super) fn canonical_pos(idx: usize) -> Vec3<usize>
+    {
+        Vec3::new(idx % SIZE, (idx / SIZE) % SIZE, idx / (SIZE * SIZE))
     }
 
     /// See [Chunk::get_unchecked]
     pub(super) unsafe fn get_unchecked_flat(&self, id: usize) -> &dyn block::Object
     {
+        debug_assert!(id < self.blocks.len(), "block index {} out of bounds", id);
+
         // Get packed state
         let state = self.blocks.get_unchecked(id);
 
@@ -56,6 +73,8 @@ impl Chunk
     #[inline]
     pub unsafe fn get_unchecked(&self, pos: Vec3<usize>) -> &dyn block::Object
     {
+        debug_assert!(Self::in_bounds(pos), "position {} out of bounds", pos);
+
         self.get_unchecked_flat(Self::flatten_idx(pos))
     }
 
@@ -64,6 +83,11 @@ impl Chunk
     /// matching to generic parameter `T`.
     pub unsafe fn get_unchecked_mut(&mut self, pos: Vec3<usize>) -> &mut dyn block::Object
     {
+        debug_assert!(Self::in_bounds(pos), "position {} out of bounds", pos);
+
+        // The block may be mutated through the reference
+        self.version += 1;
+
         // Get packed state
         let state = self.blocks.get_unchecked_mut(Self::flatten_idx(pos));
 
@@ -97,6 +121,8 @@ impl Chunk
     /// Does nothing if the `Block` type `T` isn't registered.
     pub unsafe fn set_unchecked<T: Block>(&mut self, pos: Vec3<usize>, block: T)
     {
+        debug_assert!(Self::in_bounds(pos), "position {} out of bounds", pos);
+
         // Get existing packed state
         let old = self.blocks.get_unchecked_mut(Self::flatten_idx(pos));
 
@@ -141,6 +167,7 @@ impl Chunk
                 *old = block::Packed::from_ptr(slot);
             },
         }
+        self.version += 1;
     }
 
     /// Get an immutable reference to the block at the given position in chunk-space,
@@ -148,7 +175,7 @@ impl Chunk
     /// exceed chunks' bounds.
     pub fn get<'a>(&'a self, pos: Vec3<usize>) -> Option<&'a dyn block::Object>
     {
-        match Chunk::in_bounds(pos)
+        match Self::in_bounds(pos)
         {
             // SAFETY:
             // Bounds just checked above.
@@ -163,7 +190,7 @@ impl Chunk
     /// exceed chunks' bounds.
     pub fn get_mut<'a>(&'a mut self, pos: Vec3<usize>)-> Option<&'a mut dyn block::Object>
     {
-        match Chunk::in_bounds(pos)
+        match Self::in_bounds(pos)
         {
             // SAFETY:
             // Bounds just checked above.
@@ -173,6 +200,15 @@ impl Chunk
         }
     }
 
+    /// Get an owned summary of the block at the given position in chunk-space, returning
+    /// `None` if the coordinates provided exceed chunks' bounds.
+    pub fn info(&self, pos: Vec3<usize>) -> Option<block::Info>
+    {
+        let block = self.get(pos)?;
+
+        Some(block::Info::new(block, self.blocks[Self::flatten_idx(pos)]))
+    }
+
     /// Set the block at the given position, in chunk-space, ot do nothing if the position
     /// is out of chunks' bounds. The block previously there is discarded, and replaced
     /// with that provided.
@@ -180,14 +216,175 @@ impl Chunk
     {
         // SAFETY:
         // Bounds just checked above.
-        if Chunk::in_bounds(pos)
+        if Self::in_bounds(pos)
         {
             unsafe { self.set_unchecked(pos, block) }
         }
     }
+
+    /// Set the block at the given position, in chunk-space, to an already type-erased
+    /// block, ie. one that was deserialized or picked at runtime. Otherwise the same
+    /// as [Chunk::set].
+    ///
+    /// Does nothing if the block's type isn't registered.
+    pub fn set_boxed(&mut self, pos: Vec3<usize>, block: Box<dyn block::Object>)
+    {
+        if !Self::in_bounds(pos) { return }
+
+        // Get new block's ID from registry
+        let id = match self.registry.id_of_object(&*block)
+        {
+            // Found in registry
+            Some(id) => id,
+            // Not registered, early return
+            None =>
+            {
+                #[cfg(debug_assertions)]
+                println!("Attempted to set unregistered block {} in a chunk.\n\
+                Use `BlockRegistry::register` beforehand to add it.", block.id());
+
+                return
+            }
+        };
+
+        // Clean up old block
+        let old = &mut self.blocks[Self::flatten_idx(pos)];
+        if old.tag() == block::packed::Repr::Ptr
+        {
+            // SAFETY:
+            // Just checked the tag
+            self.addr_blocks.remove(unsafe { old.ptr }.slot());
+        }
+
+        *old = match block.packed_state()
+        {
+            // Serialize, the box isn't needed anymore
+            Some(state) => block::Packed::from_val(id, state),
+            // Save as-is
+            None => block::Packed::from_ptr(self.addr_blocks.insert(block)),
+        };
+        self.version += 1;
+    }
+
+    /// Take the block at the given position, in chunk-space, out of the chunk and
+    /// leave air in its place. Returns `None` if the position is out of the chunk's
+    /// bounds.
+    pub fn take(&mut self, pos: Vec3<usize>) -> Option<Box<dyn block::Object>>
+    {
+        if !Self::in_bounds(pos) { return None }
+
+        self.version += 1;
+
+        // Air is always registered first
+        let old = std::mem::replace(&mut self.blocks[Self::flatten_idx(pos)], block::Packed::zeroed());
+
+        Some(match old.tag()
+        {
+            // SAFETY I:
+            // Just checked the tag
+            //
+            // SAFETY II:
+            // Only registered blocks are ever entered into the chunk
+            block::packed::Repr::Val => unsafe { self.registry.create_boxed(old.val) },
+            // SAFETY:
+            // Just checked the tag
+            block::packed::Repr::Ptr => self.addr_blocks.remove(unsafe { old.ptr }.slot()),
+        })
+    }
+
+    /// Typed counterpart to [Chunk::take]. Returns `None`, leaving the block in place,
+    /// if it isn't a `T`.
+    pub fn take_as<T: Block>(&mut self, pos: Vec3<usize>) -> Option<T>
+    {
+        if !self.get(pos)?.is::<T>() { return None }
+
+        let block = Box::into_raw(self.take(pos)?);
+
+        // SAFETY:
+        // Just checked the block's type, and [Chunk::take] boxes blocks as the
+        // `Block` type itself, never as a wrapper over their packed state
+        Some(*unsafe { Box::from_raw(block as *mut T) })
+    }
+
+    /// Set every block in the box between the corners `a` and `b`(inclusive, in any
+    /// order), in chunk-space, to a copy of `block`. Parts of the box out of the
+    /// chunk's bounds are ignored. Returns the number of blocks set.
+    pub fn fill_region<T: Block + Clone>(&mut self, a: Vec3<usize>, b: Vec3<usize>, block: T) -> usize
+    {
+        let min = Vec3::partial_min(a, b);
+        let max = Vec3::partial_max(a, b).map(|n: usize| n.min(SIZE - 1));
+
+        if !Self::in_bounds(min)
+        {
+            return 0
+        }
+        for z in min.z..=max.z
+        {
+            for y in min.y..=max.y
+            {
+                for x in min.x..=max.x
+                {
+                    // SAFETY:
+                    // `min` is in bounds, and `max` was clamped to them
+                    unsafe { self.set_unchecked(Vec3::new(x, y, z), block.clone()) }
+                }
+            }
+        }
+        (max - min + 1).product()
+    }
+
+    /// Replace the block at `to` with a copy of that at `from`, both in chunk-space,
+    /// ie. for brushes and symmetry tools. Returns whether `to` now holds a copy,
+    /// which is always the case when `from == to`.
+    ///
+    /// Nothing is copied if either position is out of the chunk's bounds, nor for
+    /// `Ptr` blocks that don't implement [Block::serialize] and [Block::deserialize],
+    /// which are round-tripped through them the same way they're saved.
+    pub fn copy_cell(&mut self, from: Vec3<usize>, to: Vec3<usize>) -> bool
+    {
+        if !Self::in_bounds(from) || !Self::in_bounds(to) { return false }
+        if from == to { return true }
+
+        let packed = self.blocks[Self::flatten_idx(from)];
+        let new = match packed.tag()
+        {
+            block::packed::Repr::Val => packed,
+            block::packed::Repr::Ptr =>
+            {
+                // SAFETY:
+                // Just checked the tag
+                let block = &self.addr_blocks[unsafe { packed.ptr }.slot()];
+                let mut data = Vec::new();
+
+                block.serialize(&mut data);
+                // Only registered blocks are ever entered into the chunk
+                let id = self.registry
+                    .id_of_object(&**block)
+                    .expect("block in chunk isn't registered");
+
+                match self.registry.deserialize(id, &data)
+                {
+                    Some(copy) => block::Packed::from_ptr(self.addr_blocks.insert(copy)),
+                    None => return false,
+                }
+            },
+        };
+
+        // Clean up old block
+        let old = std::mem::replace(&mut self.blocks[Self::flatten_idx(to)], new);
+        if old.tag() == block::packed::Repr::Ptr
+        {
+            // SAFETY:
+            // Just checked the tag
+            self.addr_blocks.remove(unsafe { old.ptr }.slot());
+        }
+        self.version += 1;
+
+        true
+    }
 }
 
-impl Index<Vec3<usize>> for Chunk
+impl<const SIZE: usize> Index<Vec3<usize>> for Chunk<SIZE>
 {
     type Output = dyn block::Object;
 
@@ -198,7 +395,7 @@ impl Index<Vec3<usize>> for Chunk
     }
 }
 
-impl IndexMut<Vec3<usize>> for Chunk
+impl<const SIZE: usize> IndexMut<Vec3<usize>> for Chunk<SIZE>
 {
     #[inline]
     fn index_mut(&mut self, index: Vec3<usize>) -> &mut Self::Output
@@ -207,7 +404,7 @@ impl IndexMut<Vec3<usize>> for Chunk
     }
 }
 
-impl Index<(usize, usize, usize)> for Chunk
+impl<const SIZE: usize> Index<(usize, usize, usize)> for Chunk<SIZE>
 {
     type Output = dyn block::Object;
 
@@ -218,7 +415,7 @@ impl Index<(usize, usize, usize)> for Chunk
     }
 }
 
-impl IndexMut<(usize, usize, usize)> for Chunk
+impl<const SIZE: usize> IndexMut<(usize, usize, usize)> for Chunk<SIZE>
 {
     #[inline]
     fn index_mut(&mut self, index: (usize, usize, usize)) -> &mut Self::Output
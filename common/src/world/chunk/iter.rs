@@ -1,21 +1,33 @@
 use crate::world::block;
 use crate::world::Chunk;
-use crate::math::Vec3;
+use crate::math::{ Vec3, morton };
 
 /// An iterator over a [Chunk]
-pub struct Iter<'a>
+pub struct Iter<'a, const SIZE: usize = 32>
 {
     /// The [Chunk] being iterated
-    chunk: &'a Chunk,
-    /// Next (flat) block index
+    chunk: &'a Chunk<SIZE>,
+    /// Next block index, in canonical order
     next: usize,
 }
 
-impl Chunk
+/// An iterator over the positions in a [Chunk], in Morton order. See [Chunk::morton_iter]
+pub struct MortonIter<const SIZE: usize = 32>
 {
-    /// Iterate over all of this [Chunk]'s block
+    /// Next Morton code
+    next: usize,
+}
+
+impl<const SIZE: usize> Chunk<SIZE>
+{
+    /// Iterate over all of this [Chunk]'s block, along with their position in
+    /// chunk-space. Blocks are visited in canonical order, with `x` changing the
+    /// fastest, then `y`, then `z`, regardless of how they're stored:
+    /// ```text
+    /// (0, 0, 0), (1, 0, 0), .., (SIZE - 1, 0, 0), (0, 1, 0), .., (SIZE - 1, SIZE - 1, SIZE - 1)
+    /// ```
     #[inline]
-    pub fn iter<'a>(&'a self) -> Iter<'a>
+    pub fn iter<'a>(&'a self) -> Iter<'a, SIZE>
     {
         Iter
         {
@@ -23,12 +35,26 @@ impl Chunk
             next: 0,
         }
     }
+
+    /// Iterate over every position in chunk-space along a Z-order(Morton) curve,
+    /// which visits each `2x2x2` cube, then each `4x4x4` cube, and so on, before
+    /// moving on to the next. Passes that access neighbors, ie. meshing or lighting,
+    /// stay in cache much better than with [Chunk::iter]'s linear order. Each of
+    /// the [Chunk::VOLUME] positions is visited exactly once.
+    ///
+    /// This doesn't change how blocks are stored, only the order they're visited in. See
+    /// the `morton-chunks` feature for that.
+    #[inline]
+    pub fn morton_iter() -> MortonIter<SIZE>
+    {
+        MortonIter { next: 0 }
+    }
 }
 
-impl<'a> IntoIterator for &'a Chunk
+impl<'a, const SIZE: usize> IntoIterator for &'a Chunk<SIZE>
 {
     type Item = (Vec3<usize>, &'a dyn block::Object);
-    type IntoIter = Iter<'a>;
+    type IntoIter = Iter<'a, SIZE>;
 
     fn into_iter(self) -> Self::IntoIter
     {
@@ -36,26 +62,45 @@ impl<'a> IntoIterator for &'a Chunk
     }
 }
 
-impl<'a> Iterator for Iter<'a>
+impl<'a, const SIZE: usize> Iterator for Iter<'a, SIZE>
 {
     type Item = (Vec3<usize>, &'a dyn block::Object);
 
     fn next(&mut self) -> Option<Self::Item>
     {
-        if self.next < Chunk::VOLUME
+        if self.next < Chunk::<SIZE>::VOLUME
         {
-            let pos = Vec3::new(
-                self.next & 0x1f,
-                (self.next >> 5) & 0x1f,
-                self.next >> 10,
-            );
+            let pos = Chunk::<SIZE>::canonical_pos(self.next);
             // SAFETY:
             // `self.next` is guarenteed to be in-bounds, checked above
-            let block = unsafe { self.chunk.get_unchecked_flat(self.next) };
+            let block = unsafe { self.chunk.get_unchecked(pos) };
             self.next += 1;
 
             Some((pos, block))
         }
         else { None }
     }
-}
\ No newline at end of file
+}
+impl<const SIZE: usize> Iterator for MortonIter<SIZE>
+{
+    type Item = Vec3<usize>;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        // Morton codes span a power-of-two cube, so those outside of
+        // non-power-of-two chunks are skipped
+        let end = SIZE.next_power_of_two().pow(3);
+
+        while self.next < end
+        {
+            let pos = morton::decode3(self.next);
+            self.next += 1;
+
+            if Chunk::<SIZE>::in_bounds(pos)
+            {
+                return Some(pos)
+            }
+        }
+        None
+    }
+}
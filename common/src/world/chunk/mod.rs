@@ -1,5 +1,8 @@
 mod index;
 mod iter;
+mod faces;
+mod bytes;
+mod query;
 
 use std::sync::Arc;
 
@@ -8,11 +11,12 @@ use slab::Slab;
 use crate::world::block;
 use crate::math::Vec3;
 
-/// A `32`x`32`x`32` segment of a `World`, storing `Block`s and
-/// `Entity`s
-pub struct Chunk
+/// A `SIZE`x`SIZE`x`SIZE` segment of a `World`, storing `Block`s and
+/// `Entity`s. `SIZE` defaults to `32`, and can be anything as long as
+/// `SIZE`^3 fits in the `15` bits of a `Ptr` block's slot(ie. `SIZE <= 32`).
+pub struct Chunk<const SIZE: usize = 32>
 {
-    /// This chunk's position in its world, where 1 unit = 32 blocks.
+    /// This chunk's position in its world, where 1 unit = `SIZE` blocks.
     /// That means this *isn't* the position of minimum block in this
     /// chunk.
     pos: Vec3<i32>,
@@ -21,7 +25,9 @@ pub struct Chunk
     ///
     /// This contains all inline `data` blocks as well as `addr`
     /// blocks which point to an index in `self.addr_blocks`
-    blocks: Box<[block::Packed; Chunk::VOLUME]>,
+    ///
+    /// Always of length `Chunk::STORAGE`, see `Chunk::flatten_idx`.
+    blocks: Box<[block::Packed]>,
     /// All the `Block`s in this `Chunk` that can't be packed into
     /// 6 bits and are thus saved as-is.
     ///
@@ -34,31 +40,899 @@ pub struct Chunk
     /// containing type and identifier info about `Block`s which the
     /// chunk needs for indexing and mutating operations.
     registry: Arc<block::Registry>,
+    /// Number of mutations made to this chunk since it was created or loaded,
+    /// see [Chunk::version]
+    version: u64,
 }
 
-impl Chunk
+impl<const SIZE: usize> Chunk<SIZE>
 {
     /// Size, along a single dimension, of all chunks.
-    pub const SIZE: usize = 32;
+    pub const SIZE: usize = SIZE;
     /// Total number of blocks in any one chunk(including empty/air blocks).
-    pub const VOLUME: usize = 32 * 32 * 32;
+    pub const VOLUME: usize = SIZE * SIZE * SIZE;
+    /// Number of cells in a chunk's storage, which is [Chunk::VOLUME] unless blocks
+    /// are stored in Morton order(`morton-chunks` feature). Morton codes span a
+    /// power-of-two cube, whose cells outside of the chunk are left as air.
+    const STORAGE: usize = match cfg!(feature = "morton-chunks")
+    {
+        true => SIZE.next_power_of_two().pow(3),
+        false => Self::VOLUME,
+    };
+
+    /// Fails to compile(when a chunk is created) if `SIZE` is too large for
+    /// `Ptr` blocks' slots to address every block in the chunk.
+    const VALID: () = assert!(SIZE > 0 && SIZE * SIZE * SIZE <= 1 << 15, "Chunk::SIZE^3 must fit in 15 bits");
 
     /// Create a new, unloaded(all blocks set to air), chunk at the given
     /// chunk position(not that this *isn't* the position of its corner block).
     pub fn new(pos: Vec3<i32>, registry: &Arc<block::Registry>) -> Self
     {
+        // Evaluate the assertion for this `SIZE`
+        let () = Self::VALID;
+
         Self
         {
             pos,
-            blocks: Box::new([block::Packed::zeroed(); Chunk::VOLUME]),
+            blocks: vec![block::Packed::zeroed(); Self::STORAGE].into_boxed_slice(),
             addr_blocks: Default::default(),
             registry: Arc::clone(registry),
+            version: 0,
         }
     }
 
-    /// Get this chunk's position, where 1 unit = 32 blocks
+    /// Get this chunk's position, where 1 unit = `SIZE` blocks
     pub fn pos(&self) -> Vec3<i32>
     {
         self.pos
     }
+
+    /// Get the registry of the block types this chunk holds, ie. to resolve their
+    /// numeric [block::Id]s while meshing
+    pub fn registry(&self) -> &Arc<block::Registry>
+    {
+        &self.registry
+    }
+
+    /// Get this chunk's version, which starts at `0` and goes up with every mutation
+    /// to its blocks, but never with reads. Consumers(ie. meshing, lighting or the
+    /// network) can each remember the last version they processed, and only process
+    /// the chunk again once it's higher. Mutable borrows count as mutations, whether
+    /// or not the block is actually changed.
+    pub fn version(&self) -> u64
+    {
+        self.version
+    }
+
+    /// Release the memory held by this chunk's storage of `Ptr` blocks beyond what
+    /// its remaining `Ptr` blocks need, ie. after a structure full of chests was
+    /// torn down. This moves `Ptr` blocks into the gaps left by removed ones, but
+    /// doesn't change any block so it doesn't go towards [Chunk::version].
+    pub fn shrink_to_fit(&mut self)
+    {
+        // Cell of the `Ptr` block in every slot
+        let mut cells = vec![0; self.addr_blocks.capacity()];
+
+        for (i, packed) in self.blocks.iter().enumerate()
+        {
+            if packed.tag() == block::packed::Repr::Ptr
+            {
+                // SAFETY:
+                // Just checked the tag
+                cells[unsafe { packed.ptr }.slot()] = i;
+            }
+        }
+
+        let blocks = &mut self.blocks;
+
+        // Also shrinks the slab's capacity once it's compact
+        self.addr_blocks.compact(|_, from, to|
+        {
+            blocks[cells[from]] = block::Packed::from_ptr(to);
+            true
+        });
+    }
+}
+
+// Chunks are generated on worker threads, and then shared behind locks. `Ptr`
+// blocks live in their chunk's own `addr_blocks`, so this holds as long as
+// `block::Object` stays `Send + Sync`.
+const _: fn() = ||
+{
+    fn assert_thread_safe<T: Send + Sync>() { }
+
+    assert_thread_safe::<Chunk>();
+};
+
+impl<const SIZE: usize> std::fmt::Debug for Chunk<SIZE>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.debug_struct("Chunk")
+            .field("pos", &self.pos)
+            .field("size", &SIZE)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Two chunks are equal if they're at the same position and have the same packed
+/// cells, except for `Ptr` blocks which are compared by their serialized state
+/// regardless of where they're stored.
+impl<const SIZE: usize> PartialEq for Chunk<SIZE>
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        use block::packed::Repr;
+
+        self.pos == other.pos && (0..Self::VOLUME).all(|i|
+        {
+            let (a, b) = (self.blocks[i], other.blocks[i]);
+
+            match (a.tag(), b.tag())
+            {
+                (Repr::Val, Repr::Val) => a == b,
+                // SAFETY:
+                // `i` is within both chunks' `blocks`
+                (Repr::Ptr, Repr::Ptr) => unsafe
+                {
+                    self.get_unchecked_flat(i).dyn_eq(other.get_unchecked_flat(i))
+                },
+                _ => false,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test
+{
+    use std::sync::Arc;
+
+    use crate::world::{ Chunk, Block, block, blockdef };
+    use crate::vanilla::blocks::*;
+    use crate::item::ItemStack;
+    use crate::math::{ Direction, Vec3, vec3 };
+
+    fn registry() -> Arc<block::Registry>
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+
+        Arc::new(registry)
+    }
+
+    #[test]
+    fn sized_16()
+    {
+        let mut chunk = Chunk::<16>::new(vec3(0, 0, 0), &registry());
+
+        assert_eq!(Chunk::<16>::SIZE, 16);
+        assert_eq!(Chunk::<16>::VOLUME, 4096);
+        assert!(Chunk::<16>::in_bounds(vec3(15, 15, 15)));
+        assert!(!Chunk::<16>::in_bounds(vec3(16, 0, 0)));
+
+        chunk.set(vec3(15, 15, 15), BlockWoodenPlanks { variant: WoodVariant::Oak });
+        chunk.set(vec3(16, 0, 0), BlockWoodenPlanks { variant: WoodVariant::Oak });
+
+        assert!(chunk.get(vec3(15, 15, 15)).unwrap().is::<BlockWoodenPlanks>());
+        assert!(chunk.get(vec3(16, 0, 0)).is_none());
+        assert!(chunk.get(vec3(0, 0, 16)).is_none());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "out of bounds")]
+    fn get_unchecked_out_of_bounds()
+    {
+        let chunk = Chunk::<16>::new(vec3(0, 0, 0), &registry());
+
+        // Flattens to a valid index, so this would've read the wrong block
+        let _ = unsafe { chunk.get_unchecked(vec3(16, 0, 0)) };
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "out of bounds")]
+    fn set_unchecked_out_of_bounds()
+    {
+        let mut chunk = Chunk::<16>::new(vec3(0, 0, 0), &registry());
+
+        unsafe { chunk.set_unchecked(vec3(0, 3, 16), BlockWoodenPlanks { variant: WoodVariant::Oak }) };
+    }
+
+    #[test]
+    fn registry_of()
+    {
+        let registry = registry();
+        let chunk = Chunk::<16>::new(vec3(0, 0, 0), &registry);
+
+        assert!(Arc::ptr_eq(chunk.registry(), &registry));
+        assert!(chunk.registry().id::<BlockWoodenPlanks>().is_some());
+        assert!(chunk.registry().id_of("wooden_planks").is_some());
+        assert!(chunk.registry().id::<BlockStone>().is_none());
+    }
+
+    #[test]
+    fn exposed_faces()
+    {
+        let mut chunk = Chunk::<16>::new(vec3(0, 0, 0), &registry());
+
+        let faces = |chunk: &Chunk<16>|
+        {
+            let mut faces = Vec::new();
+            chunk.visit_exposed_faces(|pos, dir, block|
+            {
+                assert!(block.is::<BlockWoodenPlanks>());
+                faces.push((pos, dir));
+            });
+            faces
+        };
+
+        assert!(faces(&chunk).is_empty());
+
+        // lone cube
+        chunk.set(vec3(4, 5, 6), BlockWoodenPlanks { variant: WoodVariant::Oak });
+
+        let mut dirs = faces(&chunk)
+            .into_iter()
+            .map(|(pos, dir)| { assert_eq!(pos, vec3(4, 5, 6)); dir as usize })
+            .collect::<Vec<_>>();
+        dirs.sort_unstable();
+        assert_eq!(dirs, vec![0, 1, 2, 3, 4, 5]);
+
+        // adjacent cubes hide the face between them
+        chunk.set(vec3(5, 5, 6), BlockWoodenPlanks { variant: WoodVariant::Oak });
+
+        let faces = faces(&chunk);
+        assert_eq!(faces.len(), 10);
+        assert!(!faces.contains(&(vec3(4, 5, 6), Direction::East)));
+        assert!(!faces.contains(&(vec3(5, 5, 6), Direction::West)));
+
+        // chunk edges count as exposed
+        let mut chunk = Chunk::<16>::new(vec3(0, 0, 0), &registry());
+        chunk.set(vec3(0, 0, 0), BlockWoodenPlanks { variant: WoodVariant::Oak });
+
+        let mut count = 0;
+        chunk.visit_exposed_faces(|_, _, _| count += 1);
+        assert_eq!(count, 6);
+    }
+
+    #[test]
+    fn surface_cells()
+    {
+        let mut chunk = Chunk::<16>::new(vec3(0, 0, 0), &registry());
+
+        assert_eq!(chunk.surface_cells().count(), 0);
+
+        // solid 3x3x3 cube, all but its center exposed
+        chunk.fill_region(vec3(4, 4, 4), vec3(6, 6, 6), BlockWoodenPlanks { variant: WoodVariant::Oak });
+
+        let cells = chunk.surface_cells().collect::<Vec<_>>();
+
+        assert_eq!(cells.len(), 26);
+        assert!(!cells.contains(&vec3(5, 5, 5)));
+        assert!(cells.iter().all(|pos| chunk[*pos].is::<BlockWoodenPlanks>()));
+
+        // chunk edges count as exposed
+        chunk.fill_region(vec3(0, 0, 0), vec3(15, 1, 15), BlockWoodenPlanks { variant: WoodVariant::Oak });
+
+        assert_eq!(chunk.surface_cells().filter(|pos| pos.y <= 1).count(), 16 * 16 * 2);
+    }
+
+    #[test]
+    fn neighbors()
+    {
+        let mut chunk = Chunk::<16>::new(vec3(0, 0, 0), &registry());
+
+        chunk.set(vec3(0, 1, 0), BlockWoodenPlanks { variant: WoodVariant::Oak });
+
+        let neighbors = chunk.neighbors(vec3(0, 0, 0));
+
+        assert!(neighbors[Direction::North as usize].is_none());
+        assert!(neighbors[Direction::West as usize].is_none());
+        assert!(neighbors[Direction::Down as usize].is_none());
+        assert!(neighbors[Direction::South as usize].unwrap().is::<BlockAir>());
+        assert!(neighbors[Direction::East as usize].unwrap().is::<BlockAir>());
+        assert!(neighbors[Direction::Up as usize].unwrap().is::<BlockWoodenPlanks>());
+    }
+
+    #[test]
+    fn iter_16()
+    {
+        let mut chunk = Chunk::<16>::new(vec3(0, 0, 0), &registry());
+
+        chunk.set(vec3(3, 9, 14), BlockWoodenPlanks { variant: WoodVariant::Acacia });
+
+        assert_eq!(chunk.iter().count(), 4096);
+        assert_eq!(chunk.iter().last().unwrap().0, vec3(15, 15, 15));
+
+        let planks = chunk
+            .iter()
+            .filter(|(_, block)| block.is::<BlockWoodenPlanks>())
+            .map(|(pos, _)| pos)
+            .collect::<Vec<_>>();
+        assert_eq!(planks, vec![vec3(3, 9, 14)]);
+    }
+
+    #[test]
+    fn morton_iter()
+    {
+        let mut seen = vec![false; Chunk::<32>::VOLUME];
+
+        for pos in Chunk::<32>::morton_iter()
+        {
+            let i = Chunk::<32>::flatten_idx(pos);
+
+            assert!(!seen[i], "{:?} visited twice", pos);
+            seen[i] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+
+        let first = Chunk::<32>::morton_iter().take(9).collect::<Vec<_>>();
+        assert_eq!(first, vec![
+            vec3(0, 0, 0), vec3(1, 0, 0), vec3(0, 1, 0), vec3(1, 1, 0),
+            vec3(0, 0, 1), vec3(1, 0, 1), vec3(0, 1, 1), vec3(1, 1, 1),
+            vec3(2, 0, 0),
+        ]);
+
+        // non power-of-two
+        assert_eq!(Chunk::<20>::morton_iter().count(), Chunk::<20>::VOLUME);
+        assert!(Chunk::<20>::morton_iter().all(Chunk::<20>::in_bounds));
+    }
+
+    /// Run with and without the `morton-chunks` feature, which must agree
+    #[test]
+    fn storage_layout()
+    {
+        // Non power-of-two, so Morton storage has padding
+        let mut chunk = Chunk::<20>::new(vec3(0, 0, 0), &registry());
+        let planks = |pos: Vec3<usize>| (pos.x * 7 + pos.y * 3 + pos.z).is_multiple_of(5);
+
+        for pos in Chunk::<20>::morton_iter().filter(|&pos| planks(pos))
+        {
+            let variant = if pos.y % 2 == 0 { WoodVariant::Oak } else { WoodVariant::Birch };
+
+            chunk.set(pos, BlockWoodenPlanks { variant });
+        }
+
+        for (i, (pos, block)) in chunk.iter().enumerate()
+        {
+            assert_eq!(pos, vec3(i % 20, (i / 20) % 20, i / (20 * 20)));
+            assert_eq!(block.is::<BlockWoodenPlanks>(), planks(pos));
+            assert_eq!(block, chunk.get(pos).unwrap());
+        }
+        assert_eq!(chunk.iter().count(), Chunk::<20>::VOLUME);
+        assert_eq!(chunk.volume_of::<BlockAir>() + chunk.volume_of::<BlockWoodenPlanks>(), Chunk::<20>::VOLUME);
+        assert_eq!(chunk.bounding_box_of::<BlockWoodenPlanks>(), Some((vec3(0, 0, 0), vec3(19, 19, 19))));
+
+        // Serialized in canonical order either way
+        let bytes = chunk.to_bytes();
+        let loaded = Chunk::<20>::from_bytes(vec3(0, 0, 0), &chunk.registry, &bytes).unwrap();
+
+        assert_eq!(loaded, chunk);
+        assert_eq!(chunk.checksum(), 2812161965455972453);
+    }
+
+    /// `cargo test --release -- --ignored --nocapture morton_bench`
+    #[test]
+    #[ignore]
+    fn morton_bench()
+    {
+        use std::time::Instant;
+
+        // Sum of each cell's six neighbors, as would a lighting pass
+        fn neighbor_sum(cells: &[u32], order: impl Iterator<Item = Vec3<usize>>) -> u64
+        {
+            let mut sum = 0;
+            for pos in order
+            {
+                for dir in Direction::ALL.iter()
+                {
+                    let n = pos.map(|n| n as i32) + dir.offset();
+
+                    if n.reduce_min() >= 0 && n.reduce_max() < 32
+                    {
+                        sum += cells[Chunk::<32>::flatten_idx(n.map(|n| n as usize))] as u64;
+                    }
+                }
+            }
+            sum
+        }
+
+        let cells = (0..Chunk::<32>::VOLUME as u32).collect::<Vec<_>>();
+        let linear = || (0..Chunk::<32>::VOLUME).map(|i| vec3(i % 32, (i / 32) % 32, i / (32 * 32)));
+
+        let mut sums = Vec::new();
+        for (name, morton) in [("linear", false), ("morton", true)].iter()
+        {
+            let start = Instant::now();
+
+            for _ in 0..100
+            {
+                sums.push(if *morton
+                {
+                    neighbor_sum(&cells, Chunk::<32>::morton_iter())
+                }
+                else
+                {
+                    neighbor_sum(&cells, linear())
+                });
+            }
+            println!("{}: {:?} / pass", name, start.elapsed() / 100);
+        }
+        assert!(sums.windows(2).all(|w| w[0] == w[1]));
+    }
+
+    #[test]
+    fn set_boxed()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+        registry.register::<BlockWoodenSlab>();
+        registry.register::<BlockChest>();
+
+        let mut chunk = Chunk::<16>::new(vec3(0, 0, 0), &Arc::new(registry));
+
+        let chest = BlockChest { name: Some("loot".into()), ..BlockChest::new(Direction::East) };
+        let slab = BlockWoodenSlab { facing: Direction::Up, variant: WoodVariant::Birch };
+        let planks = BlockWoodenPlanks { variant: WoodVariant::Acacia };
+
+        let boxed: Vec<Box<dyn block::Object>> = vec![Box::new(chest.clone()), Box::new(slab), Box::new(planks)];
+        for (x, block) in boxed.into_iter().enumerate()
+        {
+            chunk.set_boxed(vec3(x, 0, 0), block);
+        }
+
+        assert_eq!(*chunk[(0, 0, 0)].cast::<BlockChest>().unwrap(), chest);
+        assert_eq!(*chunk[(1, 0, 0)].cast::<BlockWoodenSlab>().unwrap(), slab);
+        assert_eq!(*chunk[(2, 0, 0)].cast::<BlockWoodenPlanks>().unwrap(), planks);
+
+        // replacing a `Ptr` block frees it
+        chunk.set_boxed(vec3(0, 0, 0), Box::new(planks));
+        assert_eq!(*chunk[(0, 0, 0)].cast::<BlockWoodenPlanks>().unwrap(), planks);
+
+        // out of bounds, or unregistered
+        chunk.set_boxed(vec3(16, 0, 0), Box::new(planks));
+        chunk.set_boxed(vec3(3, 0, 0), Box::new(BlockNote { text: "hi".into() }));
+        assert!(chunk[(3, 0, 0)].is::<BlockAir>());
+
+        let mut expected = Chunk::<16>::new(vec3(0, 0, 0), &chunk.registry);
+        expected.set(vec3(0, 0, 0), planks);
+        expected.set(vec3(1, 0, 0), slab);
+        expected.set(vec3(2, 0, 0), planks);
+        assert_eq!(chunk, expected);
+    }
+
+    #[test]
+    fn fill_region()
+    {
+        let mut chunk = Chunk::<16>::new(vec3(0, 0, 0), &registry());
+        let oak = BlockWoodenPlanks { variant: WoodVariant::Oak };
+
+        // corners in any order
+        assert_eq!(chunk.fill_region(vec3(3, 5, 2), vec3(1, 2, 2), oak), 3 * 4);
+        // clipped to the chunk's bounds
+        assert_eq!(chunk.fill_region(vec3(14, 0, 0), vec3(20, 0, 1), oak), 2 * 2);
+        assert_eq!(chunk.fill_region(vec3(16, 0, 0), vec3(20, 0, 1), oak), 0);
+        // single block
+        assert_eq!(chunk.fill_region(vec3(9, 9, 9), vec3(9, 9, 9), oak), 1);
+
+        let filled = chunk
+            .iter()
+            .filter(|(_, block)| block.is::<BlockWoodenPlanks>())
+            .count();
+        assert_eq!(filled, 12 + 4 + 1);
+        assert!(chunk[vec3(2, 3, 2)].is::<BlockWoodenPlanks>());
+        assert!(chunk[vec3(2, 3, 3)].is::<BlockAir>());
+        assert!(chunk[vec3(15, 0, 1)].is::<BlockWoodenPlanks>());
+    }
+
+    #[test]
+    fn bounding_box_of()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+        registry.register::<BlockWoodenSlab>();
+        registry.register::<BlockChest>();
+
+        let mut chunk = Chunk::<16>::new(vec3(0, 0, 0), &Arc::new(registry));
+        let slab = BlockWoodenSlab { facing: Direction::Down, variant: WoodVariant::Oak };
+
+        assert_eq!(chunk.bounding_box_of::<BlockWoodenSlab>(), None);
+        assert_eq!(chunk.volume_of::<BlockWoodenSlab>(), 0);
+
+        chunk.set(vec3(15, 14, 15), slab);
+        chunk.set(vec3(13, 15, 15), slab);
+        chunk.set(vec3(15, 15, 12), BlockWoodenSlab { variant: WoodVariant::Spruce, ..slab });
+        chunk.set(vec3(0, 0, 0), BlockWoodenPlanks { variant: WoodVariant::Oak });
+        chunk.set(vec3(3, 9, 1), BlockChest::new(Direction::North));
+
+        assert_eq!(chunk.bounding_box_of::<BlockWoodenSlab>(), Some((vec3(13, 14, 12), vec3(15, 15, 15))));
+        assert_eq!(chunk.volume_of::<BlockWoodenSlab>(), 3);
+
+        // `Ptr` blocks
+        assert_eq!(chunk.bounding_box_of::<BlockChest>(), Some((vec3(3, 9, 1), vec3(3, 9, 1))));
+        assert_eq!(chunk.volume_of::<BlockChest>(), 1);
+
+        assert_eq!(chunk.volume_of::<BlockAir>(), 16 * 16 * 16 - 5);
+        // not registered
+        assert_eq!(chunk.bounding_box_of::<BlockStone>(), None);
+    }
+
+    #[test]
+    fn version()
+    {
+        let mut chunk = Chunk::<32>::new(vec3(0, 0, 0), &registry());
+        let oak = BlockWoodenPlanks { variant: WoodVariant::Oak };
+
+        assert_eq!(chunk.version(), 0);
+
+        // reads
+        let _ = chunk.get(vec3(1, 2, 3));
+        let _ = chunk.info(vec3(1, 2, 3));
+        let _ = chunk[(1, 2, 3)].name();
+        let _ = chunk.iter().count();
+        let _ = chunk.to_bytes();
+        assert_eq!(chunk.version(), 0);
+
+        // mutations
+        chunk.set(vec3(1, 2, 3), oak);
+        assert_eq!(chunk.version(), 1);
+        chunk.set_boxed(vec3(1, 2, 4), Box::new(oak));
+        assert_eq!(chunk.version(), 2);
+        chunk.take(vec3(1, 2, 4));
+        assert_eq!(chunk.version(), 3);
+        chunk[(1, 2, 3)].cast_mut::<BlockWoodenPlanks>().unwrap().variant = WoodVariant::Birch;
+        assert_eq!(chunk.version(), 4);
+        assert_eq!(chunk.fill_region(vec3(0, 0, 0), vec3(1, 1, 1), oak), 8);
+        assert_eq!(chunk.version(), 12);
+
+        // out of bounds does nothing
+        chunk.set(vec3(32, 0, 0), oak);
+        assert_eq!(chunk.version(), 12);
+
+        // loading starts over
+        let loaded = Chunk::<32>::from_bytes(vec3(0, 0, 0), &registry(), &chunk.to_bytes()).unwrap();
+        assert_eq!(loaded.version(), 0);
+    }
+
+    #[test]
+    fn take()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+        registry.register::<BlockChest>();
+
+        let registry = Arc::new(registry);
+        let mut chunk = Chunk::<16>::new(vec3(0, 0, 0), &registry);
+        let planks = BlockWoodenPlanks { variant: WoodVariant::Birch };
+        let chest = BlockChest::new(Direction::East);
+
+        chunk.set(vec3(1, 2, 3), planks);
+        chunk.set(vec3(4, 5, 6), chest.clone());
+        chunk.set(vec3(7, 8, 9), chest.clone());
+
+        // `Val` blocks
+        assert_eq!(chunk[(1, 2, 3)].cast_mut::<BlockWoodenPlanks>().unwrap().take(), Some(planks));
+        assert!(chunk[(1, 2, 3)].is::<BlockAir>());
+        chunk.set(vec3(1, 2, 3), planks);
+        assert_eq!(chunk.take_as::<BlockChest>(vec3(1, 2, 3)), None);
+        assert_eq!(chunk.take_as::<BlockWoodenPlanks>(vec3(1, 2, 3)), Some(planks));
+        assert!(chunk[(1, 2, 3)].is::<BlockAir>());
+
+        // `Ptr` blocks can't be taken through a reference
+        assert_eq!(chunk[(4, 5, 6)].cast_mut::<BlockChest>().unwrap().take(), None);
+        assert_eq!(chunk.take_as::<BlockChest>(vec3(4, 5, 6)), Some(chest.clone()));
+        assert!(chunk[(4, 5, 6)].is::<BlockAir>());
+        assert_eq!(chunk.addr_blocks.len(), 1);
+
+        let taken = chunk.take(vec3(7, 8, 9)).unwrap();
+        assert_eq!(&*taken, &chest as &dyn block::Object);
+        assert_eq!(chunk.addr_blocks.len(), 0);
+        assert_eq!(chunk.volume_of::<BlockAir>(), 16 * 16 * 16);
+        assert!(chunk.take(vec3(16, 0, 0)).is_none());
+    }
+
+    #[test]
+    fn shrink_to_fit()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockChest>();
+
+        let mut chunk = Chunk::<16>::new(vec3(0, 0, 0), &Arc::new(registry));
+        let chest = |n: usize| BlockChest::new(Direction::HORIZONTAL[n % 4]);
+
+        for n in 0..256
+        {
+            chunk.set(vec3(n % 16, n / 16, 0), chest(n));
+        }
+        let grown = chunk.addr_blocks.capacity();
+
+        // Keep a few, scattered throughout the slab
+        for n in 0..256
+        {
+            if n % 50 != 7
+            {
+                chunk.take(vec3(n % 16, n / 16, 0));
+            }
+        }
+        let version = chunk.version();
+
+        chunk.shrink_to_fit();
+        assert!(chunk.addr_blocks.capacity() < grown);
+        assert_eq!(chunk.addr_blocks.len(), 5);
+        assert_eq!(chunk.version(), version);
+        for n in 0..256
+        {
+            match n % 50 == 7
+            {
+                true => assert_eq!(*chunk[(n % 16, n / 16, 0)].cast::<BlockChest>().unwrap(), chest(n)),
+                false => assert!(chunk[(n % 16, n / 16, 0)].is::<BlockAir>()),
+            }
+        }
+
+        // Still usable afterwards
+        chunk.set(vec3(0, 0, 1), chest(1));
+        assert_eq!(*chunk[(0, 0, 1)].cast::<BlockChest>().unwrap(), chest(1));
+        assert_eq!(chunk.addr_blocks.len(), 6);
+    }
+
+    blockdef!
+    {
+        id: "note",
+        name: "Note",
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct BlockNote
+        {
+            #[prop(!)]
+            pub text: String,
+        }
+
+        fn serialize(&self, out: &mut Vec<u8>)
+        {
+            out.extend_from_slice(self.text.as_bytes());
+        }
+
+        fn deserialize(bytes: &[u8]) -> Option<Self>
+        {
+            String::from_utf8(bytes.to_vec()).ok().map(|text| Self { text })
+        }
+    }
+
+    blockdef!
+    {
+        id: "scribble",
+        name: "Scribble",
+
+        // Not saved
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct BlockScribble
+        {
+            #[prop(!)]
+            pub text: String,
+        }
+    }
+
+    #[test]
+    fn bytes()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+        registry.register::<BlockNote>();
+        registry.register::<BlockScribble>();
+
+        let registry = Arc::new(registry);
+        let mut chunk = Chunk::<16>::new(vec3(1, -2, 3), &registry);
+
+        chunk.set(vec3(0, 0, 0), BlockWoodenPlanks { variant: WoodVariant::Birch });
+        chunk.set(vec3(15, 2, 7), BlockNote { text: "hello".into() });
+        chunk.set(vec3(4, 4, 4), BlockNote { text: "world".into() });
+
+        let bytes = chunk.to_bytes();
+        let loaded = Chunk::<16>::from_bytes(vec3(1, -2, 3), &registry, &bytes).unwrap();
+
+        assert_eq!(loaded, chunk);
+        assert_eq!(loaded[(15, 2, 7)].cast::<BlockNote>().unwrap().text, "hello");
+
+        // numeric IDs don't matter
+        let mut other = block::Registry::default();
+
+        other.register::<BlockNote>();
+        other.register::<BlockWoodenPlanks>();
+
+        let loaded = Chunk::<16>::from_bytes(vec3(1, -2, 3), &Arc::new(other), &bytes).unwrap();
+
+        assert_eq!(loaded[(0, 0, 0)].cast::<BlockWoodenPlanks>().unwrap().variant, WoodVariant::Birch);
+        assert_eq!(loaded[(4, 4, 4)].cast::<BlockNote>().unwrap().text, "world");
+
+        // blocks that can't be restored become air
+        chunk.set(vec3(1, 1, 1), BlockScribble { text: "lost".into() });
+
+        let loaded = Chunk::<16>::from_bytes(vec3(1, -2, 3), &registry, &chunk.to_bytes()).unwrap();
+
+        assert!(loaded[(1, 1, 1)].is::<BlockAir>());
+        assert!(loaded[(4, 4, 4)].is::<BlockNote>());
+
+        // malformed
+        assert!(Chunk::<16>::from_bytes(vec3(1, -2, 3), &registry, &bytes[..bytes.len() - 1]).is_none());
+        assert!(Chunk::<32>::from_bytes(vec3(1, -2, 3), &registry, &bytes).is_none());
+        assert!(Chunk::<16>::from_bytes(vec3(1, -2, 3), &registry, &[]).is_none());
+    }
+
+    #[test]
+    fn checksum()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+        registry.register::<BlockNote>();
+
+        let registry = Arc::new(registry);
+        let mut a = Chunk::<16>::new(vec3(0, 0, 0), &registry);
+        let mut b = Chunk::<16>::new(vec3(0, 0, 0), &registry);
+
+        a.set(vec3(0, 0, 0), BlockWoodenPlanks { variant: WoodVariant::Birch });
+        a.set(vec3(1, 0, 0), BlockNote { text: "hello".into() });
+        a.set(vec3(2, 0, 0), BlockNote { text: "world".into() });
+
+        // same blocks, in different `Ptr` slots
+        b.set(vec3(2, 0, 0), BlockNote { text: "world".into() });
+        b.set(vec3(1, 0, 0), BlockNote { text: "hello".into() });
+        b.set(vec3(0, 0, 0), BlockWoodenPlanks { variant: WoodVariant::Birch });
+
+        assert_eq!(a.checksum(), b.checksum());
+
+        let bytes = a.to_bytes();
+        let loaded = Chunk::<16>::from_bytes(vec3(0, 0, 0), &registry, &bytes).unwrap();
+
+        assert_eq!(loaded.checksum(), a.checksum());
+
+        b.set(vec3(1, 0, 0), BlockNote { text: "jello".into() });
+        assert_ne!(a.checksum(), b.checksum());
+        b.set(vec3(1, 0, 0), BlockNote { text: "hello".into() });
+        b.set(vec3(0, 0, 0), BlockWoodenPlanks { variant: WoodVariant::Oak });
+        assert_ne!(a.checksum(), b.checksum());
+
+        // corrupted, but still well-formed
+        let at = bytes.windows(5).position(|w| w == b"hello").unwrap();
+        let mut corrupt = bytes.clone();
+
+        corrupt[at] = b'j';
+        assert!(Chunk::<16>::from_bytes(vec3(0, 0, 0), &registry, &corrupt).is_none());
+    }
+
+    #[test]
+    fn chest()
+    {
+        let mut registry = block::Registry::default();
+
+        register_all(&mut registry);
+
+        let registry = Arc::new(registry);
+        let mut chunk = Chunk::<16>::new(vec3(0, 0, 0), &registry);
+        let mut chest = BlockChest { name: Some("Lööt".into()), ..BlockChest::new(Direction::South) };
+
+        chest.contents.insert(ItemStack::new("diamond", 3));
+        chest.contents.insert(ItemStack::new("stick", 64));
+        chest.contents.insert(ItemStack::new("stick", 36));
+        chunk.set(vec3(1, 2, 3), chest.clone());
+        chunk.set(vec3(3, 2, 1), BlockChest::new(Direction::East));
+
+        let loaded = Chunk::<16>::from_bytes(vec3(0, 0, 0), &registry, &chunk.to_bytes()).unwrap();
+
+        assert_eq!(*loaded[(1, 2, 3)].cast::<BlockChest>().unwrap(), chest);
+        assert_eq!(loaded[(1, 2, 3)].cast::<BlockChest>().unwrap().contents.get(2), Some(&ItemStack::new("stick", 36)));
+        assert_eq!(*loaded[(3, 2, 1)].cast::<BlockChest>().unwrap(), BlockChest::new(Direction::East));
+        assert_eq!(loaded, chunk);
+
+        // malformed
+        let mut bytes = Vec::new();
+
+        block::Object::serialize(&chest, &mut bytes);
+        assert_eq!(BlockChest::deserialize(&bytes[..bytes.len() - 1]), None);
+        assert_eq!(BlockChest::deserialize(&[&bytes[..], &[0]].concat()), None);
+    }
+
+    #[test]
+    fn copy_cell()
+    {
+        let mut registry = block::Registry::default();
+
+        register_all(&mut registry);
+        registry.register::<BlockNote>();
+        registry.register::<BlockScribble>();
+
+        let registry = Arc::new(registry);
+        let mut chunk = Chunk::<16>::new(vec3(0, 0, 0), &registry);
+        let mut chest = BlockChest::new(Direction::North);
+
+        chest.contents.insert(ItemStack::new("diamond", 3));
+        chunk.set(vec3(1, 2, 3), chest.clone());
+        chunk.set(vec3(4, 4, 4), BlockWoodenPlanks { variant: WoodVariant::Spruce });
+        chunk.set(vec3(5, 5, 5), BlockNote { text: "replaced".into() });
+
+        let version = chunk.version();
+
+        assert!(chunk.copy_cell(vec3(1, 2, 3), vec3(5, 5, 5)));
+        assert!(chunk.version() > version);
+        assert_eq!(chunk.addr_blocks.len(), 2);
+        assert_eq!(*chunk[(5, 5, 5)].cast::<BlockChest>().unwrap(), chest);
+
+        // independent copies
+        chunk[(5, 5, 5)].cast_mut::<BlockChest>().unwrap().contents.insert(ItemStack::new("stick", 1));
+
+        assert_eq!(*chunk[(1, 2, 3)].cast::<BlockChest>().unwrap(), chest);
+        assert_ne!(*chunk[(5, 5, 5)].cast::<BlockChest>().unwrap(), chest);
+
+        // `Val` blocks, over a `Ptr` block
+        assert!(chunk.copy_cell(vec3(4, 4, 4), vec3(5, 5, 5)));
+        assert_eq!(chunk.addr_blocks.len(), 1);
+        assert_eq!(chunk[(5, 5, 5)].cast::<BlockWoodenPlanks>().unwrap().variant, WoodVariant::Spruce);
+
+        // no-ops
+        let version = chunk.version();
+
+        assert!(chunk.copy_cell(vec3(1, 2, 3), vec3(1, 2, 3)));
+        assert!(!chunk.copy_cell(vec3(1, 2, 3), vec3(16, 0, 0)));
+        assert!(!chunk.copy_cell(vec3(0, 16, 0), vec3(1, 2, 3)));
+        assert_eq!(chunk.version(), version);
+        assert_eq!(*chunk[(1, 2, 3)].cast::<BlockChest>().unwrap(), chest);
+
+        // can't be round-tripped
+        chunk.set(vec3(6, 6, 6), BlockScribble { text: "lost".into() });
+
+        assert!(!chunk.copy_cell(vec3(6, 6, 6), vec3(4, 4, 4)));
+        assert!(chunk[(4, 4, 4)].is::<BlockWoodenPlanks>());
+    }
+
+    #[test]
+    fn sign()
+    {
+        let mut registry = block::Registry::default();
+
+        register_all(&mut registry);
+
+        let registry = Arc::new(registry);
+        let mut chunk = Chunk::<16>::new(vec3(0, 0, 0), &registry);
+        let mut sign = BlockSign { facing: Direction::West, lines: Default::default() };
+
+        assert_eq!(sign.name(), "Sign");
+        sign.set_line(0, "Welcome");
+        sign.set_line(4, "out of bounds");
+        assert_eq!(sign.name(), "Sign (Welcome)");
+
+        chunk.set(vec3(3, 4, 5), sign);
+
+        if let Some(mut sign) = chunk[(3, 4, 5)].cast_mut::<BlockSign>()
+        {
+            sign.set_line(2, "ünïcödé");
+            sign.set_line(3, &"long".repeat(20));
+        }
+
+        let loaded = Chunk::<16>::from_bytes(vec3(0, 0, 0), &registry, &chunk.to_bytes()).unwrap();
+        let sign = loaded[(3, 4, 5)].cast::<BlockSign>().unwrap();
+
+        assert_eq!(sign.facing, Direction::West);
+        assert_eq!(sign.lines[0], "Welcome");
+        assert_eq!(sign.lines[1], "");
+        assert_eq!(sign.lines[2], "ünïcödé");
+        assert_eq!(sign.lines[3], "long".repeat(20)[..BlockSign::MAX_LINE_LEN]);
+        assert_eq!(loaded, chunk);
+
+        // malformed
+        let mut bytes = Vec::new();
+
+        block::Object::serialize(&*sign, &mut bytes);
+        assert_eq!(BlockSign::deserialize(&bytes).as_ref(), Some(&*sign));
+        assert_eq!(BlockSign::deserialize(&bytes[..bytes.len() - 1]), None);
+        assert_eq!(BlockSign::deserialize(&[&bytes[..], &[0]].concat()), None);
+        assert_eq!(BlockSign::deserialize(&[&[4], &bytes[1..]].concat()), None);
+    }
 }
\ No newline at end of file
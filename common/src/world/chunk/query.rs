@@ -0,0 +1,48 @@
+use crate::world::block::{ Block, self };
+use crate::world::Chunk;
+use crate::math::Vec3;
+
+impl<const SIZE: usize> Chunk<SIZE>
+{
+    /// Count the blocks of type `T` in this chunk
+    pub fn volume_of<T: Block>(&self) -> usize
+    {
+        self.positions_of::<T>().count()
+    }
+
+    /// Get the minimum and maximum corners(inclusive), in chunk-space, of the smallest
+    /// box containing every block of type `T` in this chunk, or `None` if there's none.
+    pub fn bounding_box_of<T: Block>(&self) -> Option<(Vec3<usize>, Vec3<usize>)>
+    {
+        self.positions_of::<T>().fold(None, |bounds, pos| match bounds
+        {
+            Some((min, max)) => Some((Vec3::partial_min(min, pos), Vec3::partial_max(max, pos))),
+            None => Some((pos, pos)),
+        })
+    }
+
+    /// Iterate over the positions, in chunk-space, of every block of type `T` in this
+    /// chunk in canonical order. This only looks at the packed blocks, except for the
+    /// `Ptr` blocks when `T` is one too.
+    fn positions_of<T: Block>(&self) -> impl Iterator<Item = Vec3<usize>> + '_
+    {
+        // Blocks that aren't registered can't be in the chunk
+        let id = self.registry.id::<T>();
+        let ptr = matches!(T::REPR, block::Repr::Ptr);
+
+        (0..Self::VOLUME)
+            .map(Self::canonical_pos)
+            .filter(move |&pos|
+            {
+                let packed = self.blocks[Self::flatten_idx(pos)];
+
+                id.is_some() && match packed.tag()
+                {
+                    // SAFETY:
+                    // Just checked the tag
+                    block::packed::Repr::Val => !ptr && Some(unsafe { packed.val }.id()) == id,
+                    block::packed::Repr::Ptr => ptr && self.addr_blocks[unsafe { packed.ptr }.slot()].is::<T>(),
+                }
+            })
+    }
+}
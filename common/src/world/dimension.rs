@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::world::{ World, ChunkGenerator, block };
+
+/// Identifier of a dimension in [Dimensions], ie. [DimensionId::OVERWORLD]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DimensionId(pub &'static str);
+
+/// Independent [World]s, each with their own chunks and terrain, sharing a single
+/// block registry
+pub struct Dimensions
+{
+    /// Registry shared by every dimension
+    registry: Arc<block::Registry>,
+    /// Every dimension by its identifier
+    worlds: HashMap<DimensionId, World>,
+}
+
+impl DimensionId
+{
+    /// The dimension players start in
+    pub const OVERWORLD: Self = Self("overworld");
+}
+
+impl Dimensions
+{
+    /// Create an empty set of dimensions, which will share `registry`
+    pub fn new(registry: block::Registry) -> Self
+    {
+        Self
+        {
+            registry: Arc::new(registry),
+            worlds: HashMap::default(),
+        }
+    }
+
+    /// Add the dimension `id`, whose terrain is generated by `generator`, replacing
+    /// any dimension with the same identifier. Returns the new dimension's world.
+    pub fn insert(&mut self, id: DimensionId, generator: impl ChunkGenerator + 'static) -> &mut World
+    {
+        let world = World::with_generator(self.registry.clone(), generator);
+
+        self.worlds.insert(id, world);
+        self.worlds.get_mut(&id).unwrap()
+    }
+
+    /// Remove the dimension `id`, returning its world
+    pub fn remove(&mut self, id: DimensionId) -> Option<World>
+    {
+        self.worlds.remove(&id)
+    }
+
+    /// Get the world of the dimension `id`, if there's one
+    pub fn world(&self, id: DimensionId) -> Option<&World>
+    {
+        self.worlds.get(&id)
+    }
+
+    /// Get the world of the dimension `id` mutably, ie. to load chunks, if there's one
+    pub fn world_mut(&mut self, id: DimensionId) -> Option<&mut World>
+    {
+        self.worlds.get_mut(&id)
+    }
+
+    /// Get the identifiers of every dimension, in no particular order
+    pub fn ids(&self) -> impl Iterator<Item = DimensionId> + '_
+    {
+        self.worlds.keys().copied()
+    }
+
+    /// Get the registry shared by every dimension
+    pub fn registry(&self) -> &Arc<block::Registry>
+    {
+        &self.registry
+    }
+}
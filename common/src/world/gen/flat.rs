@@ -0,0 +1,76 @@
+use std::sync::atomic::{ AtomicBool, Ordering };
+
+use crate::world::gen::ChunkGenerator;
+use crate::world::{ Chunk, block };
+use crate::math::int::chunk_block_range;
+use crate::math::Vec3;
+
+/// Generates flat terrain made of horizontal layers of blocks, stacked
+/// upwards starting at `y = 0`. Everything else is left as air.
+#[derive(Debug, Clone, Default)]
+pub struct FlatGenerator
+{
+    /// The block and thickness, in blocks, of each layer, from the bottom up
+    pub layers: Vec<(block::Choice, u32)>,
+}
+
+impl FlatGenerator
+{
+    /// Create a new flat terrain generator out of the given layers, from the
+    /// bottom up.
+    pub fn new(layers: Vec<(block::Choice, u32)>) -> Self
+    {
+        Self { layers }
+    }
+
+    /// Get the block at the given height, if any
+    fn layer_at(&self, y: i32) -> Option<&block::Choice>
+    {
+        if y < 0 { return None }
+
+        let mut top = 0;
+        for (block, height) in &self.layers
+        {
+            top += *height as i32;
+            if y < top
+            {
+                return Some(block)
+            }
+        }
+        None
+    }
+}
+
+impl ChunkGenerator for FlatGenerator
+{
+    fn generate(&self, pos: Vec3<i32>, chunk: &mut Chunk)
+    {
+        self.generate_cancellable(pos, chunk, &AtomicBool::new(false))
+    }
+
+    fn generate_cancellable(&self, pos: Vec3<i32>, chunk: &mut Chunk, cancelled: &AtomicBool)
+    {
+        const CHUNK_SIZE: i32 = <Chunk>::SIZE as i32;
+
+        let (min, _) = chunk_block_range(pos);
+
+        for y in 0..CHUNK_SIZE
+        {
+            // Checked between layers
+            if cancelled.load(Ordering::Relaxed)
+            {
+                return
+            }
+            let block = match self.layer_at(min.y + y)
+            {
+                Some(block) => block,
+                None => continue,
+            };
+
+            for (x, z) in (0..CHUNK_SIZE).flat_map(|x| (0..CHUNK_SIZE).map(move |z| (x, z)))
+            {
+                block.place(chunk, Vec3::new(x, y, z).as_());
+            }
+        }
+    }
+}
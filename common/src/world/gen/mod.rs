@@ -0,0 +1,31 @@
+mod perlin;
+mod flat;
+
+pub use perlin::PerlinGenerator;
+pub use flat::FlatGenerator;
+
+use std::sync::atomic::AtomicBool;
+
+use crate::world::Chunk;
+use crate::math::Vec3;
+
+/// Trait for all terrain generators, which fill freshly created, empty chunks
+/// of a [`World`](crate::world::World) with blocks.
+///
+/// Generation happens on worker threads, hence the `Send + Sync` bound.
+pub trait ChunkGenerator: Send + Sync
+{
+    /// Generate the chunk at the given chunk position(1 unit = 32 blocks),
+    /// which is all air when passed in.
+    fn generate(&self, pos: Vec3<i32>, chunk: &mut Chunk);
+
+    /// [ChunkGenerator::generate], but may give up early once `cancelled` is set,
+    /// ie. because the chunk was unloaded while generating, leaving the chunk
+    /// partially generated. Defaults to generating the chunk entirely regardless.
+    fn generate_cancellable(&self, pos: Vec3<i32>, chunk: &mut Chunk, cancelled: &AtomicBool)
+    {
+        let _ = cancelled;
+
+        self.generate(pos, chunk)
+    }
+}
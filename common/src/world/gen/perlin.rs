@@ -0,0 +1,132 @@
+use std::sync::atomic::{ AtomicBool, Ordering };
+
+use noise::{ NoiseFn, Seedable };
+
+use crate::world::gen::ChunkGenerator;
+use crate::world::{ Chunk, block };
+use crate::math::int::chunk_block_range;
+use crate::math::Vec3;
+
+/// Generates rolling terrain from a 2D perlin noise heightmap, made of a
+/// surface block on top of a few blocks of soil, with everything beneath
+/// filled with a single block.
+#[derive(Debug, Clone)]
+pub struct PerlinGenerator
+{
+    /// The terrain height generator
+    noise: noise::Perlin,
+    /// Topmost block of each column
+    surface: block::Choice,
+    /// Blocks right below the surface
+    soil: block::Choice,
+    /// Block filling the terrain beneath the soil
+    fill: block::Choice,
+}
+
+impl PerlinGenerator
+{
+    /// Horizontal scale of the noise, in noise units per block
+    const FREQUENCY: f64 = 0.2;
+    /// Maximum height of the terrain above(and depth below) `y = 0`, in blocks
+    const AMPLITUDE: f64 = 100.0;
+    /// Thickness of the soil below the surface, in blocks
+    const SOIL_DEPTH: i32 = 3;
+
+    /// Create a new perlin terrain generator filling the terrain with `block`
+    pub fn new(block: block::Choice) -> Self
+    {
+        Self::with_seed(block, 0)
+    }
+
+    /// Create a new perlin terrain generator filling the terrain with `block`,
+    /// whose terrain is entirely determined by `seed`.
+    pub fn with_seed(block: block::Choice, seed: u64) -> Self
+    {
+        Self::with_layers(block.clone(), block.clone(), block, seed)
+    }
+
+    /// Create a new perlin terrain generator laying `surface` on top of each
+    /// column, `soil` right below and `fill` beneath, whose terrain is entirely
+    /// determined by `seed`.
+    pub fn with_layers(surface: block::Choice, soil: block::Choice, fill: block::Choice, seed: u64) -> Self
+    {
+        // `Perlin` only takes 32 bit seeds, so fold the upper bits in rather
+        // than discarding them
+        let seed = (seed ^ (seed >> 32)) as u32;
+
+        Self
+        {
+            noise: Seedable::set_seed(Default::default(), seed),
+            surface,
+            soil,
+            fill,
+        }
+    }
+
+    /// Creates a new perlin terrain generator laying grass on the surface, dirt
+    /// below and stone beneath, with the given seed.
+    pub fn vanilla(seed: u64) -> Self
+    {
+        use crate::vanilla::blocks::{ BlockGrass, BlockDirt, BlockStone };
+
+        Self::with_layers(
+            block::Choice::new(BlockGrass { snowy: false }),
+            block::Choice::new(BlockDirt),
+            block::Choice::new(BlockStone),
+            seed,
+        )
+    }
+}
+
+impl Default for PerlinGenerator
+{
+    /// Creates a new perlin terrain generator laying grass on the surface, dirt
+    /// below and stone beneath, with a seed of `0`.
+    fn default() -> Self
+    {
+        Self::vanilla(0)
+    }
+}
+
+impl ChunkGenerator for PerlinGenerator
+{
+    fn generate(&self, pos: Vec3<i32>, chunk: &mut Chunk)
+    {
+        self.generate_cancellable(pos, chunk, &AtomicBool::new(false))
+    }
+
+    fn generate_cancellable(&self, pos: Vec3<i32>, chunk: &mut Chunk, cancelled: &AtomicBool)
+    {
+        const CHUNK_SIZE: i32 = <Chunk>::SIZE as i32;
+
+        // Position of the chunk's minimum corner block
+        let (min, _) = chunk_block_range(pos);
+
+        for (x, z) in (0..CHUNK_SIZE).flat_map(|x| (0..CHUNK_SIZE).map(move |z| (x, z)))
+        {
+            // Checked between columns
+            if cancelled.load(Ordering::Relaxed)
+            {
+                return
+            }
+            let sample = [
+                (min.x + x) as f64 * Self::FREQUENCY,
+                (min.z + z) as f64 * Self::FREQUENCY,
+            ];
+            let height = (self.noise.get(sample) * Self::AMPLITUDE) as i32;
+
+            for y in 0..CHUNK_SIZE
+            {
+                let depth = height - (min.y + y);
+                let block = match depth
+                {
+                    d if d < 0 => continue,
+                    0 => &self.surface,
+                    d if d <= Self::SOIL_DEPTH => &self.soil,
+                    _ => &self.fill,
+                };
+                block.place(chunk, Vec3::new(x, y, z).as_());
+            }
+        }
+    }
+}
@@ -1,24 +1,38 @@
 pub mod block;
+pub mod gen;
 mod chunk;
 mod world;
+mod save;
+mod raycast;
+mod pending;
+mod updates;
+mod queue;
+mod scheduled;
+mod dimension;
 
 pub use block::{ Block, blockdef };
+pub use gen::ChunkGenerator;
 pub use chunk::Chunk;
-pub use world::World;
+pub use world::{ World, WorldInfo, CachedReader, chunks_in_sphere, ChunkEvent, ChunkState, StreamingReport, BatchResult, BatchFailure, FillReport, WorldAccessError, UnloadError };
+pub use save::{ SaveReport, LoadError };
+pub use raycast::{ RaycastHit, Unloaded };
+pub use dimension::{ Dimensions, DimensionId };
 
 #[cfg(test)]
 mod tests
 {
-    use crate::world::{ World, block };
+    use crate::world::{ World, WorldInfo, Dimensions, DimensionId, Block, chunks_in_sphere, ChunkEvent, ChunkState, BatchFailure, FillReport, WorldAccessError, UnloadError, SaveReport, LoadError, RaycastHit, Unloaded, block };
+    use crate::world::gen::FlatGenerator;
     use crate::vanilla::blocks::*;
-    use crate::math::vec3;
+    use crate::vanilla::props::WoodVariant;
+    use crate::item::ItemStack;
+    use crate::math::{ Direction, Vec3, vec3 };
 
     #[test]
     fn test_world()
     {
         let mut registry = block::Registry::default();
 
-        registry.register::<BlockAir>();
         registry.register::<BlockChest>();
         registry.register::<BlockWoodenPlanks>();
         registry.register::<BlockWoodenSlab>();
@@ -29,24 +43,2351 @@ mod tests
         assert!(matches!(world.get(vec3(0, 0, 0)), None));
 
         println!("start loading chunks...");
-        for x in 0..12
+        world.load_chunks_in_radius(vec3(0, 0, 0), 2);
+
+        // wait for chunk(s) to load
+        world.wait_for_loads();
+        
+        println!("done loading chunks...");
+
+        println!("world[0, 0, 0] = {:?}", world.get(vec3(0, 0, 0)).map(|b| b.name()));
+        println!("world[1, 0, 0] = {:?}", world.get(vec3(1, 0, 0)).map(|b| b.name()));
+    }
+
+    #[test]
+    fn loading_counter()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+
+        let mut world = World::new(registry);
+
+        assert!(!world.has_pending_loads());
+
+        world.load_chunk(vec3(0, 0, 0));
+
+        // accounted for as soon as `load_chunk` returns
+        assert_eq!(world.num_chunks_loading(), 1);
+        assert!(world.has_pending_loads());
+
+        // loading the same chunk again doesn't count twice
+        world.load_chunk(vec3(0, 0, 0));
+        assert!(world.num_chunks_loading() <= 1);
+
+        world.wait_for_loads();
+
+        assert_eq!(world.num_chunks_loading(), 0);
+        assert!(world.get_chunk_mut(vec3(0, 0, 0)).is_some());
+    }
+
+    #[test]
+    fn wait_for_loads()
+    {
+        use std::sync::mpsc;
+        use std::time::{ Duration, Instant };
+
+        use parking_lot::Mutex;
+
+        let (tx, rx) = mpsc::channel();
+        let mut world = World::with_generator(block::Registry::default(), Stalled(Mutex::new(rx)));
+
+        // nothing to wait for
+        world.wait_for_loads();
+        assert!(world.wait_for_loads_timeout(Duration::from_secs(0)));
+
+        world.load_chunk(vec3(0, 0, 0));
+        world.load_chunk(vec3(1, 0, 0));
+
+        assert!(!world.wait_for_loads_timeout(Duration::from_millis(50)));
+        assert_eq!(world.num_chunks_loading(), 2);
+
+        tx.send(()).unwrap();
+        assert!(!world.wait_for_loads_timeout(Duration::from_millis(50)));
+
+        // returns promptly once generation completes
+        tx.send(()).unwrap();
+
+        let start = Instant::now();
+        world.wait_for_loads();
+
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(world.num_chunks_loading(), 0);
+        assert!(world.wait_for_loads_timeout(Duration::from_secs(0)));
+    }
+
+    #[test]
+    fn chunk_state()
+    {
+        use std::sync::mpsc;
+
+        use parking_lot::Mutex;
+
+        let (tx, rx) = mpsc::channel();
+        let mut world = World::with_generator(block::Registry::default(), Stalled(Mutex::new(rx)));
+
+        let pos = vec3(0, -1, 0);
+
+        assert_eq!(world.chunk_state(pos), ChunkState::Absent);
+        assert!(!world.is_chunk_loaded(pos));
+        assert!(!world.contains_block(vec3(0, -1, 0)));
+
+        world.load_chunk(pos);
+
+        assert_eq!(world.chunk_state(pos), ChunkState::Generating);
+        assert!(!world.is_chunk_loaded(pos));
+        assert!(!world.contains_block(vec3(0, -1, 0)));
+
+        tx.send(()).unwrap();
+        world.wait_for_loads();
+
+        assert_eq!(world.chunk_state(pos), ChunkState::Loaded);
+        assert!(world.is_chunk_loaded(pos));
+        assert!(world.contains_block(vec3(31, -32, 31)));
+        assert!(!world.contains_block(vec3(0, 0, 0)));
+        assert_eq!(world.chunk_state(vec3(0, 0, 0)), ChunkState::Absent);
+
+        // borrowed chunks are still loaded
+        let lock = world.get_chunk_mut(pos).unwrap();
+        assert!(world.is_chunk_loaded(pos));
+        drop(lock);
+
+        world.unload_chunk(pos).unwrap();
+        assert_eq!(world.chunk_state(pos), ChunkState::Absent);
+    }
+
+    /// Generator that doesn't generate until told to
+    struct Stalled(parking_lot::Mutex<std::sync::mpsc::Receiver<()>>);
+
+    impl crate::world::ChunkGenerator for Stalled
+    {
+        fn generate(&self, _: Vec3<i32>, _: &mut crate::world::Chunk)
+        {
+            self.0.lock().recv().unwrap();
+        }
+    }
+
+    /// Generator that reports when it starts generating a chunk, and doesn't
+    /// finish until told to
+    struct Gated
+    {
+        started: parking_lot::Mutex<std::sync::mpsc::Sender<Vec3<i32>>>,
+        go: parking_lot::Mutex<std::sync::mpsc::Receiver<()>>,
+    }
+
+    impl crate::world::ChunkGenerator for Gated
+    {
+        fn generate(&self, pos: Vec3<i32>, _: &mut crate::world::Chunk)
+        {
+            self.started.lock().send(pos).unwrap();
+            self.go.lock().recv().unwrap();
+        }
+    }
+
+    /// Single-threaded world using a [Gated] generator, along with the receiver of
+    /// the chunks it started and the sender to let them finish
+    fn gated_world() -> (World, std::sync::mpsc::Receiver<Vec3<i32>>, std::sync::mpsc::Sender<()>)
+    {
+        use std::sync::mpsc;
+
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+
+        let (started, started_rx) = mpsc::channel();
+        let (go_tx, go) = mpsc::channel();
+        let mut world = World::with_generator(registry, Gated
+        {
+            started: parking_lot::Mutex::new(started),
+            go: parking_lot::Mutex::new(go),
+        });
+        world.set_worker_threads(1);
+
+        (world, started_rx, go_tx)
+    }
+
+    /// World with every vanilla block registered, on a single layer of stone at
+    /// `y = 0`, with `chunks` loaded
+    fn flat_world(chunks: &[Vec3<i32>]) -> World
+    {
+        let mut registry = block::Registry::default();
+
+        register_all(&mut registry);
+
+        let mut world = World::with_generator(registry, FlatGenerator::new(vec![(block::Choice::new(BlockStone), 1)]));
+
+        for &pos in chunks
+        {
+            world.load_chunk(pos);
+        }
+        world.wait_for_loads();
+        world
+    }
+
+    #[test]
+    fn load_priority()
+    {
+        let (mut world, started, go) = gated_world();
+
+        // occupy the only worker
+        world.load_chunk(vec3(9, 9, 9));
+        assert_eq!(started.recv().unwrap(), vec3(9, 9, 9));
+
+        world.set_load_anchor(vec3(10, 0, 0));
+        for &pos in &[vec3(0, 0, 0), vec3(14, 0, 0), vec3(10, 1, 0), vec3(5, 0, 0), vec3(10, 0, 0), vec3(10, 0, 2)]
+        {
+            world.load_chunk(pos);
+        }
+        for _ in 0..7
+        {
+            go.send(()).unwrap();
+        }
+        world.wait_for_loads();
+
+        assert_eq!(started.try_iter().collect::<Vec<_>>(), vec![
+            vec3(10, 0, 0),
+            vec3(10, 1, 0),
+            vec3(10, 0, 2),
+            vec3(14, 0, 0),
+            vec3(5, 0, 0),
+            vec3(0, 0, 0),
+        ]);
+    }
+
+    #[test]
+    fn cancel_loading()
+    {
+        let (mut world, started, go) = gated_world();
+        let events = world.subscribe_chunk_events();
+
+        world.load_chunk(vec3(0, 0, 0));
+        assert_eq!(started.recv().unwrap(), vec3(0, 0, 0));
+
+        world.load_chunk(vec3(1, 0, 0));
+        world.load_chunk(vec3(2, 0, 0));
+        world.set_deferred(vec3(5, 5, 5), BlockWoodenPlanks { variant: WoodVariant::Oak }).unwrap();
+
+        assert_eq!(world.num_chunks_loading(), 3);
+        assert_eq!(world.pending_edit_count(), 1);
+
+        // queued, never starts
+        assert!(matches!(world.unload_chunk(vec3(1, 0, 0)), Ok(None)));
+        assert_eq!(world.num_chunks_loading(), 2);
+        // already started, discarded once done
+        assert!(matches!(world.unload_chunk(vec3(0, 0, 0)), Ok(None)));
+
+        for _ in 0..2
+        {
+            go.send(()).unwrap();
+        }
+        world.wait_for_loads();
+
+        assert_eq!(started.try_iter().collect::<Vec<_>>(), vec![vec3(2, 0, 0)]);
+        assert_eq!(events.try_iter().collect::<Vec<_>>(), vec![ChunkEvent::Generated(vec3(2, 0, 0))]);
+        assert_eq!(world.chunk_positions(), vec![vec3(2, 0, 0)]);
+        assert!(world.get(vec3(5, 5, 5)).is_none());
+
+        // edits to the cancelled chunk are kept for next time
+        assert_eq!(world.pending_edit_count(), 1);
+
+        world.load_chunk(vec3(0, 0, 0));
+        go.send(()).unwrap();
+
+        assert!(world.get(vec3(5, 5, 5)).unwrap().is::<BlockWoodenPlanks>());
+        assert_eq!(world.pending_edit_count(), 0);
+    }
+
+    #[test]
+    fn cancel_many()
+    {
+        let (mut world, started, go) = gated_world();
+        let events = world.subscribe_chunk_events();
+
+        // occupy the only worker
+        world.load_chunk(vec3(0, 9, 0));
+        assert_eq!(started.recv().unwrap(), vec3(0, 9, 0));
+
+        for x in 0..32
+        {
+            world.load_chunk(vec3(x, 0, 0));
+        }
+        for x in (0..32).step_by(2)
+        {
+            world.unload_chunk(vec3(x, 0, 0)).unwrap();
+        }
+        for _ in 0..17
+        {
+            go.send(()).unwrap();
+        }
+        world.wait_for_loads();
+
+        let mut generated = events
+            .try_iter()
+            .map(|event| match event
+            {
+                ChunkEvent::Generated(pos) => pos,
+                event => panic!("unexpected {:?}", event),
+            })
+            .collect::<Vec<_>>();
+        generated.sort_unstable_by_key(|pos| (pos.y, pos.x));
+
+        let odd = (1..32).step_by(2).map(|x| vec3(x, 0, 0));
+        assert_eq!(generated, odd.chain(Some(vec3(0, 9, 0))).collect::<Vec<_>>());
+        assert!(started.try_iter().all(|pos| pos.x % 2 == 1));
+
+        for x in 0..32
+        {
+            let expected = if x % 2 == 0 { ChunkState::Absent } else { ChunkState::Loaded };
+
+            assert_eq!(world.chunk_state(vec3(x, 0, 0)), expected);
+        }
+    }
+
+    #[test]
+    fn generate_cancellable()
+    {
+        use std::sync::atomic::AtomicBool;
+
+        use crate::world::{ ChunkGenerator, Chunk };
+        use crate::world::gen::PerlinGenerator;
+
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+
+        let registry = std::sync::Arc::new(registry);
+        let planks = block::Choice::new(BlockWoodenPlanks { variant: WoodVariant::Oak });
+        let generators: [Box<dyn ChunkGenerator>; 2] = [
+            Box::new(PerlinGenerator::new(planks.clone())),
+            Box::new(FlatGenerator::new(vec![(planks, 32)])),
+        ];
+
+        for generator in generators.iter()
+        {
+            let mut full = Chunk::new(vec3(0, 0, 0), &registry);
+            let mut cancelled = Chunk::new(vec3(0, 0, 0), &registry);
+
+            generator.generate_cancellable(vec3(0, 0, 0), &mut full, &AtomicBool::new(false));
+            generator.generate_cancellable(vec3(0, 0, 0), &mut cancelled, &AtomicBool::new(true));
+
+            assert!(full.iter().any(|(_, block)| block.is::<BlockWoodenPlanks>()));
+            assert!(cancelled.iter().all(|(_, block)| block.is::<BlockAir>()));
+        }
+    }
+
+    #[test]
+    fn perlin_layers()
+    {
+        use crate::world::{ ChunkGenerator, Chunk };
+        use crate::world::gen::PerlinGenerator;
+
+        let mut registry = block::Registry::default();
+
+        register_all(&mut registry);
+
+        let registry = std::sync::Arc::new(registry);
+        let generator = PerlinGenerator::vanilla(42);
+        let mut columns = 0;
+
+        for &pos in &[vec3(0, 0, 0), vec3(0, -1, 0), vec3(3, 0, -2), vec3(0, 1, 0)]
+        {
+            let mut chunk = Chunk::new(pos, &registry);
+
+            generator.generate(pos, &mut chunk);
+
+            for (x, z) in (0..32).flat_map(|x| (0..32).map(move |z| (x, z)))
+            {
+                let column = (0..32)
+                    .rev()
+                    .map(|y| chunk[(x, y, z)].id())
+                    .skip_while(|&id| id == BlockAir::ID)
+                    .collect::<Vec<_>>();
+
+                // surface is within this chunk, with the soil fully below it
+                if column.len() < 5 || column.len() == 32
+                {
+                    continue
+                }
+                columns += 1;
+
+                assert_eq!(column[0], BlockGrass::ID);
+                assert_eq!(&column[1..4], &[BlockDirt::ID; 3]);
+                assert!(column[4..].iter().all(|&id| id == BlockStone::ID));
+            }
+        }
+        assert!(columns > 0);
+    }
+
+    #[test]
+    fn perlin_world_coordinates()
+    {
+        use crate::world::{ ChunkGenerator, Chunk };
+        use crate::world::gen::PerlinGenerator;
+
+        let mut registry = block::Registry::default();
+
+        register_all(&mut registry);
+
+        let registry = std::sync::Arc::new(registry);
+        let generator = PerlinGenerator::vanilla(42);
+        let generate = |pos|
+        {
+            let mut chunk = Chunk::new(pos, &registry);
+
+            generator.generate(pos, &mut chunk);
+            chunk
+                .iter()
+                .map(|(_, block)| block.id())
+                .collect::<Vec<_>>()
+        };
+
+        // noise is sampled at world coordinates, so neighboring chunks don't
+        // repeat the same terrain
+        for &y in &[-1, 0]
+        {
+            let origin = generate(vec3(0, y, 0));
+
+            assert_ne!(origin, generate(vec3(1, y, 0)));
+            assert_ne!(origin, generate(vec3(0, y, 1)));
+            assert_eq!(origin, generate(vec3(0, y, 0)));
+        }
+    }
+
+    #[test]
+    fn grass_random_tick()
+    {
+        let world = flat_world(&[vec3(0, 0, 0)]);
+
+        world.fill_region(vec3(0, 1, 0), vec3(31, 1, 31), BlockDirt);
+        world.fill_region(vec3(0, 2, 0), vec3(31, 2, 31), BlockGrass { snowy: false });
+
+        // uncovered
+        assert_eq!(world.random_tick(vec3(0, 2, 0)), Ok(false));
+        assert!(world.get(vec3(0, 2, 0)).unwrap().is::<BlockGrass>());
+
+        // under a full block
+        world.set(vec3(0, 3, 0), BlockStone).unwrap();
+        assert_eq!(world.random_tick(vec3(0, 2, 0)), Ok(true));
+        assert!(world.get(vec3(0, 2, 0)).unwrap().is::<BlockDirt>());
+
+        // under a block that doesn't cover it
+        world.set(vec3(1, 3, 0), BlockChest::new(Direction::North)).unwrap();
+        assert_eq!(world.random_tick(vec3(1, 2, 0)), Ok(false));
+
+        // dirt doesn't tick
+        assert_eq!(world.random_tick(vec3(2, 1, 0)), Ok(false));
+
+        // not loaded
+        assert_eq!(world.random_tick(vec3(0, 40, 0)), Err(WorldAccessError::ChunkNotLoaded));
+    }
+
+    #[test]
+    fn crops()
+    {
+        let world = flat_world(&[vec3(0, 0, 0)]);
+
+        world.fill_region(vec3(0, 1, 0), vec3(31, 1, 31), BlockDirt);
+
+        let age = |world: &World, pos| world.get_as::<BlockWheat>(pos).map(|wheat| wheat.age);
+        let moisture = |world: &World, pos| world.get_as::<BlockFarmland>(pos).map(|farmland| farmland.moisture);
+
+        // hydrated farmland
+        world.set(vec3(4, 1, 4), BlockFarmland { moisture: 0 }).unwrap();
+        world.set(vec3(4, 2, 4), BlockWheat { age: 0 }).unwrap();
+        world.set(vec3(8, 1, 4), BlockWater { level: 0, falling: false }).unwrap();
+
+        assert_eq!(world.random_tick(vec3(4, 1, 4)), Ok(true));
+        assert_eq!(moisture(&world, vec3(4, 1, 4)), Some(BlockFarmland::MAX_MOISTURE));
+
+        let mut last = 0;
+        for _ in 0..10
+        {
+            world.random_tick(vec3(4, 2, 4)).unwrap();
+
+            let age = age(&world, vec3(4, 2, 4)).unwrap();
+            assert!(age >= last);
+            last = age;
+        }
+        assert_eq!(last, BlockWheat::MAX_AGE);
+        assert_eq!(world.random_tick(vec3(4, 2, 4)), Ok(false));
+        assert_eq!(world.get_as::<BlockWheat>(vec3(4, 2, 4)).unwrap().drops(), vec![
+            ItemStack::new("wheat", 1),
+            ItemStack::new("wheat_seeds", 2),
+        ]);
+
+        // no growth on stone, nor on dirt
+        world.set(vec3(10, 1, 10), BlockStone).unwrap();
+        world.set(vec3(10, 2, 10), BlockWheat { age: 0 }).unwrap();
+        world.set(vec3(11, 2, 10), BlockWheat { age: 0 }).unwrap();
+        for _ in 0..10
+        {
+            assert_eq!(world.random_tick(vec3(10, 2, 10)), Ok(false));
+            assert_eq!(world.random_tick(vec3(11, 2, 10)), Ok(false));
+        }
+        assert_eq!(age(&world, vec3(10, 2, 10)), Some(0));
+
+        // dries out away from water, turning back into dirt unless planted
+        world.set(vec3(20, 1, 20), BlockFarmland { moisture: 1 }).unwrap();
+        world.set(vec3(22, 1, 20), BlockFarmland { moisture: 0 }).unwrap();
+        world.set(vec3(22, 2, 20), BlockWheat { age: 3 }).unwrap();
+
+        assert_eq!(world.random_tick(vec3(20, 1, 20)), Ok(true));
+        assert_eq!(moisture(&world, vec3(20, 1, 20)), Some(0));
+        assert_eq!(world.random_tick(vec3(20, 1, 20)), Ok(true));
+        assert!(world.get(vec3(20, 1, 20)).unwrap().is::<BlockDirt>());
+        assert_eq!(world.random_tick(vec3(22, 1, 20)), Ok(false));
+
+        // wheat pops off without farmland
+        world.process_updates();
+        world.set(vec3(22, 1, 20), BlockDirt).unwrap();
+        world.process_updates();
+        assert!(world.get(vec3(22, 2, 20)).unwrap().is::<BlockAir>());
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn load_chunk_async()
+    {
+        use std::future::Future;
+        use std::task::{ Context, Poll, Wake };
+        use std::sync::Arc;
+
+        /// Run `future` to completion on the current thread, parking it while pending
+        fn block_on<F: Future>(future: F) -> F::Output
+        {
+            struct Unpark(std::thread::Thread);
+
+            impl Wake for Unpark
+            {
+                fn wake(self: Arc<Self>)
+                {
+                    self.0.unpark();
+                }
+            }
+
+            let waker = Arc::new(Unpark(std::thread::current())).into();
+            let mut cx = Context::from_waker(&waker);
+            let mut future = Box::pin(future);
+            loop
+            {
+                match future.as_mut().poll(&mut cx)
+                {
+                    Poll::Ready(out) => return out,
+                    Poll::Pending => std::thread::park(),
+                }
+            }
+        }
+
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockStone>();
+
+        let generator = FlatGenerator::new(vec![(block::Choice::new(BlockStone), 1)]);
+        let mut world = World::with_generator(registry, generator);
+
+        let load = world.load_chunk_async(vec3(0, 0, 0));
+        block_on(load);
+
+        assert_eq!(world.chunk_state(vec3(0, 0, 0)), ChunkState::Loaded);
+        assert!(world.try_get(vec3(3, 0, 3)).unwrap().is::<BlockStone>());
+
+        // already loaded
+        block_on(world.load_chunk_async(vec3(0, 0, 0)));
+        // several at once
+        let loads = (1..4)
+            .map(|x| world.load_chunk_async(vec3(x, 0, 0)))
+            .collect::<Vec<_>>();
+        for load in loads
+        {
+            block_on(load);
+        }
+        assert_eq!(world.num_chunks_loading(), 0);
+    }
+
+    #[test]
+    fn tuple_coords()
+    {
+        let mut world = World::new(block::Registry::default());
+
+        world.load_chunk(vec3(0, 1, -1));
+        world.wait_for_loads();
+
+        world.set((5, 40, -3), BlockAir).unwrap();
+        assert!(world.get((5, 40, -3)).unwrap().is::<BlockAir>());
+        assert!(world.get_as::<BlockAir>((5, 40, -3)).is_some());
+        assert!(world.get((5, 64, -3)).is_none());
+        assert!(world.get_chunk((0, 1, -1)).is_some());
+    }
+
+    #[test]
+    fn slabs()
+    {
+        let world = flat_world(&[vec3(0, 0, 0)]);
+
+        let slab = |facing, variant| BlockWoodenSlab { facing, variant };
+        let oak = BlockWoodenPlanks { variant: WoodVariant::Oak };
+
+        // top onto bottom, by clicking its top face
+        world.set(vec3(4, 1, 4), slab(Direction::Down, WoodVariant::Oak)).unwrap();
+        assert_eq!(world.place(vec3(4, 2, 4), Direction::Up, slab(Direction::Up, WoodVariant::Oak)), Ok(true));
+        assert_eq!(world.get_as::<BlockWoodenPlanks>(vec3(4, 1, 4)).as_deref(), Some(&oak));
+        assert!(world.get(vec3(4, 2, 4)).unwrap().is::<BlockAir>());
+
+        // top into a cell with a bottom, by clicking a neighbor
+        world.set(vec3(6, 1, 4), slab(Direction::Down, WoodVariant::Oak)).unwrap();
+        assert_eq!(world.place(vec3(6, 1, 4), Direction::East, slab(Direction::Up, WoodVariant::Oak)), Ok(true));
+        assert_eq!(world.get_as::<BlockWoodenPlanks>(vec3(6, 1, 4)).as_deref(), Some(&oak));
+
+        // mismatched variants stack
+        world.set(vec3(8, 1, 4), slab(Direction::Down, WoodVariant::Oak)).unwrap();
+        assert_eq!(world.place(vec3(8, 2, 4), Direction::Up, slab(Direction::Down, WoodVariant::Birch)), Ok(true));
+        assert_eq!(world.get_as::<BlockWoodenSlab>(vec3(8, 1, 4)).as_deref(), Some(&slab(Direction::Down, WoodVariant::Oak)));
+        assert_eq!(world.get_as::<BlockWoodenSlab>(vec3(8, 2, 4)).as_deref(), Some(&slab(Direction::Down, WoodVariant::Birch)));
+
+        // clicking the full half doesn't merge
+        world.set(vec3(10, 2, 4), slab(Direction::Down, WoodVariant::Oak)).unwrap();
+        assert_eq!(world.place(vec3(10, 1, 4), Direction::Down, slab(Direction::Up, WoodVariant::Oak)), Ok(true));
+        assert_eq!(world.get_as::<BlockWoodenSlab>(vec3(10, 2, 4)).as_deref(), Some(&slab(Direction::Down, WoodVariant::Oak)));
+        assert_eq!(world.get_as::<BlockWoodenSlab>(vec3(10, 1, 4)).as_deref(), Some(&slab(Direction::Up, WoodVariant::Oak)));
+
+        // vertical slabs, along their axis only
+        world.set(vec3(12, 1, 4), slab(Direction::North, WoodVariant::Oak)).unwrap();
+        assert_eq!(world.place(vec3(12, 1, 5), Direction::South, slab(Direction::South, WoodVariant::Oak)), Ok(true));
+        assert_eq!(world.get_as::<BlockWoodenPlanks>(vec3(12, 1, 4)).as_deref(), Some(&oak));
+        assert!(world.get(vec3(12, 1, 5)).unwrap().is::<BlockAir>());
+
+        world.set(vec3(14, 1, 4), slab(Direction::North, WoodVariant::Oak)).unwrap();
+        assert_eq!(world.place(vec3(14, 1, 4), Direction::Up, slab(Direction::East, WoodVariant::Oak)), Ok(true));
+        assert_eq!(world.get_as::<BlockWoodenSlab>(vec3(14, 1, 4)).as_deref(), Some(&slab(Direction::East, WoodVariant::Oak)));
+    }
+
+    #[test]
+    fn redstone()
+    {
+        let world = flat_world(&[vec3(0, 0, 0)]);
+
+        // settles, rather than updating forever
+        let settle = |world: &World|
+        {
+            for _ in 0..100
+            {
+                if world.process_updates() == 0
+                {
+                    return
+                }
+            }
+            panic!("updates never settled");
+        };
+        let power = |world: &World, x| world.get_as::<BlockRedstoneDust>(vec3(x, 1, 4)).unwrap().power;
+
+        for x in 1..=16
+        {
+            world.set(vec3(x, 1, 4), BlockRedstoneDust { power: 0 }).unwrap();
+        }
+        world.set(vec3(0, 1, 4), BlockRedstoneTorch { attached: Direction::Up }).unwrap();
+        settle(&world);
+
+        for x in 1..=16
+        {
+            assert_eq!(power(&world, x), (BlockRedstoneDust::MAX_POWER + 1).saturating_sub(x as u8), "dust at x = {}", x);
+        }
+
+        // shapes
+        assert_eq!(BlockRedstoneDust::connections(&world, vec3(1, 1, 4)), vec![Direction::East, Direction::West]);
+        assert_eq!(BlockRedstoneDust::connections(&world, vec3(16, 1, 4)), vec![Direction::East, Direction::West]);
+        assert_eq!(world.place(vec3(8, 1, 5), Direction::Up, BlockRedstoneDust { power: 0 }), Ok(true));
+        assert_eq!(BlockRedstoneDust::connections(&world, vec3(8, 1, 4)), vec![Direction::South, Direction::East, Direction::West]);
+        assert_eq!(world.get_as::<BlockRedstoneDust>(vec3(8, 1, 5)).unwrap().power, power(&world, 8) - 1);
+
+        // everything dies out without the source
+        world.set(vec3(0, 1, 4), BlockAir).unwrap();
+        settle(&world);
+
+        for x in 1..=16
+        {
+            assert_eq!(power(&world, x), 0, "dust at x = {}", x);
+        }
+        assert!(BlockRedstoneDust::connections(&world, vec3(20, 1, 20)).is_empty());
+
+        // levers power dust when flipped on
+        world.set(vec3(17, 1, 4), BlockLever { attached: Direction::Up, powered: false }).unwrap();
+        settle(&world);
+        assert_eq!(power(&world, 16), 0);
+
+        assert_eq!(BlockLever::toggle(&world, vec3(17, 1, 4)), Ok(true));
+        settle(&world);
+        assert_eq!(power(&world, 16), BlockRedstoneDust::MAX_POWER);
+        assert_eq!(power(&world, 2), 1);
+
+        assert_eq!(BlockLever::toggle(&world, vec3(17, 1, 4)), Ok(true));
+        settle(&world);
+        assert_eq!(power(&world, 16), 0);
+        assert_eq!(BlockLever::toggle(&world, vec3(18, 1, 4)), Ok(false));
+    }
+
+    #[test]
+    fn tnt()
+    {
+        let world = flat_world(&[vec3(0, 0, 0)]);
+
+        world.fill_region(vec3(0, 1, 0), vec3(31, 3, 31), BlockStone);
+
+        let is_air = |world: &World, pos| world.get(pos).unwrap().is::<BlockAir>();
+
+        world.set(vec3(8, 4, 8), BlockTnt { primed: false }).unwrap();
+        world.set(vec3(11, 4, 8), BlockTnt { primed: false }).unwrap();
+        world.set(vec3(8, 4, 10), BlockObsidian).unwrap();
+        world.set(vec3(8, 3, 7), BlockObsidian).unwrap();
+
+        assert_eq!(BlockTnt::prime(&world, vec3(8, 4, 8)), Ok(true));
+        assert_eq!(BlockTnt::prime(&world, vec3(8, 4, 8)), Ok(false));
+        for _ in 1..BlockTnt::FUSE_TICKS
+        {
+            world.tick(usize::MAX);
+        }
+        assert_eq!(world.get_as::<BlockTnt>(vec3(8, 4, 8)).as_deref(), Some(&BlockTnt { primed: true }));
+
+        // first crater
+        assert_eq!(world.tick(usize::MAX), 1);
+        assert!(is_air(&world, vec3(8, 4, 8)));
+        assert!(is_air(&world, vec3(8, 3, 8)));
+        assert!(is_air(&world, vec3(8, 2, 8)));
+        assert!(world.get(vec3(8, 1, 8)).unwrap().is::<BlockStone>());
+        assert!(world.get(vec3(8, 4, 10)).unwrap().is::<BlockObsidian>());
+        assert!(world.get(vec3(8, 3, 7)).unwrap().is::<BlockObsidian>());
+        // primes its neighbor rather than destroying it
+        assert_eq!(world.get_as::<BlockTnt>(vec3(11, 4, 8)).as_deref(), Some(&BlockTnt { primed: true }));
+
+        for _ in 1..BlockTnt::CHAIN_FUSE_TICKS
+        {
+            world.tick(usize::MAX);
+        }
+        assert!(world.get(vec3(11, 4, 8)).unwrap().is::<BlockTnt>());
+        assert!(world.get(vec3(11, 3, 8)).unwrap().is::<BlockStone>());
+
+        // second crater
+        assert_eq!(world.tick(usize::MAX), 1);
+        assert!(is_air(&world, vec3(11, 4, 8)));
+        assert!(is_air(&world, vec3(11, 3, 8)));
+        assert!(world.get(vec3(11, 1, 8)).unwrap().is::<BlockStone>());
+        assert!(world.get(vec3(8, 4, 10)).unwrap().is::<BlockObsidian>());
+        assert_eq!(world.scheduled_tick_count(), 0);
+
+        // lit by redstone
+        world.set(vec3(20, 4, 20), BlockTnt { primed: false }).unwrap();
+        world.set(vec3(21, 4, 20), BlockLever { attached: Direction::Up, powered: false }).unwrap();
+        world.process_updates();
+        assert_eq!(world.get_as::<BlockTnt>(vec3(20, 4, 20)).as_deref(), Some(&BlockTnt { primed: false }));
+
+        BlockLever::toggle(&world, vec3(21, 4, 20)).unwrap();
+        world.process_updates();
+        assert_eq!(world.get_as::<BlockTnt>(vec3(20, 4, 20)).as_deref(), Some(&BlockTnt { primed: true }));
+        assert_eq!(world.scheduled_tick_count(), 1);
+    }
+
+    #[test]
+    fn tick_budget()
+    {
+        let world = flat_world(&[vec3(0, 0, 0)]);
+
+        world.fill_region(vec3(0, 1, 0), vec3(31, 3, 31), BlockStone);
+
+        // far enough apart not to set each other off
+        let tnt = [vec3(2, 4, 4), vec3(10, 4, 4), vec3(18, 4, 4), vec3(26, 4, 4), vec3(2, 4, 20)];
+        let exploded = |world: &World| tnt
+            .iter()
+            .map(|&pos| world.get(pos).unwrap().is::<BlockAir>())
+            .collect::<Vec<_>>();
+
+        for &pos in &tnt
+        {
+            world.set(pos, BlockTnt { primed: true }).unwrap();
+            world.schedule_tick(pos, 1);
+        }
+        world.schedule_tick(vec3(10, 4, 20), 2);
+
+        assert_eq!(world.tick(2), 2);
+        assert_eq!(exploded(&world), vec![true, true, false, false, false]);
+        assert_eq!(world.pending_update_count(), 3);
+        assert_eq!(world.scheduled_tick_count(), 1);
+
+        // deferred ticks go first
+        assert_eq!(world.tick(2), 2);
+        assert_eq!(exploded(&world), vec![true, true, true, true, false]);
+        assert_eq!(world.pending_update_count(), 2);
+
+        assert_eq!(world.tick(0), 0);
+        assert_eq!(world.pending_update_count(), 2);
+
+        // `(10, 4, 20)` is just stone
+        assert_eq!(world.tick(usize::MAX), 1);
+        assert_eq!(exploded(&world), vec![true; 5]);
+        assert_eq!(world.pending_update_count(), 0);
+        assert_eq!(world.scheduled_tick_count(), 0);
+        assert_eq!(world.current_tick(), 4);
+    }
+
+    #[test]
+    fn leaves()
+    {
+        let world = flat_world(&[vec3(0, 0, 0), vec3(1, 0, 0)]);
+
+        let settle = |world: &World| while world.process_updates() > 0 {};
+        let distance = |world: &World, pos| world.get_as::<BlockLeaves>(pos).map(|leaves| leaves.distance);
+        let leaves = BlockLeaves { variant: WoodVariant::Oak, distance: BlockLeaves::MAX_DISTANCE, persistent: false };
+
+        // canopy straddling the chunk border at x = 31/32
+        let (min, max) = (vec3(28, 4, 6), vec3(35, 6, 10));
+        world.fill_region(min, max, leaves);
+        world.set(vec3(35, 6, 10), BlockLeaves { persistent: true, ..leaves }).unwrap();
+        for y in 0..=5
+        {
+            world.set(vec3(31, y, 8), BlockLog { variant: WoodVariant::Oak }).unwrap();
+        }
+        settle(&world);
+
+        assert_eq!(distance(&world, vec3(30, 5, 8)), Some(1));
+        assert_eq!(distance(&world, vec3(32, 5, 8)), Some(1));
+        assert_eq!(distance(&world, vec3(35, 4, 8)), Some(4));
+        assert_eq!(distance(&world, vec3(35, 6, 10)), Some(BlockLeaves::MAX_DISTANCE));
+        // only the far corners are out of reach
+        for pos in (min.x..=max.x).flat_map(|x| (min.y..=max.y).flat_map(move |y| (min.z..=max.z).map(move |z| vec3(x, y, z))))
+        {
+            world.random_tick(pos).unwrap();
+        }
+        assert!(world.get(vec3(28, 4, 6)).unwrap().is::<BlockLeaves>());
+        assert!(world.get(vec3(35, 6, 6)).unwrap().is::<BlockAir>());
+        assert!(world.get(vec3(35, 6, 10)).unwrap().is::<BlockLeaves>());
+
+        // chop the trunk
+        for y in 0..=5
+        {
+            world.set(vec3(31, y, 8), BlockAir).unwrap();
+        }
+        settle(&world);
+
+        assert_eq!(distance(&world, vec3(32, 5, 8)), Some(BlockLeaves::MAX_DISTANCE));
+        for pos in (min.x..=max.x).flat_map(|x| (min.y..=max.y).flat_map(move |y| (min.z..=max.z).map(move |z| vec3(x, y, z))))
+        {
+            world.random_tick(pos).unwrap();
+            match pos == vec3(35, 6, 10)
+            {
+                true => assert!(world.get(pos).unwrap().is::<BlockLeaves>()),
+                false => assert!(world.get(pos).unwrap().is::<BlockAir>(), "{:?}", pos),
+            }
+        }
+    }
+
+    #[test]
+    fn neighbor_updates_bounded()
+    {
+        let world = flat_world(&[vec3(0, 0, 0)]);
+
+        // replacing a block twice only updates its neighbors once
+        world.set(vec3(4, 4, 4), BlockStone).unwrap();
+        world.set(vec3(4, 4, 4), BlockAir).unwrap();
+        assert_eq!(world.neighbor_update_count(), 6);
+        world.process_updates();
+        assert_eq!(world.neighbor_update_count(), 0);
+
+        // filled blocks update their neighbors too
+        world.fill_region(vec3(0, 0, 0), vec3(1, 0, 0), BlockStone);
+        assert_eq!(world.neighbor_update_count(), 2 * 6);
+
+        // never processed, but bounded
+        world.set_neighbor_update_cap(100);
+        assert_eq!(world.neighbor_update_count(), 12);
+        world.fill_region(vec3(0, 0, 0), vec3(31, 31, 31), BlockStone);
+        assert_eq!(world.neighbor_update_count(), 100);
+        world.set_neighbor_update_cap(10);
+        assert_eq!(world.neighbor_update_count(), 10);
+        world.process_updates();
+        assert_eq!(world.neighbor_update_count(), 0);
+    }
+
+    #[test]
+    fn stairs_corner()
+    {
+        use crate::math::Aabb;
+
+        let world = flat_world(&[vec3(0, 0, 0)]);
+
+        let stairs = |facing| BlockStairs { facing, half: Half::Bottom, shape: StairsShape::Straight };
+        let shape = |world: &World, pos| world.get_as::<BlockStairs>(pos).unwrap().shape;
+
+        world.set(vec3(4, 0, 4), stairs(Direction::North)).unwrap();
+        world.process_updates();
+        assert_eq!(shape(&world, vec3(4, 0, 4)), StairsShape::Straight);
+
+        // against the side of stairs behind
+        world.set(vec3(4, 0, 3), stairs(Direction::East)).unwrap();
+        assert_eq!(shape(&world, vec3(4, 0, 4)), StairsShape::Straight);
+        assert_eq!(world.process_updates(), 1);
+        assert_eq!(shape(&world, vec3(4, 0, 4)), StairsShape::OuterRight);
+        assert_eq!(shape(&world, vec3(4, 0, 3)), StairsShape::Straight);
+
+        let boxes = world.get(vec3(4, 0, 4)).unwrap().collision_boxes();
+        assert_eq!(boxes, vec![
+            Aabb { min: vec3(0.0, 0.0, 0.0), max: vec3(1.0, 0.5, 1.0) },
+            Aabb { min: vec3(0.5, 0.5, 0.0), max: vec3(1.0, 1.0, 0.5) },
+        ]);
+
+        // against the side of stairs in front
+        world.set(vec3(4, 0, 3), BlockAir).unwrap();
+        world.set(vec3(4, 0, 5), stairs(Direction::West)).unwrap();
+        world.process_updates();
+        assert_eq!(shape(&world, vec3(4, 0, 4)), StairsShape::InnerLeft);
+
+        // different halves don't connect
+        world.set(vec3(4, 0, 5), BlockStairs { half: Half::Top, ..stairs(Direction::West) }).unwrap();
+        world.process_updates();
+        assert_eq!(shape(&world, vec3(4, 0, 4)), StairsShape::Straight);
+
+        // nothing left to update
+        world.process_updates();
+        assert_eq!(world.process_updates(), 0);
+    }
+
+    #[test]
+    fn fluid_flow()
+    {
+        let world = flat_world(&[vec3(0, 0, 0)]);
+
+        let settle = |world: &World|
+        {
+            for _ in 0..64
+            {
+                if world.process_updates() == 0 { return }
+            }
+            panic!("fluid never settled");
+        };
+        let water = |world: &World, x, z| world
+            .get_as::<BlockWater>(vec3(x, 1, z))
+            .map(|water| water.level);
+
+        world.set(vec3(16, 1, 16), BlockWater { level: 0, falling: false }).unwrap();
+        settle(&world);
+
+        // level is the distance from the source
+        for x in 8..=24
+        {
+            for z in 8..=24
+            {
+                let dist = (x - 16i32).abs() + (z - 16i32).abs();
+                let expected = if dist <= 7 { Some(dist as u8) } else { None };
+
+                assert_eq!(water(&world, x, z), expected, "at ({}, {})", x, z);
+            }
+        }
+        assert!(world.get(vec3(16, 2, 16)).unwrap().is::<BlockAir>());
+
+        // lava runs half as far
+        world.set(vec3(4, 1, 4), BlockLava { level: 0, falling: false }).unwrap();
+        settle(&world);
+
+        assert_eq!(world.get_as::<BlockLava>(vec3(7, 1, 4)).map(|lava| lava.level), Some(6));
+        assert!(world.get(vec3(8, 1, 4)).unwrap().is::<BlockAir>());
+
+        // falls off ledges
+        world.set(vec3(16, 4, 16), BlockStone).unwrap();
+        world.set(vec3(16, 5, 16), BlockWater { level: 0, falling: false }).unwrap();
+        settle(&world);
+
+        assert_eq!(world.get_as::<BlockWater>(vec3(17, 5, 16)).map(|water| water.level), Some(1));
+        assert_eq!(world.get_as::<BlockWater>(vec3(17, 4, 16)).map(|water| *water), Some(BlockWater { level: 0, falling: true }));
+
+        // draining
+        world.set(vec3(16, 5, 16), BlockAir).unwrap();
+        world.set(vec3(16, 1, 16), BlockAir).unwrap();
+        settle(&world);
+
+        for x in 8..=24
+        {
+            for z in 8..=24
+            {
+                assert_eq!(water(&world, x, z), None, "at ({}, {})", x, z);
+            }
+        }
+        assert!(world.get(vec3(17, 4, 16)).unwrap().is::<BlockAir>());
+    }
+
+    #[test]
+    fn generate_ptr_blocks()
+    {
+        use crate::world::{ ChunkGenerator, Chunk };
+
+        /// Hides a loot chest in every chunk
+        struct LootGenerator;
+
+        impl ChunkGenerator for LootGenerator
+        {
+            fn generate(&self, pos: Vec3<i32>, chunk: &mut Chunk)
+            {
+                let mut chest = BlockChest::new(Direction::South);
+
+                chest.contents.insert(ItemStack::new("diamond", (pos.x + 1) as u8));
+                chest.name = Some(format!("Loot {}", pos.x));
+                block::Choice::new(chest).place(chunk, vec3(pos.x as usize, 3, 7));
+            }
+        }
+
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockChest>();
+
+        let mut world = World::with_generator(registry, LootGenerator);
+
+        for x in 0..8
+        {
+            world.load_chunk(vec3(x, 0, 0));
+        }
+        world.wait_for_loads();
+
+        for x in 0..8
+        {
+            let chest = world.get_as::<BlockChest>(vec3(x * 32 + x, 3, 7)).unwrap();
+
+            assert_eq!(chest.contents.get(0), Some(&ItemStack::new("diamond", (x + 1) as u8)));
+            assert_eq!(chest.name, Some(format!("Loot {}", x)));
+        }
+        for (_, chunk) in world.chunks()
+        {
+            assert_eq!(chunk.volume_of::<BlockChest>(), 1);
+        }
+    }
+
+    #[test]
+    fn torch()
+    {
+        let world = flat_world(&[vec3(0, 0, 0)]);
+
+        let torch = BlockTorch { attached: Direction::Up };
+        let attached = |world: &World, pos| world.get_as::<BlockTorch>(pos).map(|torch| torch.attached);
+
+        // on the floor, and on the north side of a wall
+        world.set(vec3(8, 1, 8), BlockStone).unwrap();
+        assert_eq!(world.place(vec3(5, 1, 5), Direction::Up, torch), Ok(true));
+        assert_eq!(world.place(vec3(8, 1, 7), Direction::North, torch), Ok(true));
+        assert_eq!(attached(&world, vec3(5, 1, 5)), Some(Direction::Up));
+        assert_eq!(attached(&world, vec3(8, 1, 7)), Some(Direction::North));
+
+        // not on ceilings
+        assert_eq!(world.place(vec3(8, 0, 8), Direction::Down, torch), Ok(false));
+        assert!(world.get(vec3(8, 0, 8)).unwrap().is::<BlockStone>());
+        assert_eq!(world.place(vec3(0, 40, 0), Direction::Up, torch), Err(WorldAccessError::ChunkNotLoaded));
+
+        let block = world.get(vec3(5, 1, 5)).unwrap();
+        assert_eq!(block.light_emission(), 14);
+        assert!(block.collision_boxes().is_empty());
+        drop(block);
+
+        // pops off when its wall is removed, but not when something else changes
+        world.set(vec3(8, 2, 7), BlockStone).unwrap();
+        world.set(vec3(8, 1, 6), BlockAir).unwrap();
+        world.process_updates();
+        assert_eq!(attached(&world, vec3(8, 1, 7)), Some(Direction::North));
+
+        world.set(vec3(8, 1, 8), BlockAir).unwrap();
+        world.process_updates();
+        assert!(world.get(vec3(8, 1, 7)).unwrap().is::<BlockAir>());
+        assert_eq!(attached(&world, vec3(5, 1, 5)), Some(Direction::Up));
+    }
+
+    #[test]
+    fn door()
+    {
+        let world = flat_world(&[vec3(0, 0, 0), vec3(0, 1, 0)]);
+
+        let door = BlockDoor { facing: Direction::North, hinge: Hinge::Left, open: false, half: DoorHalf::Upper };
+        let get = |world: &World, pos| world.get_as::<BlockDoor>(pos).map(|door| *door);
+
+        // within a chunk
+        assert_eq!(world.place(vec3(4, 1, 4), Direction::Up, door), Ok(true));
+        assert_eq!(get(&world, vec3(4, 1, 4)), Some(BlockDoor { half: DoorHalf::Lower, ..door }));
+        assert_eq!(get(&world, vec3(4, 2, 4)), Some(door));
+
+        world.set(vec3(5, 2, 4), BlockStone).unwrap();
+        assert_eq!(world.place(vec3(5, 1, 4), Direction::Up, door), Ok(false));
+        assert!(world.get(vec3(5, 1, 4)).unwrap().is::<BlockAir>());
+
+        assert_eq!(BlockDoor::toggle_open(&world, vec3(4, 2, 4)), Ok(true));
+        assert!(get(&world, vec3(4, 1, 4)).unwrap().open);
+        assert!(get(&world, vec3(4, 2, 4)).unwrap().open);
+        assert_ne!(world.get(vec3(4, 1, 4)).unwrap().collision_boxes(), BlockDoor { open: false, ..door }.collision_boxes());
+        assert_eq!(BlockDoor::toggle_open(&world, vec3(5, 2, 4)), Ok(false));
+
+        world.break_block(vec3(4, 1, 4)).unwrap();
+        assert!(world.get(vec3(4, 1, 4)).unwrap().is::<BlockAir>());
+        assert!(world.get(vec3(4, 2, 4)).unwrap().is::<BlockAir>());
+
+        // across the vertical seam
+        assert_eq!(world.place(vec3(8, 31, 8), Direction::Up, door), Ok(true));
+        assert_eq!(get(&world, vec3(8, 32, 8)), Some(door));
+
+        let upper = world.get_chunk_arc(vec3(0, 1, 0)).unwrap();
+        let lock = upper.write();
+
+        assert_eq!(BlockDoor::toggle_open(&world, vec3(8, 31, 8)), Err(WorldAccessError::WouldBlock));
+        assert_eq!(world.set_atomic(vec![
+            (vec3(8, 31, 8), block::Choice::new(BlockStone)),
+            (vec3(8, 32, 8), block::Choice::new(BlockStone)),
+        ]), Err(WorldAccessError::WouldBlock));
+        drop(lock);
+        assert!(!get(&world, vec3(8, 31, 8)).unwrap().open);
+
+        assert_eq!(BlockDoor::toggle_open(&world, vec3(8, 31, 8)), Ok(true));
+        assert!(get(&world, vec3(8, 32, 8)).unwrap().open);
+
+        world.break_block(vec3(8, 32, 8)).unwrap();
+        assert!(world.get(vec3(8, 31, 8)).unwrap().is::<BlockAir>());
+        assert!(world.get(vec3(8, 32, 8)).unwrap().is::<BlockAir>());
+
+        // nothing known above
+        assert_eq!(world.place(vec3(8, 63, 8), Direction::Up, door), Ok(false));
+    }
+
+    #[test]
+    fn dimensions()
+    {
+        let mut registry = block::Registry::default();
+
+        register_all(&mut registry);
+
+        let nether = DimensionId("nether");
+        let mut dims = Dimensions::new(registry);
+
+        dims.insert(DimensionId::OVERWORLD, FlatGenerator::new(vec![(block::Choice::new(BlockGrass { snowy: false }), 4)]))
+            .load_chunk(vec3(0, 0, 0));
+        dims.insert(nether, FlatGenerator::new(vec![(block::Choice::new(BlockLava { level: 0, falling: false }), 4)]))
+            .load_chunk(vec3(0, 0, 0));
+
+        for id in [DimensionId::OVERWORLD, nether]
+        {
+            dims.world(id).unwrap().wait_for_loads();
+        }
+
+        let overworld = dims.world(DimensionId::OVERWORLD).unwrap();
+        let nether = dims.world(nether).unwrap();
+
+        assert!(overworld.get(vec3(1, 2, 3)).unwrap().is::<BlockGrass>());
+        assert!(nether.get(vec3(1, 2, 3)).unwrap().is::<BlockLava>());
+        assert!(std::sync::Arc::ptr_eq(overworld.registry(), nether.registry()));
+
+        // independent chunks
+        overworld.set(vec3(1, 2, 3), BlockStone).unwrap();
+        assert!(nether.get(vec3(1, 2, 3)).unwrap().is::<BlockLava>());
+        assert!(dims.world(DimensionId("end")).is_none());
+        assert_eq!(dims.ids().count(), 2);
+    }
+
+    #[test]
+    fn furnace()
+    {
+        let world = flat_world(&[vec3(0, 0, 0)]);
+
+        let pos = vec3(4, 4, 4);
+        let mut furnace = BlockFurnace::new(Direction::South);
+
+        assert_eq!(furnace.insert_input(ItemStack::new("coal", 1)), Some(ItemStack::new("coal", 1)));
+        assert_eq!(furnace.insert_input(ItemStack::new("cobblestone", 60)), None);
+        assert_eq!(furnace.insert_input(ItemStack::new("cobblestone", 6)), Some(ItemStack::new("cobblestone", 2)));
+        assert_eq!(furnace.insert_fuel(ItemStack::new("cobblestone", 1)), Some(ItemStack::new("cobblestone", 1)));
+        assert_eq!(furnace.insert_fuel(ItemStack::new("wooden_planks", 1)), None);
+        furnace.input = Some(ItemStack::new("cobblestone", 1));
+
+        // Empty stacks never end up in a slot
+        let mut empty = BlockFurnace::new(Direction::South);
+
+        assert_eq!(empty.insert_input(ItemStack { id: "iron_ore".into(), count: 0 }), None);
+        assert_eq!(empty.insert_fuel(ItemStack { id: "coal".into(), count: 0 }), None);
+        assert_eq!(empty.input, None);
+        assert_eq!(empty.fuel, None);
+        assert_eq!(empty.insert_input(ItemStack { id: "iron_ore".into(), count: 70 }), Some(ItemStack::new("iron_ore", 6)));
+        assert_eq!(empty.input, Some(ItemStack::new("iron_ore", ItemStack::MAX_COUNT)));
+
+        world.set(pos, furnace).unwrap();
+        world.schedule_tick(pos, 1);
+
+        let furnace = |world: &World| world.get_as::<BlockFurnace>(pos).unwrap().clone();
+
+        // burns a plank on the first tick
+        assert_eq!(world.tick(usize::MAX), 1);
+        assert!(furnace(&world).lit);
+        assert_eq!(furnace(&world).fuel, None);
+        assert_eq!(world.get(pos).unwrap().light_emission(), 13);
+
+        for _ in 1..BlockFurnace::SMELT_TICKS - 1
+        {
+            world.tick(usize::MAX);
+        }
+        assert_eq!(furnace(&world).output, None);
+        assert_eq!(furnace(&world).progress, BlockFurnace::SMELT_TICKS - 1);
+
+        world.tick(usize::MAX);
+        assert_eq!(furnace(&world).input, None);
+        assert_eq!(furnace(&world).output, Some(ItemStack::new("stone", 1)));
+        assert!(furnace(&world).lit);
+
+        // the plank keeps burning with nothing left to smelt, then goes out
+        while world.current_tick() < 299
+        {
+            world.tick(usize::MAX);
+        }
+        assert!(furnace(&world).lit);
+        world.tick(usize::MAX);
+        assert!(!furnace(&world).lit);
+        assert_eq!(world.scheduled_tick_count(), 0);
+        assert_eq!(world.tick(usize::MAX), 0);
+
+        // persists
+        let furnace = furnace(&world);
+        let mut bytes = Vec::new();
+
+        block::Object::serialize(&furnace, &mut bytes);
+        assert_eq!(BlockFurnace::deserialize(&bytes), Some(furnace));
+        assert_eq!(BlockFurnace::deserialize(&bytes[..bytes.len() - 1]), None);
+    }
+
+    #[test]
+    fn chunk_events()
+    {
+        let mut world = World::with_generator(block::Registry::default(), FlatGenerator::new(vec![]));
+
+        let a = world.subscribe_chunk_events();
+        let b = world.subscribe_chunk_events();
+        let dropped = world.subscribe_chunk_events();
+
+        drop(dropped);
+
+        let chunks = [vec3(0, 0, 0), vec3(-1, 2, 0), vec3(5, 0, 5)];
+        for &pos in &chunks
+        {
+            world.load_chunk(pos);
+        }
+        world.wait_for_loads();
+
+        for rx in &[&a, &b]
+        {
+            let mut events = rx.try_iter().collect::<Vec<_>>();
+            events.sort_by_key(|event| match event
+            {
+                ChunkEvent::Generated(pos) => (pos.x, pos.y, pos.z),
+                _ => panic!("unexpected {:?}", event),
+            });
+
+            assert_eq!(events, vec![
+                ChunkEvent::Generated(vec3(-1, 2, 0)),
+                ChunkEvent::Generated(vec3(0, 0, 0)),
+                ChunkEvent::Generated(vec3(5, 0, 5)),
+            ]);
+        }
+
+        // late subscribers only get what happens next
+        let c = world.subscribe_chunk_events();
+
+        world.unload_chunk(vec3(5, 0, 5)).unwrap();
+
+        for rx in &[&a, &b, &c]
+        {
+            assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![ChunkEvent::Unloaded(vec3(5, 0, 5))]);
+        }
+    }
+
+    #[test]
+    fn unload_chunk()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+
+        let mut world = World::new(registry);
+
+        world.load_chunk(vec3(0, 0, 0));
+        world.load_chunk(vec3(1, 0, 0));
+        world.load_chunk(vec3(2, 0, 0));
+
+        world.wait_for_loads();
+
+        world.set(vec3(5, 6, 7), BlockWoodenPlanks { variant: WoodVariant::Oak }).unwrap();
+
+        let chunk = world.unload_chunk(vec3(0, 0, 0)).unwrap().unwrap();
+
+        assert_eq!(chunk.pos(), vec3(0, 0, 0));
+        assert!(chunk[(5, 6, 7)].is::<BlockWoodenPlanks>());
+        assert!(world.get(vec3(5, 6, 7)).is_none());
+        assert!(world.get_chunk(vec3(0, 0, 0)).is_none());
+
+        // nothing left to unload
+        assert!(matches!(world.unload_chunk(vec3(0, 0, 0)), Ok(None)));
+
+        // borrowed externally
+        let arc = world.get_chunk_arc(vec3(1, 0, 0)).unwrap();
+        let lock = arc.read();
+
+        assert_eq!(world.unload_chunk(vec3(1, 0, 0)).err(), Some(UnloadError::Locked));
+        drop(lock);
+
+        // shared externally, removed but not handed back
+        assert!(matches!(world.unload_chunk(vec3(1, 0, 0)), Ok(None)));
+        assert!(world.get_chunk(vec3(1, 0, 0)).is_none());
+        assert_eq!(arc.read().pos(), vec3(1, 0, 0));
+
+        // bulk
+        world.load_chunk(vec3(3, 0, 0));
+        world.wait_for_loads();
+
+        let mut unloaded = world
+            .unload_where(|pos| pos.x >= 2)
+            .into_iter()
+            .map(|chunk| chunk.pos())
+            .collect::<Vec<_>>();
+        unloaded.sort_by_key(|pos| pos.x);
+
+        assert_eq!(unloaded, vec![vec3(2, 0, 0), vec3(3, 0, 0)]);
+    }
+
+    #[test]
+    fn flat_generator()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+        registry.register::<BlockWoodenSlab>();
+
+        let planks = |variant| block::Choice::new(BlockWoodenPlanks { variant });
+        let generator = FlatGenerator::new(vec![
+            (planks(WoodVariant::Oak), 1),
+            (planks(WoodVariant::Birch), 3),
+            (planks(WoodVariant::DarkOak), 30),
+        ]);
+        let mut world = World::with_generator(registry, generator);
+
+        world.load_chunk(vec3(0, 0, 0));
+        world.load_chunk(vec3(0, 1, 0));
+        world.load_chunk(vec3(0, -1, 0));
+        world.wait_for_loads();
+
+        let variant = |pos| world
+            .get(pos)
+            .and_then(|b| b.cast::<BlockWoodenPlanks>().map(|p| p.variant));
+
+        for &(x, z) in &[(0, 0), (5, 31), (31, 17)]
+        {
+            assert_eq!(variant(vec3(x, -1, z)), None);
+            assert_eq!(variant(vec3(x, 0, z)), Some(WoodVariant::Oak));
+            assert_eq!(variant(vec3(x, 1, z)), Some(WoodVariant::Birch));
+            assert_eq!(variant(vec3(x, 3, z)), Some(WoodVariant::Birch));
+            assert_eq!(variant(vec3(x, 4, z)), Some(WoodVariant::DarkOak));
+            assert_eq!(variant(vec3(x, 33, z)), Some(WoodVariant::DarkOak));
+            assert_eq!(variant(vec3(x, 34, z)), None);
+        }
+        assert!(world.get(vec3(0, 34, 0)).unwrap().is::<BlockAir>());
+        assert!(world.get(vec3(0, -32, 0)).unwrap().is::<BlockAir>());
+    }
+
+    #[test]
+    fn cached_reader()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+
+        let mut world = World::with_generator(registry, FlatGenerator::new(vec![]));
+
+        world.load_chunk(vec3(0, 0, 0));
+        world.load_chunk(vec3(1, 0, 0));
+        world.wait_for_loads();
+
+        world.set(vec3(31, 2, 0), BlockWoodenPlanks { variant: WoodVariant::Oak }).unwrap();
+        world.set(vec3(32, 2, 0), BlockWoodenPlanks { variant: WoodVariant::Birch }).unwrap();
+
+        let mut reader = world.cached_reader();
+        let variant = |reader: &mut crate::world::CachedReader<'_>, pos| reader
+            .get(pos)
+            .and_then(|b| b.cast::<BlockWoodenPlanks>().map(|p| p.variant));
+
+        assert_eq!(reader.cached_chunk(), None);
+        assert_eq!(variant(&mut reader, vec3(31, 2, 0)), Some(WoodVariant::Oak));
+        assert_eq!(reader.cached_chunk(), Some(vec3(0, 0, 0)));
+        assert!(reader.get(vec3(0, 0, 0)).unwrap().is::<BlockAir>());
+
+        // Crossing into the next chunk releases the last
+        assert_eq!(variant(&mut reader, vec3(32, 2, 0)), Some(WoodVariant::Birch));
+        assert_eq!(reader.cached_chunk(), Some(vec3(1, 0, 0)));
+        assert!(world.try_get_mut(vec3(0, 0, 0)).is_ok());
+        assert_eq!(world.try_get_mut(vec3(32, 0, 0)).err(), Some(WorldAccessError::WouldBlock));
+
+        assert!(reader.get(vec3(-1, 0, 0)).is_none());
+        assert_eq!(reader.cached_chunk(), None);
+        assert!(world.try_get_mut(vec3(32, 0, 0)).is_ok());
+    }
+
+    /// `cargo test --release -- --ignored --nocapture cached_reader_bench`
+    #[test]
+    #[ignore]
+    fn cached_reader_bench()
+    {
+        use std::time::Instant;
+
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockStone>();
+
+        let mut world = World::with_generator(registry, FlatGenerator::new(vec![(block::Choice::new(BlockStone), 16)]));
+
+        world.load_chunk(vec3(0, 0, 0));
+        world.wait_for_loads();
+
+        let scan = || (0..32 * 32 * 32).map(|i| vec3(i % 32, (i / 32) % 32, i / (32 * 32)));
+
+        let start = Instant::now();
+        let mut uncached = 0;
+        for _ in 0..10
+        {
+            uncached += scan().filter(|&pos| world.get(pos).unwrap().is::<BlockStone>()).count();
+        }
+        println!("get: {:?} / pass", start.elapsed() / 10);
+
+        let start = Instant::now();
+        let mut cached = 0;
+        for _ in 0..10
+        {
+            let mut reader = world.cached_reader();
+
+            cached += scan().filter(|&pos| reader.get(pos).unwrap().is::<BlockStone>()).count();
+        }
+        println!("cached_reader.get: {:?} / pass", start.elapsed() / 10);
+
+        assert_eq!(uncached, cached);
+        assert_eq!(cached, 10 * 16 * 32 * 32);
+    }
+
+    #[test]
+    fn set_generator()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+
+        let planks = |variant| block::Choice::new(BlockWoodenPlanks { variant });
+        let mut world = World::with_generator(registry, FlatGenerator::new(vec![(planks(WoodVariant::Oak), 1)]));
+
+        world.load_chunk(vec3(0, 0, 0));
+        world.set_generator(std::sync::Arc::new(FlatGenerator::new(vec![(planks(WoodVariant::Spruce), 2)])));
+        world.load_chunk(vec3(1, 0, 0));
+        world.wait_for_loads();
+
+        let variant = |pos| world
+            .get(pos)
+            .and_then(|b| b.cast::<BlockWoodenPlanks>().map(|p| p.variant));
+
+        // Queued before the swap
+        assert_eq!(variant(vec3(0, 0, 0)), Some(WoodVariant::Oak));
+        assert_eq!(variant(vec3(0, 1, 0)), None);
+        // After
+        assert_eq!(variant(vec3(32, 0, 0)), Some(WoodVariant::Spruce));
+        assert_eq!(variant(vec3(32, 1, 0)), Some(WoodVariant::Spruce));
+        assert_ne!(world.get_chunk(vec3(0, 0, 0)).unwrap().checksum(), world.get_chunk(vec3(1, 0, 0)).unwrap().checksum());
+    }
+
+    #[test]
+    fn seeded_generation()
+    {
+        let registry = ||
+        {
+            let mut registry = block::Registry::default();
+
+            register_all(&mut registry);
+            registry
+        };
+        let generate = |seed, pos|
+        {
+            let mut world = World::with_seed(registry(), seed);
+
+            world.load_chunk(pos);
+            world.wait_for_loads();
+
+            assert_eq!(world.seed(), seed);
+            world.unload_chunk(pos).unwrap().unwrap()
+        };
+
+        assert_eq!(generate(42, vec3(0, 0, 0)), generate(42, vec3(0, 0, 0)));
+        assert_eq!(generate(42, vec3(-3, 0, 5)), generate(42, vec3(-3, 0, 5)));
+        assert_eq!(generate(7, vec3(0, -1, 0)), generate(7, vec3(0, -1, 0)));
+        assert!(generate(42, vec3(0, 0, 0)) != generate(43, vec3(0, 0, 0)));
+        assert!(generate(42, vec3(0, 0, 0)) != generate(42, vec3(1, 0, 0)));
+    }
+
+    #[test]
+    fn negative_coordinates()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockChest>();
+        registry.register::<BlockWoodenPlanks>();
+        registry.register::<BlockWoodenSlab>();
+
+        let mut world = World::new(registry);
+
+        let chunks = [vec3(-1, -2, -2), vec3(-1, 0, 0), vec3(0, 0, 0)];
+
+        for &pos in &chunks
+        {
+            world.load_chunk(pos);
+        }
+
+        // wait for chunk(s) to load
+        world.wait_for_loads();
+
+        // (-1, -33, -64) is in chunk (-1, -2, -2) at (31, 31, 0)
+        world.set(vec3(-1, -33, -64), BlockWoodenPlanks { variant: WoodVariant::Birch }).unwrap();
+        world.set(vec3(-1, 0, 0), BlockWoodenPlanks { variant: WoodVariant::Spruce }).unwrap();
+        world.set(vec3(0, 0, 0), BlockWoodenPlanks { variant: WoodVariant::Oak }).unwrap();
+
+        let planks = |pos| world
+            .get(pos)
+            .and_then(|b| b.cast::<BlockWoodenPlanks>().map(|p| p.variant));
+
+        assert_eq!(planks(vec3(-1, -33, -64)), Some(WoodVariant::Birch));
+        assert_eq!(planks(vec3(-1, 0, 0)), Some(WoodVariant::Spruce));
+        assert_eq!(planks(vec3(0, 0, 0)), Some(WoodVariant::Oak));
+
+        let chunk = world.get_chunk(vec3(-1, -2, -2)).unwrap();
+        assert!(chunk[(31, 31, 0)].is::<BlockWoodenPlanks>());
+        drop(chunk);
+
+        // chunk (0, -2, -2) isn't loaded
+        assert!(world.get(vec3(0, -33, -64)).is_none());
+        assert!(world.set(vec3(0, -33, -64), BlockAir).is_err());
+    }
+
+    #[test]
+    fn set_many()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+
+        let mut world = World::with_generator(registry, FlatGenerator::new(vec![]));
+
+        world.load_chunk(vec3(-1, 0, 0));
+        world.load_chunk(vec3(0, 0, 0));
+        world.wait_for_loads();
+
+        let oak = block::Choice::new(BlockWoodenPlanks { variant: WoodVariant::Oak });
+        let birch = block::Choice::new(BlockWoodenPlanks { variant: WoodVariant::Birch });
+
+        // a wall spanning chunks -1, 0 and 1(not loaded) along x
+        let mut edits = Vec::new();
+        for x in -16..80
+        {
+            for y in 0..4
+            {
+                edits.push((vec3(x, y, 5), oak.clone()));
+            }
+        }
+        // later edits to the same block win
+        edits.push((vec3(0, 0, 5), birch.clone()));
+
+        let result = world.set_many(edits);
+
+        assert_eq!(result.applied, 48 * 4 + 1);
+        assert_eq!(result.failed, vec![BatchFailure
+        {
+            chunk: vec3(1, 0, 0),
+            error: WorldAccessError::ChunkNotLoaded,
+            edits: (32..64)
+                .flat_map(|x| (0..4).map(move |y| vec3(x, y, 5)))
+                .collect(),
+        }, BatchFailure
+        {
+            chunk: vec3(2, 0, 0),
+            error: WorldAccessError::ChunkNotLoaded,
+            edits: (64..80)
+                .flat_map(|x| (0..4).map(move |y| vec3(x, y, 5)))
+                .collect(),
+        }]);
+
+        let variant = |pos| world
+            .get(pos)
+            .and_then(|b| b.cast::<BlockWoodenPlanks>().map(|p| p.variant));
+
+        for x in -16..32
+        {
+            for y in 0..4
+            {
+                let expected = if (x, y) == (0, 0) { WoodVariant::Birch } else { WoodVariant::Oak };
+
+                assert_eq!(variant(vec3(x, y, 5)), Some(expected));
+            }
+            assert_eq!(variant(vec3(x, 4, 5)), None);
+        }
+
+        // locked chunks
+        let lock = world.get_chunk(vec3(0, 0, 0)).unwrap();
+        let result = world.set_many(vec![(vec3(-1, 0, 0), birch.clone()), (vec3(1, 0, 0), birch)]);
+
+        assert_eq!(result.applied, 1);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].error, WorldAccessError::WouldBlock);
+        assert_eq!(result.failed[0].edits, vec![vec3(1, 0, 0)]);
+        drop(lock);
+    }
+
+    #[test]
+    fn fill_region()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+
+        let mut world = World::with_generator(registry, FlatGenerator::new(vec![]));
+
+        for &pos in &[vec3(-1, 0, -1), vec3(-1, 0, 0), vec3(0, 0, -1), vec3(0, 0, 0)]
+        {
+            world.load_chunk(pos);
+        }
+        world.wait_for_loads();
+
+        let oak = BlockWoodenPlanks { variant: WoodVariant::Oak };
+
+        // straddles four chunks, corners given out of order
+        let report = world.fill_region(vec3(5, 3, 9), vec3(-7, 1, -4), oak);
+
+        assert_eq!(report.written, 13 * 3 * 14);
+        assert!(report.skipped.is_empty());
+
+        let mut count = 0;
+        for x in -10..10
+        {
+            for y in 0..5
+            {
+                for z in -10..12
+                {
+                    let planks = world.get(vec3(x, y, z)).unwrap().is::<BlockWoodenPlanks>();
+                    let inside = (-7..=5).contains(&x) && (1..=3).contains(&y) && (-4..=9).contains(&z);
+
+                    assert_eq!(planks, inside, "{}, {}, {}", x, y, z);
+                    count += planks as usize;
+                }
+            }
+        }
+        assert_eq!(count, report.written);
+
+        // degenerate boxes
+        assert_eq!(world.fill_region(vec3(-3, 20, -3), vec3(-3, 20, -3), oak).written, 1);
+        assert_eq!(world.fill_region(vec3(-40, 10, 0), vec3(40, 10, 0), oak), FillReport
+        {
+            written: 64,
+            skipped: vec![(vec3(-2, 0, 0), WorldAccessError::ChunkNotLoaded), (vec3(1, 0, 0), WorldAccessError::ChunkNotLoaded)],
+        });
+    }
+
+    #[test]
+    fn neighbors()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+        registry.register::<BlockChest>();
+
+        let mut world = World::with_generator(registry, FlatGenerator::new(vec![]));
+
+        world.load_chunk(vec3(0, 0, 0));
+        world.load_chunk(vec3(1, 0, 0));
+        world.load_chunk(vec3(0, -1, 0));
+        world.wait_for_loads();
+
+        // straddling the boundary between chunks (0, 0, 0) and (1, 0, 0)
+        world.set(vec3(31, 0, 0), BlockWoodenPlanks { variant: WoodVariant::Oak }).unwrap();
+        world.set(vec3(32, 0, 0), BlockWoodenPlanks { variant: WoodVariant::Birch }).unwrap();
+        world.set(vec3(31, -1, 0), BlockChest::new(Direction::North)).unwrap();
+
+        let neighbors = world.neighbors(vec3(31, 0, 0));
+        let east = neighbors[Direction::East as usize].unwrap();
+
+        assert!(east.is::<BlockWoodenPlanks>());
+        assert_eq!(east, world.block_at(vec3(32, 0, 0)).unwrap());
+        assert_ne!(east.state, world.block_at(vec3(31, 0, 0)).unwrap().state);
+        assert!(east.solid);
+        assert_eq!(east.occludes, [true; 6]);
+
+        let down = neighbors[Direction::Down as usize].unwrap();
+
+        assert!(down.is::<BlockChest>());
+        assert_eq!(down.state, None);
+        assert_eq!(down.occludes, [false; 6]);
+
+        assert!(neighbors[Direction::West as usize].unwrap().is::<BlockAir>());
+        assert!(!neighbors[Direction::West as usize].unwrap().solid);
+        assert!(neighbors[Direction::Up as usize].unwrap().is::<BlockAir>());
+        assert!(neighbors[Direction::South as usize].unwrap().is::<BlockAir>());
+        // chunk (0, 0, -1) isn't loaded
+        assert!(neighbors[Direction::North as usize].is_none());
+
+        // crossing back
+        let neighbors = world.neighbors(vec3(32, 0, 0));
+
+        assert!(neighbors[Direction::West as usize].unwrap().is::<BlockWoodenPlanks>());
+        assert!(neighbors[Direction::Down as usize].is_none());
+    }
+
+    #[test]
+    fn set_deferred()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+
+        let mut world = World::with_generator(registry, FlatGenerator::new(vec![]));
+
+        world.load_chunk(vec3(0, 0, 0));
+        world.wait_for_loads();
+
+        let planks = |variant| BlockWoodenPlanks { variant };
+        let variant = |world: &World, pos| world
+            .get(pos)
+            .and_then(|b| b.cast::<BlockWoodenPlanks>().map(|p| p.variant));
+
+        // loaded, applied right away
+        world.set_deferred(vec3(1, 1, 1), planks(WoodVariant::Oak)).unwrap();
+
+        assert_eq!(world.pending_edit_count(), 0);
+        assert_eq!(variant(&world, vec3(1, 1, 1)), Some(WoodVariant::Oak));
+
+        // canopy overhanging into unloaded chunks
+        world.set_deferred(vec3(-1, 5, 0), planks(WoodVariant::Birch)).unwrap();
+        world.set_deferred(vec3(-2, 5, 0), planks(WoodVariant::Birch)).unwrap();
+        world.set_deferred(vec3(-1, 5, 0), planks(WoodVariant::Spruce)).unwrap();
+        world.set_deferred(vec3(40, 5, 0), planks(WoodVariant::Acacia)).unwrap();
+
+        assert_eq!(world.pending_edit_count(), 4);
+        assert!(world.get(vec3(-1, 5, 0)).is_none());
+
+        let events = world.subscribe_chunk_events();
+
+        world.load_chunk(vec3(-1, 0, 0));
+        world.wait_for_loads();
+
+        // applied before announcing the chunk
+        assert_eq!(events.try_recv(), Ok(ChunkEvent::Generated(vec3(-1, 0, 0))));
+        assert_eq!(world.pending_edit_count(), 1);
+        assert_eq!(variant(&world, vec3(-1, 5, 0)), Some(WoodVariant::Spruce));
+        assert_eq!(variant(&world, vec3(-2, 5, 0)), Some(WoodVariant::Birch));
+
+        world.load_chunk(vec3(1, 0, 0));
+        world.wait_for_loads();
+
+        assert_eq!(world.pending_edit_count(), 0);
+        assert_eq!(variant(&world, vec3(40, 5, 0)), Some(WoodVariant::Acacia));
+
+        // over capacity, the oldest edits are dropped
+        world.set_pending_edit_cap(2);
+        world.set_deferred(vec3(0, 40, 0), planks(WoodVariant::Oak)).unwrap();
+        world.set_deferred(vec3(1, 100, 0), planks(WoodVariant::Birch)).unwrap();
+        world.set_deferred(vec3(2, 40, 0), planks(WoodVariant::Spruce)).unwrap();
+
+        assert_eq!(world.pending_edit_count(), 2);
+
+        world.load_chunk(vec3(0, 1, 0));
+        world.wait_for_loads();
+
+        assert_eq!(variant(&world, vec3(0, 40, 0)), None);
+        assert_eq!(variant(&world, vec3(2, 40, 0)), Some(WoodVariant::Spruce));
+        assert_eq!(world.pending_edit_count(), 1);
+
+        world.set_pending_edit_cap(0);
+        assert_eq!(world.pending_edit_count(), 0);
+
+        // loaded but locked
+        let lock = world.get_chunk(vec3(0, 0, 0)).unwrap();
+        assert_eq!(world.set_deferred(vec3(0, 0, 0), BlockAir), Err(WorldAccessError::WouldBlock));
+        drop(lock);
+    }
+
+    #[test]
+    fn auto_load()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockStone>();
+
+        let mut world = World::with_generator(registry, FlatGenerator::new(vec![(block::Choice::new(BlockStone), 1)]));
+
+        // off by default
+        assert_eq!(world.set(vec3(40, 5, 0), BlockStone), Err(WorldAccessError::ChunkNotLoaded));
+        assert_eq!(world.load_requested(), 0);
+
+        world.set_auto_load(true);
+        world.set(vec3(40, 5, 0), BlockStone).unwrap();
+        world.set(vec3(41, 0, 0), BlockAir).unwrap();
+        world.set(vec3(-1, 5, 0), BlockStone).unwrap();
+        assert!(world.get(vec3(40, 5, 0)).is_none());
+
+        assert_eq!(world.load_requested(), 2);
+        assert_eq!(world.load_requested(), 0);
+        world.wait_for_loads();
+
+        assert!(world.get(vec3(40, 5, 0)).unwrap().is::<BlockStone>());
+        assert!(world.get(vec3(41, 0, 0)).unwrap().is::<BlockAir>());
+        assert!(world.get(vec3(40, 0, 0)).unwrap().is::<BlockStone>());
+        assert!(world.get(vec3(-1, 5, 0)).unwrap().is::<BlockStone>());
+        assert_eq!(world.pending_edit_count(), 0);
+
+        // loaded chunks are written to right away
+        world.set(vec3(40, 6, 0), BlockStone).unwrap();
+        assert!(world.get(vec3(40, 6, 0)).unwrap().is::<BlockStone>());
+        assert_eq!(world.load_requested(), 0);
+    }
+
+    #[test]
+    fn blocking_access()
+    {
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+
+        let mut world = World::with_generator(registry, FlatGenerator::new(vec![]));
+
+        world.load_chunk(vec3(0, 0, 0));
+        world.wait_for_loads();
+
+        let pos = vec3(1, 2, 3);
+        let oak = BlockWoodenPlanks { variant: WoodVariant::Oak };
+
+        assert!(world.try_get(pos).is_ok());
+        assert!(matches!(world.try_get(vec3(0, 32, 0)), Err(WorldAccessError::ChunkNotLoaded)));
+        assert!(world.get(vec3(0, 32, 0)).is_none());
+        assert_eq!(world.set(vec3(0, 32, 0), oak), Err(WorldAccessError::ChunkNotLoaded));
+
+        // hold a write guard from another thread
+        let chunk = world.get_chunk_arc(vec3(0, 0, 0)).unwrap();
+        let (locked, rx) = mpsc::channel();
+        let handle = std::thread::spawn(move ||
+        {
+            let _guard = chunk.write();
+
+            locked.send(()).unwrap();
+            std::thread::sleep(Duration::from_millis(50));
+        });
+        rx.recv().unwrap();
+
+        assert!(matches!(world.try_get(pos), Err(WorldAccessError::WouldBlock)));
+        assert!(matches!(world.try_get_mut(pos), Err(WorldAccessError::WouldBlock)));
+        assert_eq!(world.try_set(pos, oak), Err(WorldAccessError::WouldBlock));
+
+        // waits for the guard to be released
+        assert!(world.get(pos).unwrap().is::<BlockAir>());
+        handle.join().unwrap();
+
+        world.set(pos, oak).unwrap();
+        assert!(world.try_get(pos).unwrap().is::<BlockWoodenPlanks>());
+        assert!(world.get_mut(pos).is_some());
+    }
+
+    #[test]
+    fn trim()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+
+        let mut world = World::with_generator(registry, FlatGenerator::new(vec![]));
+
+        for x in 0..6
+        {
+            world.load_chunk(vec3(x, 0, 0));
+        }
+        world.wait_for_loads();
+
+        // touch a subset
+        assert!(world.get(vec3(32, 0, 0)).is_some());
+        world.set(vec3(4 * 32, 0, 0), BlockWoodenPlanks { variant: WoodVariant::Oak }).unwrap();
+
+        // under the limit
+        assert!(world.trim(6).is_empty());
+
+        // untouched, but held elsewhere
+        let arc = world.get_chunk_arc(vec3(0, 0, 0)).unwrap();
+        let unloaded = world.trim(3);
+
+        let mut positions = unloaded
+            .iter()
+            .map(|chunk| chunk.pos().x)
+            .collect::<Vec<_>>();
+        positions.sort_unstable();
+
+        assert_eq!(positions, vec![2, 3, 5]);
+        assert_eq!(world.chunk_positions(), vec![vec3(0, 0, 0), vec3(1, 0, 0), vec3(4, 0, 0)]);
+
+        // least recently used goes first
+        drop(arc);
+        world.get(vec3(0, 0, 0)).unwrap();
+
+        assert_eq!(world.trim(2).len(), 1);
+        assert_eq!(world.chunk_positions(), vec![vec3(0, 0, 0), vec3(4, 0, 0)]);
+    }
+
+    #[test]
+    fn get_as()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+        registry.register::<BlockWoodenSlab>();
+
+        let mut world = World::with_generator(registry, FlatGenerator::new(vec![]));
+
+        world.load_chunk(vec3(0, 0, 0));
+        world.wait_for_loads();
+
+        let slab = vec3(1, 2, 3);
+        let planks = vec3(4, 5, 6);
+
+        world.set(slab, BlockWoodenSlab { facing: Direction::Down, variant: WoodVariant::Oak }).unwrap();
+        world.set(planks, BlockWoodenPlanks { variant: WoodVariant::Oak }).unwrap();
+
+        assert_eq!(world.get_as::<BlockWoodenSlab>(slab).unwrap().variant, WoodVariant::Oak);
+        assert!(world.get_as::<BlockWoodenPlanks>(slab).is_none());
+        assert!(world.get_mut_as::<BlockWoodenSlab>(planks).is_none());
+        assert!(world.get_as::<BlockAir>(vec3(0, 32, 0)).is_none());
+
+        world.get_mut_as::<BlockWoodenSlab>(slab).unwrap().variant = WoodVariant::Jungle;
+        world.get_mut_as::<BlockWoodenPlanks>(planks).unwrap().variant = WoodVariant::DarkOak;
+
+        let slab = world.get(slab).unwrap();
+        let slab = slab.cast::<BlockWoodenSlab>().unwrap();
+        assert_eq!(slab.variant, WoodVariant::Jungle);
+        assert_eq!(slab.facing, Direction::Down);
+
+        let planks = world.get(planks).unwrap();
+        assert_eq!(planks.cast::<BlockWoodenPlanks>().unwrap().variant, WoodVariant::DarkOak);
+    }
+
+    #[test]
+    fn get_mut_persists()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+        registry.register::<BlockWoodenSlab>();
+
+        let mut world = World::with_generator(registry, FlatGenerator::new(vec![]));
+
+        world.load_chunk(vec3(0, 0, 0));
+        world.wait_for_loads();
+
+        let slab = vec3(7, 8, 9);
+        let planks = vec3(7, 8, 10);
+
+        world.set(slab, BlockWoodenSlab { facing: Direction::Up, variant: WoodVariant::Oak }).unwrap();
+        world.set(planks, BlockWoodenPlanks { variant: WoodVariant::Oak }).unwrap();
+
+        // `Ptr` block
+        {
+            let mut guard = world.get_mut(slab).unwrap();
+            let mut block = guard.cast_mut::<BlockWoodenSlab>().unwrap();
+
+            block.variant = WoodVariant::Acacia;
+        }
+        // `Val` block, whose state is re-packed when the `RefMut` is dropped
+        {
+            let mut guard = world.get_mut(planks).unwrap();
+            let mut block = guard.cast_mut::<BlockWoodenPlanks>().unwrap();
+
+            block.variant = WoodVariant::Spruce;
+        }
+
+        let block = world.get(slab).unwrap();
+        assert_eq!(*block.cast::<BlockWoodenSlab>().unwrap(), BlockWoodenSlab { facing: Direction::Up, variant: WoodVariant::Acacia });
+        drop(block);
+
+        let block = world.get(planks).unwrap();
+        assert_eq!(*block.cast::<BlockWoodenPlanks>().unwrap(), BlockWoodenPlanks { variant: WoodVariant::Spruce });
+        drop(block);
+
+        // the same through the chunk
+        let chunk = world.get_chunk(vec3(0, 0, 0)).unwrap();
+        assert_eq!(chunk[planks.map(|n| n as usize)].cast::<BlockWoodenPlanks>().unwrap().variant, WoodVariant::Spruce);
+    }
+
+    #[test]
+    fn compare_and_set()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+
+        let mut world = World::with_generator(registry, FlatGenerator::new(vec![]));
+
+        world.load_chunk(vec3(0, 0, 0));
+        world.wait_for_loads();
+
+        let pos = vec3(1, 2, 3);
+        let oak = BlockWoodenPlanks { variant: WoodVariant::Oak };
+        let birch = BlockWoodenPlanks { variant: WoodVariant::Birch };
+
+        // matches
+        assert_eq!(world.compare_and_set(pos, BlockAir, oak), Ok(true));
+        assert_eq!(world.get(pos).unwrap().cast::<BlockWoodenPlanks>().unwrap().variant, WoodVariant::Oak);
+
+        // block changed since, so doesn't match anymore
+        assert_eq!(world.compare_and_set(pos, BlockAir, birch), Ok(false));
+        assert_eq!(world.compare_and_set(pos, birch, BlockAir), Ok(false));
+        assert_eq!(world.get(pos).unwrap().cast::<BlockWoodenPlanks>().unwrap().variant, WoodVariant::Oak);
+
+        // same type, same state
+        assert_eq!(world.compare_and_set(pos, oak, birch), Ok(true));
+        assert_eq!(world.get(pos).unwrap().cast::<BlockWoodenPlanks>().unwrap().variant, WoodVariant::Birch);
+
+        // can't access
+        let lock = world.get_chunk(vec3(0, 0, 0)).unwrap();
+        assert_eq!(world.compare_and_set(pos, birch, oak), Err(WorldAccessError::WouldBlock));
+        drop(lock);
+
+        assert_eq!(world.compare_and_set(vec3(0, 32, 0), BlockAir, oak), Err(WorldAccessError::ChunkNotLoaded));
+    }
+
+    #[test]
+    fn raycast()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+
+        let mut world = World::with_generator(registry, FlatGenerator::new(vec![]));
+
+        world.load_chunk(vec3(0, 0, 0));
+        world.load_chunk(vec3(1, 0, 0));
+        world.load_chunk(vec3(3, 0, 0));
+        world.wait_for_loads();
+
+        let planks = BlockWoodenPlanks { variant: WoodVariant::Oak };
+        for &pos in &[vec3(5, 0, 0), vec3(0, 5, 5), vec3(7, 1, 2), vec3(33, 3, 3), vec3(100, 3, 3)]
+        {
+            world.set(pos, planks).unwrap();
+        }
+
+        let hit = |pos, face, distance| Some(RaycastHit { pos, face, distance });
+
+        // simple
+        assert_eq!(world.raycast(vec3(0.5, 0.5, 0.5), vec3(1.0, 0.0, 0.0), 10.0), hit(vec3(5, 0, 0), Direction::West, 4.5));
+        assert_eq!(world.raycast(vec3(0.5, 0.5, 0.5), vec3(1.0, 0.0, 0.0), 4.0), None);
+        assert_eq!(world.raycast(vec3(0.5, 0.5, 0.5), vec3(-1.0, 0.0, 0.0), 10.0), None);
+        assert_eq!(world.raycast(vec3(0.5, 10.5, 5.5), vec3(0.0, -2.0, 0.0), 10.0), hit(vec3(0, 5, 5), Direction::Up, 4.5));
+
+        // starting inside a block
+        assert_eq!(world.raycast(vec3(5.2, 0.7, 0.1), vec3(0.0, 0.0, -1.0), 10.0), hit(vec3(5, 0, 0), Direction::South, 0.0));
+
+        // axis aligned, exactly on cell boundaries
+        assert_eq!(world.raycast(vec3(10.0, 1.0, 2.0), vec3(-1.0, 0.0, 0.0), 10.0), hit(vec3(7, 1, 2), Direction::East, 2.0));
+        assert_eq!(world.raycast(vec3(4.0, 1.0, 2.0), vec3(1.0, 0.0, 0.0), 10.0), hit(vec3(7, 1, 2), Direction::West, 3.0));
+        assert_eq!(world.raycast(vec3(8.0, 1.0, 2.0), vec3(-1.0, 0.0, 0.0), 10.0), hit(vec3(7, 1, 2), Direction::East, 0.0));
+
+        // diagonal
+        let hit_diag = world.raycast(vec3(0.5, 0.5, 0.5), vec3(0.0, 1.0, 1.0), 10.0).unwrap();
+        assert_eq!(hit_diag.pos, vec3(0, 5, 5));
+        assert!((hit_diag.distance - 4.5 * 2f32.sqrt()).abs() < 1e-4);
+
+        // crossing a chunk border
+        assert_eq!(world.raycast(vec3(30.5, 3.5, 3.5), vec3(1.0, 0.0, 0.0), 10.0), hit(vec3(33, 3, 3), Direction::West, 2.5));
+        assert_eq!(world.raycast(vec3(36.5, 3.5, 3.5), vec3(-1.0, 0.0, 0.0), 10.0), hit(vec3(33, 3, 3), Direction::East, 2.5));
+
+        // chunk (2, 0, 0) isn't loaded
+        assert_eq!(world.raycast(vec3(60.5, 3.5, 3.5), vec3(1.0, 0.0, 0.0), 50.0), None);
+        assert_eq!(
+            world.raycast_with(vec3(60.5, 3.5, 3.5), vec3(1.0, 0.0, 0.0), 50.0, Unloaded::PassThrough),
+            hit(vec3(100, 3, 3), Direction::West, 39.5),
+        );
+
+        // no direction
+        assert_eq!(world.raycast(vec3(0.5, 0.5, 0.5), vec3(0.0, 0.0, 0.0), 10.0), None);
+    }
+
+    #[test]
+    fn chunks()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+
+        let mut world = World::new(registry);
+
+        for x in (-1..=1).rev()
+        {
+            for z in -1..=1
+            {
+                world.load_chunk(vec3(x, 0, z));
+            }
+        }
+
+        // every chunk is known, even while loading
+        let mut expected = Vec::new();
+        for x in -1..=1
+        {
+            for z in -1..=1
+            {
+                expected.push(vec3(x, 0, z));
+            }
+        }
+        assert_eq!(world.chunk_positions(), expected);
+
+        world.wait_for_loads();
+
+        let chunks = world
+            .chunks()
+            .map(|(pos, chunk)| { assert_eq!(chunk.pos(), pos); pos })
+            .collect::<Vec<_>>();
+        assert_eq!(chunks, expected);
+
+        // borrowed chunks are skipped
+        let lock = world.get_chunk_mut(vec3(0, 0, 0)).unwrap();
+
+        assert_eq!(world.chunks().count(), 8);
+        assert!(world.chunks().all(|(pos, _)| pos != vec3(0, 0, 0)));
+        assert_eq!(world.chunk_positions().len(), 9);
+        drop(lock);
+    }
+
+    #[test]
+    fn load_chunks_in_radius()
+    {
+        let mut world = World::with_generator(block::Registry::default(), FlatGenerator::new(vec![]));
+
+        // every chunk position in the cube of the given radius, sorted
+        let cube = |center: Vec3<i32>, r: i32|
+        {
+            let mut cube = Vec::new();
+            for x in -r..=r
+            {
+                for y in -r..=r
+                {
+                    for z in -r..=r
+                    {
+                        cube.push(center + vec3(x, y, z));
+                    }
+                }
+            }
+            cube
+        };
+
+        world.load_chunks_in_radius(vec3(0, 0, 0), -1);
+        assert!(world.chunk_positions().is_empty());
+
+        world.load_chunks_in_radius(vec3(0, 0, 0), 0);
+        assert_eq!(world.chunk_positions(), vec![vec3(0, 0, 0)]);
+
+        world.load_chunks_in_radius(vec3(2, -1, 3), 1);
+        world.wait_for_loads();
+
+        let mut expected = cube(vec3(2, -1, 3), 1);
+        expected.push(vec3(0, 0, 0));
+        expected.sort_by_key(|pos| (pos.x, pos.y, pos.z));
+
+        assert_eq!(world.chunk_positions(), expected);
+
+        // skips those already loaded
+        world.load_chunks_in_radius(vec3(2, -1, 3), 1);
+        assert!(!world.has_pending_loads());
+
+        let unloaded = world.unload_chunks_outside_radius(vec3(2, -1, 3), 1);
+
+        assert_eq!(unloaded.len(), 1);
+        assert_eq!(unloaded[0].pos(), vec3(0, 0, 0));
+        assert_eq!(world.chunk_positions(), cube(vec3(2, -1, 3), 1));
+    }
+
+    #[test]
+    fn ensure_loaded_around()
+    {
+        let mut registry = block::Registry::default();
+
+        registry.register::<BlockWoodenPlanks>();
+
+        let mut world = World::with_generator(registry, FlatGenerator::new(vec![]));
+
+        // every chunk position within 2 chunks of `center`, sorted
+        let ball = |center: Vec3<i32>|
         {
-            for z in 0..12
+            let mut ball = Vec::new();
+            for x in -2..=2
             {
-                for y in 0..12
+                for y in -2..=2
                 {
-                    world.load_chunk(vec3(x * 32, y * 32, z * 32));
+                    for z in -2..=2
+                    {
+                        if x * x + y * y + z * z <= 4
+                        {
+                            ball.push(center + vec3(x, y, z));
+                        }
+                    }
                 }
             }
+            ball.sort_by_key(|pos| (pos.x, pos.y, pos.z));
+            ball
+        };
+
+        let report = world.ensure_loaded_around(vec3(0, 0, 0), 2);
+
+        assert_eq!(report.queued, 33);
+        assert!(report.evictable.is_empty());
+        assert_eq!(world.chunk_positions(), ball(vec3(0, 0, 0)));
+
+        // nothing new to queue
+        world.wait_for_loads();
+        assert_eq!(world.ensure_loaded_around(vec3(0, 0, 0), 2).queued, 0);
+
+        let mut prev = vec3(0, 0, 0);
+        for &center in &[vec3(1, 0, 0), vec3(3, 0, -1), vec3(3, -2, -1), vec3(-10, 4, 7)]
+        {
+            let report = world.ensure_loaded_around(center, 2);
+            let old = ball(prev);
+            let new = ball(center);
+
+            assert_eq!(report.queued, new.iter().filter(|pos| !old.contains(pos)).count());
+            assert_eq!(report.evictable, old.iter().copied().filter(|pos| !new.contains(pos)).collect::<Vec<_>>());
+
+            world.wait_for_loads();
+
+            let evicted = world.evict_out_of_range(center, 2);
+
+            assert_eq!(evicted.len(), report.evictable.len());
+            assert_eq!(world.chunk_positions(), new);
+            prev = center;
         }
+    }
 
-        // wait for chunk(s) to load
-        while world.num_chunks_loading() != 0 { }
-        
-        println!("done loading chunks...");
+    #[test]
+    fn chunks_in_sphere_order()
+    {
+        for &(center, radius) in &[(vec3(0, 0, 0), 0), (vec3(0, 0, 0), 3), (vec3(-5, 2, 7), 4)]
+        {
+            let chunks = chunks_in_sphere(center, radius).collect::<Vec<_>>();
+            let dist = |pos: Vec3<i32>| (pos - center).map(|n| n * n).sum();
 
-        println!("world[0, 0, 0] = {:?}", world.get(vec3(0, 0, 0)).map(|b| b.name()));
-        println!("world[1, 0, 0] = {:?}", world.get(vec3(1, 0, 0)).map(|b| b.name()));
+            assert_eq!(chunks[0], center);
+            assert!(chunks.windows(2).all(|w| dist(w[0]) <= dist(w[1])));
+
+            // brute force
+            let mut expected = Vec::new();
+            for x in -10..=10
+            {
+                for y in -10..=10
+                {
+                    for z in -10..=10
+                    {
+                        let pos = center + vec3(x, y, z);
+
+                        if dist(pos) <= radius * radius
+                        {
+                            expected.push(pos);
+                        }
+                    }
+                }
+            }
+            let mut sorted = chunks.clone();
+            let key = |pos: &Vec3<i32>| (pos.x, pos.y, pos.z);
+
+            sorted.sort_by_key(key);
+            expected.sort_by_key(key);
+            assert_eq!(sorted, expected);
+        }
+        assert_eq!(chunks_in_sphere(vec3(0, 0, 0), -1).count(), 0);
+    }
+
+    #[test]
+    fn save_and_open()
+    {
+        let registry = ||
+        {
+            let mut registry = block::Registry::default();
+
+            register_all(&mut registry);
+            registry
+        };
+        let dir = std::env::temp_dir().join(format!("miners-save-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        // generate, edit and save
+        let mut world = World::with_seed(registry(), 1234);
+
+        world.load_chunk(vec3(0, 0, 0));
+        world.load_chunk(vec3(-1, 0, 0));
+        world.wait_for_loads();
+
+        world.set(vec3(3, 20, 0), BlockWoodenPlanks { variant: WoodVariant::Spruce }).unwrap();
+        world.set(vec3(-1, 1, 2), BlockWoodenPlanks { variant: WoodVariant::Acacia }).unwrap();
+        world.set_spawn(vec3(-40, 72, 9));
+        world.set_time(18000);
+
+        let report = world.save(&dir).unwrap();
+        assert_eq!(report, SaveReport { saved: 2, skipped: 0 });
+
+        let generated = world.unload_chunk(vec3(-1, 0, 0)).unwrap().unwrap();
+
+        // reopen and find the edits
+        let mut world = World::open(&dir, registry()).unwrap();
+
+        assert_eq!(world.seed(), 1234);
+        assert_eq!(*world.info(), WorldInfo { spawn: vec3(-40, 72, 9), seed: 1234, time: 18000 });
+
+        let events = world.subscribe_chunk_events();
+
+        world.load_chunk(vec3(0, 0, 0));
+        world.load_chunk(vec3(-1, 0, 0));
+        world.wait_for_loads();
+
+        // read from the save rather than generated
+        assert_eq!(events.try_iter().filter(|event| matches!(event, ChunkEvent::Loaded(_))).count(), 2);
+
+        let planks = |pos| world
+            .get(pos)
+            .and_then(|b| b.cast::<BlockWoodenPlanks>().map(|p| p.variant));
+
+        assert_eq!(planks(vec3(3, 20, 0)), Some(WoodVariant::Spruce));
+        assert_eq!(planks(vec3(-1, 1, 2)), Some(WoodVariant::Acacia));
+        assert!(*world.get_chunk(vec3(-1, 0, 0)).unwrap() == generated);
+
+        // corrupt and missing chunks are generated instead
+        std::fs::write(dir.join("chunks").join("-1_0_0.chunk"), b"garbage").unwrap();
+
+        let mut world = World::open(&dir, registry()).unwrap();
+        let events = world.subscribe_chunk_events();
+
+        world.load_chunk(vec3(-1, 0, 0));
+        world.load_chunk(vec3(5, 0, 0));
+        world.wait_for_loads();
+
+        assert_eq!(events.try_iter().filter(|event| matches!(event, ChunkEvent::Generated(_))).count(), 2);
+
+        let mut expected = World::with_seed(registry(), 1234);
+
+        expected.load_chunk(vec3(-1, 0, 0));
+        expected.load_chunk(vec3(5, 0, 0));
+        expected.wait_for_loads();
+
+        assert!(*world.get_chunk(vec3(-1, 0, 0)).unwrap() == *expected.get_chunk(vec3(-1, 0, 0)).unwrap());
+        assert!(*world.get_chunk(vec3(5, 0, 0)).unwrap() == *expected.get_chunk(vec3(5, 0, 0)).unwrap());
+
+        // bad manifests
+        std::fs::write(dir.join("manifest.txt"), "version 99\nseed 0\n").unwrap();
+        assert!(matches!(World::open(&dir, registry()), Err(LoadError::UnsupportedVersion(99))));
+
+        std::fs::write(dir.join("manifest.txt"), "version 1\nseed\n").unwrap();
+        assert!(matches!(World::open(&dir, registry()), Err(LoadError::Corrupt)));
+
+        std::fs::write(dir.join("manifest.txt"), "version 2\nseed 5\nspawn 1 2\ntime 0\nfingerprint 0\n").unwrap();
+        assert!(matches!(World::open(&dir, registry()), Err(LoadError::Corrupt)));
+
+        // from before the spawn point and time were saved
+        std::fs::write(dir.join("manifest.txt"), "version 1\nseed 5\nfingerprint 0\n").unwrap();
+        assert_eq!(*World::open(&dir, registry()).unwrap().info(), WorldInfo { seed: 5, ..Default::default() });
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(World::open(&dir, registry()), Err(LoadError::Io(_))));
     }
 }
 
@@ -65,7 +2406,6 @@ mod test
     {
         let mut registry = block::Registry::default();
 
-        registry.register::<BlockAir>();
         registry.register::<BlockWoodenPlanks>();
         registry.register::<BlockWoodenSlab>();
         registry.register::<BlockChest>();
@@ -80,21 +2420,14 @@ mod test
         
         dbg!(chunk.get(vec3(0, 0, 0)).map(|b| b.name()));
 
-        chunk.set(vec3(0, 0, 0), BlockChest
-            {
-                facing: Direction::North,
-                contents: vec!["Stick x64", "Diamonds x3"],
-                name: None
-            }
-        );
+        chunk.set(vec3(0, 0, 0), BlockChest::new(Direction::North));
         
         dbg!(chunk.get(vec3(0, 0, 0)).map(|b| b.name()));
 
         chunk.set(vec3(1, 0, 0), BlockChest
             {
-                facing: Direction::North,
-                contents: vec!["Dirt x12"],
                 name: Some("_nahoy's Chest".into()),
+                ..BlockChest::new(Direction::North)
             }
         );
         chunk.set(vec3(0, 1, 0), BlockWoodenPlanks { variant: WoodVariant::Birch });
@@ -0,0 +1,102 @@
+use std::collections::{ HashMap, VecDeque };
+
+use crate::world::block;
+use crate::math::Vec3;
+
+/// An edit's order, position in chunk-space, and block
+type Edit = (u64, Vec3<usize>, Box<dyn block::Object>);
+
+/// Edits waiting for their chunk to be loaded, see [World::set_deferred](crate::world::World::set_deferred)
+pub(super) struct PendingEdits
+{
+    /// Edits by chunk position, oldest first
+    edits: HashMap<Vec3<i32>, VecDeque<Edit>>,
+    /// Order of the next edit
+    next: u64,
+    /// Total number of edits
+    len: usize,
+    /// Maximum number of edits, past which the oldest are dropped
+    cap: usize,
+}
+
+impl PendingEdits
+{
+    /// Default maximum number of pending edits in a world
+    pub const DEFAULT_CAP: usize = 1 << 16;
+
+    /// Record an edit to the chunk at `chunk`, dropping the oldest edit(of any
+    /// chunk) if over capacity.
+    pub fn push(&mut self, chunk: Vec3<i32>, pos: Vec3<usize>, block: Box<dyn block::Object>)
+    {
+        self.edits
+            .entry(chunk)
+            .or_default()
+            .push_back((self.next, pos, block));
+        self.next += 1;
+        self.len += 1;
+
+        self.evict();
+    }
+
+    /// Take all the edits to the chunk at `chunk`, in the order they were made
+    pub fn drain(&mut self, chunk: Vec3<i32>) -> impl Iterator<Item = (Vec3<usize>, Box<dyn block::Object>)>
+    {
+        let edits = self.edits
+            .remove(&chunk)
+            .unwrap_or_default();
+
+        self.len -= edits.len();
+        edits
+            .into_iter()
+            .map(|(_, pos, block)| (pos, block))
+    }
+
+    /// Get the total number of edits
+    pub fn len(&self) -> usize
+    {
+        self.len
+    }
+
+    /// Change the maximum number of edits, dropping the oldest if over it
+    pub fn set_cap(&mut self, cap: usize)
+    {
+        self.cap = cap;
+        self.evict();
+    }
+
+    /// Drop the oldest edits until within capacity
+    fn evict(&mut self)
+    {
+        while self.len > self.cap
+        {
+            // Front of each queue is its oldest
+            let chunk = *self.edits
+                .iter()
+                .min_by_key(|(_, edits)| edits.front().map(|(order, _, _)| *order))
+                .unwrap()
+                .0;
+            let edits = self.edits.get_mut(&chunk).unwrap();
+
+            edits.pop_front();
+            if edits.is_empty()
+            {
+                self.edits.remove(&chunk);
+            }
+            self.len -= 1;
+        }
+    }
+}
+
+impl Default for PendingEdits
+{
+    fn default() -> Self
+    {
+        Self
+        {
+            edits: Default::default(),
+            next: 0,
+            len: 0,
+            cap: Self::DEFAULT_CAP,
+        }
+    }
+}
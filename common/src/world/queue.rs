@@ -0,0 +1,70 @@
+use crate::math::Vec3;
+
+/// Chunks waiting to be loaded by a [World](crate::world::World)'s worker threads,
+/// closest to the anchor first. See [World::set_load_anchor](crate::world::World::set_load_anchor)
+pub(super) struct LoadQueue
+{
+    /// Jobs that haven't started yet, in the order they were pushed
+    jobs: Vec<Job>,
+    /// Chunk position(1 unit = 32 blocks) that the closest chunks are loaded from
+    anchor: Vec3<i32>,
+}
+
+/// Loads the chunk at a given chunk position
+struct Job
+{
+    /// Chunk position(1 unit = 32 blocks) of the chunk loaded
+    pos: Vec3<i32>,
+    /// Runs the job. Dropping it instead cancels the job
+    run: Box<dyn FnOnce() + Send>,
+}
+
+impl LoadQueue
+{
+    /// Queue the job `run`, which loads the chunk at the given chunk position
+    pub fn push(&mut self, pos: Vec3<i32>, run: impl FnOnce() + Send + 'static)
+    {
+        self.jobs.push(Job { pos, run: Box::new(run) });
+    }
+
+    /// Take the job loading the chunk closest to the anchor, the oldest first
+    /// when tied
+    pub fn pop(&mut self) -> Option<impl FnOnce() + Send>
+    {
+        let anchor = self.anchor;
+        let (i, _) = self.jobs
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, job)| (job.pos - anchor).map(|n| n as i64 * n as i64).sum())?;
+
+        Some(self.jobs.remove(i).run)
+    }
+
+    /// Cancel the job loading the chunk at the given chunk position if it hasn't
+    /// started yet, returning whether there was one
+    pub fn remove(&mut self, pos: Vec3<i32>) -> bool
+    {
+        let len = self.jobs.len();
+
+        self.jobs.retain(|job| job.pos != pos);
+        self.jobs.len() != len
+    }
+
+    /// Change the chunk position that the closest chunks are loaded from
+    pub fn set_anchor(&mut self, anchor: Vec3<i32>)
+    {
+        self.anchor = anchor;
+    }
+}
+
+impl Default for LoadQueue
+{
+    fn default() -> Self
+    {
+        Self
+        {
+            jobs: Vec::new(),
+            anchor: Vec3::zero(),
+        }
+    }
+}
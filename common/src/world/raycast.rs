@@ -0,0 +1,64 @@
+use crate::world::World;
+use crate::math::{ Vec3, Direction, GridRay };
+
+/// The first solid block hit by a ray, as returned by [World::raycast]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RaycastHit
+{
+    /// World coordinates of the block that was hit
+    pub pos: Vec3<i32>,
+    /// Face of the block through which the ray entered it
+    pub face: Direction,
+    /// Distance along the ray from its origin to where it entered the block
+    pub distance: f32,
+}
+
+/// How rays cast by [World::raycast_with] treat chunks that aren't loaded(or are
+/// currently borrowed mutably)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unloaded
+{
+    /// The ray stops and hits nothing
+    Stop,
+    /// The ray goes through as if the chunk were air
+    PassThrough,
+}
+
+impl World
+{
+    /// Marches a ray from world coordinates `origin` along `dir`, returning the
+    /// first [solid](crate::world::Block::solid) block within `max_dist`. The ray
+    /// stops at unloaded chunks, see [World::raycast_with].
+    pub fn raycast(&self, origin: Vec3<f32>, dir: Vec3<f32>, max_dist: f32) -> Option<RaycastHit>
+    {
+        self.raycast_with(origin, dir, max_dist, Unloaded::Stop)
+    }
+
+    /// [World::raycast], but with control over how unloaded chunks are handled.
+    ///
+    /// A ray starting inside a solid block hits it at a distance of `0`, through
+    /// the face it would've entered from along its main axis. Rays starting exactly
+    /// on a cell boundary start in the cell they're heading into, and those running
+    /// along a boundary belong to the cell on its positive side.
+    ///
+    /// Rays passing through unloaded chunks are only bounded by `max_dist`, which
+    /// should then be finite.
+    pub fn raycast_with(&self, origin: Vec3<f32>, dir: Vec3<f32>, max_dist: f32, unloaded: Unloaded) -> Option<RaycastHit>
+    {
+        for (cell, face, distance) in GridRay::new(origin, dir)?
+        {
+            if distance > max_dist
+            {
+                return None
+            }
+            match self.try_get(cell)
+            {
+                Ok(block) if block.solid() => return Some(RaycastHit { pos: cell, face, distance }),
+                Ok(_) => (),
+                Err(_) if unloaded == Unloaded::Stop => return None,
+                Err(_) => (),
+            }
+        }
+        None
+    }
+}
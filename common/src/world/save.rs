@@ -0,0 +1,146 @@
+use std::path::{ Path, PathBuf };
+use std::io;
+
+use crate::world::{ WorldInfo, block };
+use crate::math::Vec3;
+
+/// Version of the save format written by [World::save](crate::world::World::save).
+/// Version `1` didn't save the spawn point nor the time, which are defaulted.
+const VERSION: u32 = 2;
+
+/// Outcome of [World::save](crate::world::World::save)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SaveReport
+{
+    /// Number of chunks written to disk
+    pub saved: usize,
+    /// Number of loaded chunks that weren't written because they were still
+    /// generating or borrowed mutably
+    pub skipped: usize,
+}
+
+/// Reasons a saved world can't be opened by [World::open](crate::world::World::open)
+#[derive(Debug)]
+pub enum LoadError
+{
+    /// The world's manifest couldn't be read
+    Io(io::Error),
+    /// The world's manifest is malformed
+    Corrupt,
+    /// The world was saved in a format version this game doesn't support
+    UnsupportedVersion(u32),
+}
+
+impl From<io::Error> for LoadError
+{
+    fn from(err: io::Error) -> Self
+    {
+        Self::Io(err)
+    }
+}
+
+/// The part of a saved world that isn't chunks
+pub(super) struct Manifest
+{
+    /// See [World::info](crate::world::World::info)
+    pub info: WorldInfo,
+    /// [block::Registry::fingerprint] of the registry the world was saved with
+    pub fingerprint: u64,
+}
+
+impl Manifest
+{
+    /// Write this manifest, along with the ID map of the registry it was saved
+    /// with, to `dir`.
+    /// ```text
+    /// version 2
+    /// seed 1234
+    /// spawn 0 64 0
+    /// time 6000
+    /// fingerprint 9876
+    /// block 0 air
+    /// block 1 planks
+    /// ```
+    pub fn write(&self, dir: &Path, registry: &block::Registry) -> io::Result<()>
+    {
+        let WorldInfo { seed, spawn, time } = self.info;
+        let mut out = format!(
+            "version {}\nseed {}\nspawn {} {} {}\ntime {}\nfingerprint {}\n",
+            VERSION, seed, spawn.x, spawn.y, spawn.z, time, self.fingerprint,
+        );
+
+        for (id, name) in registry.str_ids().enumerate()
+        {
+            out += &format!("block {} {}\n", id, name);
+        }
+        std::fs::write(dir.join("manifest.txt"), out)
+    }
+
+    /// Read the manifest saved in `dir`. Chunks are stored by the string
+    /// identifiers of their blocks, so the saved ID map is only checked for
+    /// well-formedness, and the fingerprint is left for the caller to compare.
+    pub fn read(dir: &Path) -> Result<Self, LoadError>
+    {
+        let text = std::fs::read_to_string(dir.join("manifest.txt"))?;
+        let mut lines = text.lines();
+
+        let version = lines
+            .next()
+            .and_then(|line| line.strip_prefix("version "))
+            .and_then(|n| n.parse().ok())
+            .ok_or(LoadError::Corrupt)?;
+        if version == 0 || version > VERSION
+        {
+            return Err(LoadError::UnsupportedVersion(version))
+        }
+
+        let seed = lines
+            .next()
+            .and_then(|line| line.strip_prefix("seed "))
+            .and_then(|n| n.parse().ok())
+            .ok_or(LoadError::Corrupt)?;
+        let mut info = WorldInfo { seed, ..Default::default() };
+        if version >= 2
+        {
+            let spawn = lines
+                .next()
+                .and_then(|line| line.strip_prefix("spawn "))
+                .map(|line| line.split(' ').map(str::parse).collect::<Result<Vec<i32>, _>>());
+            info.spawn = match spawn
+            {
+                Some(Ok(spawn)) if spawn.len() == 3 => Vec3::from_slice(&spawn),
+                _ => return Err(LoadError::Corrupt),
+            };
+            info.time = lines
+                .next()
+                .and_then(|line| line.strip_prefix("time "))
+                .and_then(|n| n.parse().ok())
+                .ok_or(LoadError::Corrupt)?;
+        }
+        let fingerprint = lines
+            .next()
+            .and_then(|line| line.strip_prefix("fingerprint "))
+            .and_then(|n| n.parse().ok())
+            .ok_or(LoadError::Corrupt)?;
+
+        for line in lines
+        {
+            let mut words = line.strip_prefix("block ").ok_or(LoadError::Corrupt)?.split(' ');
+
+            match (words.next().map(str::parse::<u16>), words.next(), words.next())
+            {
+                (Some(Ok(_)), Some(_), None) => (),
+                _ => return Err(LoadError::Corrupt),
+            }
+        }
+
+        Ok(Self { info, fingerprint })
+    }
+}
+
+/// Path of the file the chunk at the given chunk position is saved to, within
+/// a saved world's directory.
+pub(super) fn chunk_path(dir: &Path, pos: Vec3<i32>) -> PathBuf
+{
+    dir.join("chunks").join(format!("{}_{}_{}.chunk", pos.x, pos.y, pos.z))
+}
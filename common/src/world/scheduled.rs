@@ -0,0 +1,70 @@
+use std::collections::BinaryHeap;
+use std::cmp::Reverse;
+
+use crate::math::Vec3;
+
+/// Ticks scheduled for blocks by [World::schedule_tick](crate::world::World::schedule_tick),
+/// and the world's current tick
+#[derive(Default)]
+pub(super) struct ScheduledTicks
+{
+    /// Due tick, order, and world coordinates of the block of every scheduled
+    /// tick, soonest(then oldest) first
+    ticks: BinaryHeap<Reverse<(u64, u64, [i32; 3])>>,
+    /// The world's current tick
+    now: u64,
+    /// Order of the next scheduled tick
+    next: u64,
+}
+
+impl ScheduledTicks
+{
+    /// Schedule a tick for the block at the world coordinates `pos`, `delay`
+    /// ticks from now
+    pub fn push(&mut self, pos: Vec3<i32>, delay: u64)
+    {
+        self.ticks.push(Reverse((self.now + delay, self.next, pos.into_array())));
+        self.next += 1;
+    }
+
+    /// Advance to the next tick, and take the world coordinates of up to `max`
+    /// blocks whose tick is due, in the order they were scheduled. Those past
+    /// `max` stay due, and come first on the next tick.
+    pub fn advance(&mut self, max: usize) -> Vec<Vec3<i32>>
+    {
+        let mut due = Vec::new();
+
+        self.now += 1;
+        while let Some(&Reverse((tick, _, pos))) = self.ticks.peek()
+        {
+            if tick > self.now || due.len() >= max
+            {
+                break
+            }
+            self.ticks.pop();
+            due.push(Vec3::from(pos));
+        }
+        due
+    }
+
+    /// Get the world's current tick
+    pub fn now(&self) -> u64
+    {
+        self.now
+    }
+
+    /// Get the number of ticks scheduled which aren't due yet
+    pub fn len(&self) -> usize
+    {
+        self.ticks.len() - self.overdue()
+    }
+
+    /// Get the number of ticks which are due, but were left over by [ScheduledTicks::advance]
+    pub fn overdue(&self) -> usize
+    {
+        self.ticks
+            .iter()
+            .filter(|Reverse((tick, _, _))| *tick <= self.now)
+            .count()
+    }
+}
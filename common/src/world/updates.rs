@@ -0,0 +1,79 @@
+use std::collections::{ HashSet, VecDeque };
+
+use crate::math::{ Direction, Vec3 };
+
+/// Blocks whose neighbor was replaced, along with the direction of that neighbor,
+/// see [World::process_updates](crate::world::World::process_updates)
+pub(super) struct NeighborUpdates
+{
+    /// Updates in the order they were queued
+    queue: VecDeque<(Vec3<i32>, Direction)>,
+    /// Same as `queue`, so that an update already queued isn't queued again
+    queued: HashSet<(Vec3<i32>, Direction)>,
+    /// Maximum number of updates, past which the oldest are dropped
+    cap: usize,
+}
+
+impl NeighborUpdates
+{
+    /// Default maximum number of queued updates in a world
+    pub const DEFAULT_CAP: usize = 1 << 16;
+
+    /// Queue an update for the block at `pos`, whose neighbor towards `dir` was
+    /// replaced, unless it's already queued. Drops the oldest update if over
+    /// capacity.
+    pub fn push(&mut self, pos: Vec3<i32>, dir: Direction)
+    {
+        if self.queued.insert((pos, dir))
+        {
+            self.queue.push_back((pos, dir));
+            self.evict();
+        }
+    }
+
+    /// Take all the queued updates, in the order they were queued
+    pub fn take(&mut self) -> VecDeque<(Vec3<i32>, Direction)>
+    {
+        self.queued.clear();
+
+        std::mem::take(&mut self.queue)
+    }
+
+    /// Get the number of queued updates
+    pub fn len(&self) -> usize
+    {
+        self.queue.len()
+    }
+
+    /// Change the maximum number of updates, dropping the oldest if over it
+    pub fn set_cap(&mut self, cap: usize)
+    {
+        self.cap = cap;
+        self.evict();
+    }
+
+    /// Drop the oldest updates until within capacity
+    fn evict(&mut self)
+    {
+        while self.queue.len() > self.cap
+        {
+            if let Some(update) = self.queue.pop_front()
+            {
+                self.queued.remove(&update);
+            }
+        }
+    }
+}
+
+impl Default for NeighborUpdates
+{
+    fn default() -> Self
+    {
+        Self
+        {
+            queue: Default::default(),
+            queued: Default::default(),
+            cap: Self::DEFAULT_CAP,
+        }
+    }
+}
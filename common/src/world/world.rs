@@ -1,13 +1,26 @@
-use std::collections::HashMap;
+use std::collections::{ HashMap, HashSet };
 use std::ops::{ Deref, DerefMut };
+use std::path::{ Path, PathBuf };
+use std::sync::mpsc::{ self, Sender, Receiver };
 use std::sync::Arc;
-use std::sync::atomic::{ AtomicUsize, Ordering };
+use std::io;
+use std::sync::atomic::{ AtomicBool, AtomicUsize, AtomicU64, Ordering };
+use std::time::{ Duration, Instant };
+use std::task::Waker;
 
-use parking_lot::{ RwLock, RwLockReadGuard, RwLockWriteGuard };
-use noise::NoiseFn;
+use parking_lot::{ RwLock, RwLockReadGuard, RwLockUpgradableReadGuard, RwLockWriteGuard, Mutex, Condvar };
 
+use crate::world::gen::{ ChunkGenerator, PerlinGenerator };
+use crate::world::save::{ self, SaveReport, LoadError, Manifest };
+use crate::world::pending::PendingEdits;
+use crate::world::updates::NeighborUpdates;
+use crate::world::scheduled::ScheduledTicks;
+use crate::world::queue::LoadQueue;
 use crate::world::{ Chunk, Block, block };
-use crate::math::Vec3;
+use crate::vanilla::blocks::BlockAir;
+use crate::math::int::{ block_to_chunk, chunk_block_range };
+use crate::math::iter::{ positions_in_box, positions_in_sphere };
+use crate::math::{ Direction, Vec3 };
 
 pub struct World
 {
@@ -16,158 +29,1802 @@ pub struct World
     /// All the chunks in this world which are currently loaded or being loaded.
     /// They're protected by a `RwLock` such that multiple mutable borrows can be
     /// made to different chunks while only holding an immutable borrow to this `World`.
-    chunks: HashMap<Vec3<i32>, Arc<RwLock<Chunk>>>,
+    chunks: HashMap<Vec3<i32>, Entry>,
     /// Number of chunks currently loading
-    loading: Arc<AtomicUsize>,
-    /// The terrain height generator used by all threads loading chunks
-    noise: Arc<noise::Perlin>,
+    loading: Arc<Loading>,
+    /// The terrain generator used by all threads loading chunks, swapped out by
+    /// [World::set_generator]
+    generator: RwLock<Arc<dyn ChunkGenerator>>,
+    /// Chunks waiting to be loaded, shared with the worker threads
+    queue: Arc<Mutex<LoadQueue>>,
+    /// Worker threads loading chunks, separate from rayon's global pool
+    pool: rayon::ThreadPool,
+    /// Metadata saved along with this world, see [World::info]
+    info: WorldInfo,
+    /// Directory this world was opened from, whose saved chunks are loaded
+    /// instead of being generated
+    save_dir: Option<PathBuf>,
+    /// Receivers of this world's [ChunkEvent]s, shared with loading tasks
+    subscribers: Arc<Subscribers>,
+    /// Edits made to chunks before they were loaded, applied by loading tasks
+    pending: Arc<Mutex<PendingEdits>>,
+    /// Incremented on every access to a chunk, see [Entry::last_access]
+    tick: AtomicU64,
+    /// Blocks whose neighbor was replaced, along with the direction of that
+    /// neighbor, see [World::process_updates]
+    updates: Mutex<NeighborUpdates>,
+    /// Ticks scheduled for blocks, see [World::schedule_tick]
+    scheduled: Mutex<ScheduledTicks>,
+    /// Whether [World::set] defers edits to unloaded chunks, see [World::set_auto_load]
+    auto_load: bool,
+    /// Unloaded chunks edited by [World::set] with auto-loading on, waiting for
+    /// [World::load_requested]
+    requested: Mutex<HashSet<Vec3<i32>>>,
+}
+
+/// A chunk in a [World], along with its loading status
+struct Entry
+{
+    /// The chunk itself
+    chunk: Arc<RwLock<Chunk>>,
+    /// Whether the chunk is still being generated, shared with its generation task
+    generating: Arc<AtomicBool>,
+    /// Whether the chunk was unloaded before it was done generating, shared with
+    /// its generation task
+    cancelled: Arc<AtomicBool>,
+    /// The world's tick when this chunk was last accessed, see [World::trim]
+    last_access: AtomicU64,
+}
+
+/// Something that happened to a chunk in a [World], sent to the receivers from
+/// [World::subscribe_chunk_events]. Positions are chunk positions(1 unit = 32 blocks).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChunkEvent
+{
+    /// The chunk was read from its save and is ready to be used
+    Loaded(Vec3<i32>),
+    /// The chunk was unloaded from the world
+    Unloaded(Vec3<i32>),
+    /// The chunk was generated and is ready to be used
+    Generated(Vec3<i32>),
+}
+
+/// Whether a chunk is in a [World], as returned by [World::chunk_state]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChunkState
+{
+    /// The chunk isn't in the world
+    Absent,
+    /// The chunk is in the world, but is still being generated(or read from its save)
+    Generating,
+    /// The chunk is in the world and ready to be used
+    Loaded,
+}
+
+/// Metadata of a [World] that isn't chunks, saved along with them by [World::save]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WorldInfo
+{
+    /// World coordinates of the block players spawn at
+    pub spawn: Vec3<i32>,
+    /// See [World::seed]
+    pub seed: u64,
+    /// Time of day, in ticks
+    pub time: u64,
+}
+
+/// Outcome of [World::ensure_loaded_around]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StreamingReport
+{
+    /// Number of chunks that were newly queued for loading
+    pub queued: usize,
+    /// Chunk positions(1 unit = 32 blocks), sorted, of the chunks in the world
+    /// that are out of range and may be unloaded, ie. by [World::evict_out_of_range]
+    pub evictable: Vec<Vec3<i32>>,
+}
+
+/// Outcome of [World::set_many]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BatchResult
+{
+    /// Number of edits that were applied
+    pub applied: usize,
+    /// Edits that couldn't be applied, grouped by chunk and sorted by chunk position
+    pub failed: Vec<BatchFailure>,
+}
+
+/// Edits of a [World::set_many] batch that couldn't be applied to a chunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchFailure
+{
+    /// Position of the chunk(1 unit = 32 blocks)
+    pub chunk: Vec3<i32>,
+    /// Why the chunk couldn't be edited
+    pub error: WorldAccessError,
+    /// World coordinates of the edits that weren't applied, in order
+    pub edits: Vec<Vec3<i32>>,
+}
+
+/// Outcome of [World::fill_region] and [World::edit_sphere]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FillReport
+{
+    /// Number of blocks that were set
+    pub written: usize,
+    /// Chunks overlapping the region that couldn't be filled, sorted by chunk
+    /// position(1 unit = 32 blocks)
+    pub skipped: Vec<(Vec3<i32>, WorldAccessError)>,
+}
+
+/// Reasons a block in a [World] can't be accessed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldAccessError
+{
+    /// The chunk containing the block isn't loaded
+    ChunkNotLoaded,
+    /// The chunk containing the block is currently borrowed, and accessing it
+    /// would've blocked
+    WouldBlock,
+}
+
+/// Reasons a chunk can't be unloaded from a [World]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnloadError
+{
+    /// The chunk is currently borrowed through an `Arc` obtained by [World::get_chunk_arc]
+    Locked,
 }
 
 impl World
 {
-    /// Creates a new `World` with no loaded `Chunk`s
-    pub fn new(registry: block::Registry) -> Self
+    /// Creates a new `World` with no loaded `Chunk`s, whose terrain is generated
+    /// by a [PerlinGenerator] with a seed of `0`. The registry can be shared with
+    /// other worlds by passing it as an `Arc`.
+    pub fn new(registry: impl Into<Arc<block::Registry>>) -> Self
+    {
+        Self::with_seed(registry, 0)
+    }
+
+    /// Creates a new `World` with no loaded `Chunk`s, whose terrain is generated
+    /// by a [PerlinGenerator] with the given seed. Worlds with the same seed
+    /// generate identical chunks.
+    pub fn with_seed(registry: impl Into<Arc<block::Registry>>, seed: u64) -> Self
+    {
+        Self
+        {
+            info: WorldInfo { seed, ..Default::default() },
+            ..Self::with_generator(registry, PerlinGenerator::vanilla(seed))
+        }
+    }
+
+    /// Creates a new `World` with no loaded `Chunk`s, whose terrain is generated
+    /// by `generator`. The generator's own seed, if any, is independent from the
+    /// world's, which is `0`.
+    pub fn with_generator(registry: impl Into<Arc<block::Registry>>, generator: impl ChunkGenerator + 'static) -> Self
     {
         Self
         {
-            registry: Arc::new(registry),
+            registry: registry.into(),
             chunks: HashMap::default(),
-            loading: Arc::new(AtomicUsize::new(0)),
-            noise: Arc::new(Default::default()),
+            loading: Default::default(),
+            generator: RwLock::new(Arc::new(generator)),
+            queue: Default::default(),
+            pool: worker_pool(None),
+            info: WorldInfo::default(),
+            save_dir: None,
+            subscribers: Default::default(),
+            pending: Default::default(),
+            tick: AtomicU64::new(0),
+            updates: Default::default(),
+            scheduled: Default::default(),
+            auto_load: false,
+            requested: Default::default(),
+        }
+    }
+
+    /// Opens a `World` previously saved to `dir` by [World::save], with no loaded
+    /// `Chunk`s. Saved chunks are read from disk as they're loaded by [World::load_chunk],
+    /// and the others are generated from the saved seed.
+    pub fn open(dir: impl AsRef<Path>, registry: impl Into<Arc<block::Registry>>) -> Result<Self, LoadError>
+    {
+        let dir = dir.as_ref();
+        let registry = registry.into();
+        let manifest = Manifest::read(dir)?;
+
+        // Blocks that aren't registered anymore come back as air
+        #[cfg(debug_assertions)]
+        if manifest.fingerprint != registry.fingerprint()
+        {
+            println!("Opened world at {} with a different set of blocks than it was saved with.\n\
+            Blocks that aren't registered anymore are replaced with air.", dir.display());
+        }
+
+        Ok(Self
+        {
+            save_dir: Some(dir.to_path_buf()),
+            info: manifest.info,
+            ..Self::with_seed(registry, manifest.info.seed)
+        })
+    }
+
+    /// Saves every loaded chunk, along with this world's [info](World::info) and block IDs, to
+    /// `dir` so that it can be restored by [World::open]. Chunks that are still
+    /// generating or are borrowed mutably are skipped, which is non-blocking.
+    pub fn save(&self, dir: impl AsRef<Path>) -> io::Result<SaveReport>
+    {
+        let dir = dir.as_ref();
+        let mut report = SaveReport::default();
+
+        std::fs::create_dir_all(dir.join("chunks"))?;
+        Manifest { info: self.info, fingerprint: self.registry.fingerprint() }.write(dir, &self.registry)?;
+
+        for (&pos, entry) in &self.chunks
+        {
+            let chunk = match entry.chunk.try_read()
+            {
+                Some(chunk) if !entry.generating.load(Ordering::Acquire) => chunk,
+                _ =>
+                {
+                    report.skipped += 1;
+                    continue;
+                }
+            };
+            std::fs::write(save::chunk_path(dir, pos), chunk.to_bytes())?;
+
+            report.saved += 1;
         }
+
+        Ok(report)
+    }
+
+    /// Get the registry of all the block types in this world, which may be shared
+    /// with other worlds
+    pub fn registry(&self) -> &Arc<block::Registry>
+    {
+        &self.registry
+    }
+
+    /// Get the seed from which this world's generation and randomness derive
+    pub fn seed(&self) -> u64
+    {
+        self.info.seed
+    }
+
+    /// Get the metadata saved along with this world, ie. its spawn point
+    pub fn info(&self) -> &WorldInfo
+    {
+        &self.info
+    }
+
+    /// Get the world coordinates of the block players spawn at
+    pub fn spawn(&self) -> Vec3<i32>
+    {
+        self.info.spawn
+    }
+
+    /// Set the world coordinates of the block players spawn at
+    pub fn set_spawn(&mut self, spawn: Vec3<i32>)
+    {
+        self.info.spawn = spawn;
+    }
+
+    /// Get the time of day, in ticks
+    pub fn time(&self) -> u64
+    {
+        self.info.time
+    }
+
+    /// Set the time of day, in ticks
+    pub fn set_time(&mut self, time: u64)
+    {
+        self.info.time = time;
+    }
+
+    /// Get the terrain generator that chunks loaded from now on are generated by
+    pub fn generator(&self) -> Arc<dyn ChunkGenerator>
+    {
+        Arc::clone(&self.generator.read())
+    }
+
+    /// Replace the terrain generator with `generator`, ie. to change how newly
+    /// explored regions are generated. Only chunks passed to [World::load_chunk]
+    /// from now on use it: those already loaded, and those already queued or
+    /// generating, keep the generator they were loaded with.
+    pub fn set_generator(&self, generator: Arc<dyn ChunkGenerator>)
+    {
+        *self.generator.write() = generator;
     }
 
     /// Returns some [Block] at the world coordinates `pos` if the chunk it's in is
-    /// loaded and not locked. This is a non-blocking operation.
-    pub fn get(&self, pos: Vec3<i32>) -> Option<impl Deref<Target = dyn block::Object> + '_>
+    /// loaded. This blocks until the chunk can be borrowed immutably, which includes
+    /// waiting for it to finish generating, see [World::try_get] otherwise.
+    ///
+    /// Like every accessor here, `pos` can also be given as a tuple, ie.
+    /// `world.get((5, 64, -3))`.
+    pub fn get(&self, pos: impl Into<Vec3<i32>>) -> Option<impl Deref<Target = dyn block::Object> + '_>
     {
-        let lock = self.chunks
-            // Chunk position, 1 unit = 32 blocks
-            .get(&(pos / Chunk::SIZE as i32))?
+        let (chunk, local) = block_to_chunk(pos.into());
+        let lock = self
+            .loaded_entry(chunk)?
+            .chunk
             // Block until acquired a read-only lock
-            .try_read()?;
+            .read();
         
         Some(RwLockReadGuard::map(lock, |chunk| unsafe
         {
             // SAFETY:
             // Position is euclidian reminder'd by 32, and
             // therefore must be in bounds
-            chunk.get_unchecked(pos.as_() & 0x1f)
+            chunk.get_unchecked(local)
+        }))
+    }
+
+    /// Get a reader of this world's blocks for hot loops, ie. a pathfinder scanning
+    /// the same area over and over, that keeps the last chunk accessed borrowed so
+    /// that accesses within it skip the lookup and locking of [World::get].
+    ///
+    /// That chunk stays borrowed immutably until the reader moves on to another
+    /// chunk or is dropped, so it shouldn't be kept around: editing the chunk on
+    /// the same thread in the meantime deadlocks, and other threads block.
+    pub fn cached_reader(&self) -> CachedReader<'_>
+    {
+        CachedReader { world: self, cached: None }
+    }
+
+    /// Returns the [Block] at the world coordinates `pos` if the chunk it's in is
+    /// loaded and not borrowed mutably. This is a non-blocking operation.
+    pub fn try_get(&self, pos: impl Into<Vec3<i32>>) -> Result<impl Deref<Target = dyn block::Object> + '_, WorldAccessError>
+    {
+        let (chunk, local) = block_to_chunk(pos.into());
+        let lock = self
+            .entry(chunk)
+            .ok_or(WorldAccessError::ChunkNotLoaded)?
+            .chunk
+            .try_read()
+            .ok_or(WorldAccessError::WouldBlock)?;
+        
+        Ok(RwLockReadGuard::map(lock, |chunk| unsafe
+        {
+            // SAFETY:
+            // Position is euclidian reminder'd by 32, and
+            // therefore must be in bounds
+            chunk.get_unchecked(local)
         }))
     }
 
     /// Returns some [Block] at the world coordinates `pos` if the chunk it's in is
-    /// loaded and not locked. This is a non-blocking operation.
-    pub fn get_mut(&self, pos: Vec3<i32>) -> Option<impl DerefMut<Target = dyn block::Object> + '_>
+    /// loaded. This blocks until the chunk can be borrowed mutably, which includes
+    /// waiting for it to finish generating, see [World::try_get_mut] otherwise.
+    ///
+    /// `Val` blocks are unpacked by `cast_mut`, and changes are packed back into the
+    /// chunk(keeping the block's ID) when the [block::RefMut] is dropped.
+    pub fn get_mut(&self, pos: impl Into<Vec3<i32>>) -> Option<impl DerefMut<Target = dyn block::Object> + '_>
     {
-        let lock = self.chunks
-            // Chunk position, 1 unit = 32 blocks
-            .get(&(pos / Chunk::SIZE as i32))?
-            // Block until acquired a read-only lock
-            .try_write()?;
+        let (chunk, local) = block_to_chunk(pos.into());
+        let lock = self
+            .loaded_entry(chunk)?
+            .chunk
+            // Block until acquired a read-write lock
+            .write();
         
         Some(RwLockWriteGuard::map(lock, |chunk| unsafe
         {
             // SAFETY:
             // Position is euclidian reminder'd by 32, and
             // therefore must be in bounds
-            chunk.get_unchecked_mut(pos.as_() & 0x1f)
+            chunk.get_unchecked_mut(local)
         }))
     }
 
-    /// Set the [Block] at the world coordinates `pos` if the chunk it's in is loaded
-    /// and not locked. This is a non-blocking operation.
-    pub fn set<T: Block>(&self, pos: Vec3<i32>, block: T) -> Result<(), ()>
+    /// Returns the [Block] at the world coordinates `pos` if the chunk it's in is
+    /// loaded and not borrowed. This is a non-blocking operation.
+    pub fn try_get_mut(&self, pos: impl Into<Vec3<i32>>) -> Result<impl DerefMut<Target = dyn block::Object> + '_, WorldAccessError>
     {
-        let mut lock = self.chunks
-            // Chunk position, 1 unit = 32 blocks
-            .get(&(pos / Chunk::SIZE as i32))
-            .ok_or(())?
-            // Block until acquired a read-only lock
+        let (chunk, local) = block_to_chunk(pos.into());
+        let lock = self
+            .entry(chunk)
+            .ok_or(WorldAccessError::ChunkNotLoaded)?
+            .chunk
             .try_write()
-            .ok_or(())?;
+            .ok_or(WorldAccessError::WouldBlock)?;
+        
+        Ok(RwLockWriteGuard::map(lock, |chunk| unsafe
+        {
+            // SAFETY:
+            // Position is euclidian reminder'd by 32, and
+            // therefore must be in bounds
+            chunk.get_unchecked_mut(local)
+        }))
+    }
 
-        unsafe
+    /// Returns some [Block] of type `T` at the world coordinates `pos` if the chunk
+    /// it's in is loaded and the block found is a `T`. This blocks like [World::get].
+    pub fn get_as<T: Block>(&self, pos: impl Into<Vec3<i32>>) -> Option<impl Deref<Target = T> + '_>
+    {
+        let (chunk, local) = block_to_chunk(pos.into());
+        let lock = self
+            .loaded_entry(chunk)?
+            .chunk
+            // Block until acquired a read-only lock
+            .read();
+
+        let block = unsafe
+        {
+            // SAFETY:
+            // Position is euclidian reminder'd by 32, and
+            // therefore must be in bounds
+            lock.get_unchecked(local)
+        }.cast::<T>()?;
+        let block = unsafe
+        {
+            // SAFETY:
+            // The chunk lives behind an `Arc`, and doesn't move along with its guard,
+            // which is kept alive for as long as the reference
+            std::mem::transmute::<block::Ref<'_, T>, block::Ref<'_, T>>(block)
+        };
+
+        Some(TypedRef { block, _lock: lock })
+    }
+
+    /// Returns some [Block] of type `T` at the world coordinates `pos` if the chunk
+    /// it's in is loaded and the block found is a `T`. This blocks like [World::get_mut].
+    ///
+    /// Changes to `Val` blocks are packed back into the chunk once the returned
+    /// reference is dropped.
+    pub fn get_mut_as<T: Block>(&self, pos: impl Into<Vec3<i32>>) -> Option<impl DerefMut<Target = T> + '_>
+    {
+        let (chunk, local) = block_to_chunk(pos.into());
+        let mut lock = self
+            .loaded_entry(chunk)?
+            .chunk
+            // Block until acquired a read-write lock
+            .write();
+
+        let block = unsafe
+        {
+            // SAFETY:
+            // Position is euclidian reminder'd by 32, and
+            // therefore must be in bounds
+            lock.get_unchecked_mut(local)
+        }.cast_mut::<T>()?;
+        let block = unsafe
+        {
+            // SAFETY:
+            // Same as `World::get_as`
+            std::mem::transmute::<block::RefMut<'_, T>, block::RefMut<'_, T>>(block)
+        };
+
+        Some(TypedRefMut { block, _lock: lock })
+    }
+
+    /// Set the [Block] at the world coordinates `pos`, or if the chunk it's in isn't
+    /// loaded(or is still generating), remember to do so once it is. Deferred edits
+    /// are applied before the chunk is announced as loaded, in the order they were
+    /// made. This is a non-blocking operation, and only fails if the chunk is loaded
+    /// but locked.
+    ///
+    /// Past [World::set_pending_edit_cap] deferred edits, the oldest are dropped.
+    pub fn set_deferred<T: Block>(&self, pos: Vec3<i32>, block: T) -> Result<(), WorldAccessError>
+    {
+        let (chunk, local) = block_to_chunk(pos);
+        // Held while checking the chunk's status, see `World::load_chunk`
+        let mut pending = self.pending.lock();
+
+        match self.chunks.get(&chunk)
         {
-            Ok(lock.set_unchecked(pos.as_() & 0x1f, block))
+            Some(entry) if !entry.generating.load(Ordering::Acquire) =>
+            {
+                drop(pending);
+
+                let mut lock = entry.chunk
+                    .try_write()
+                    .ok_or(WorldAccessError::WouldBlock)?;
+                unsafe
+                {
+                    // SAFETY:
+                    // Position is euclidian reminder'd by 32, and
+                    // therefore must be in bounds
+                    lock.set_unchecked(local, block);
+                }
+            },
+            _ => pending.push(chunk, local, Box::new(block)),
         }
+        Ok(())
     }
 
-    /// Get the chunk at the given chunk position(1 unit = 32 blocks) if it's
-    /// loaded and not already being borrowed mutably.
-    pub fn get_chunk(&self, pos: Vec3<i32>) -> Option<impl Deref<Target = Chunk> + '_>
+    /// Get the number of edits deferred by [World::set_deferred] that are waiting
+    /// for their chunk to load
+    pub fn pending_edit_count(&self) -> usize
     {
-        self.chunks
-            .get(&pos)?
-            .try_read()
+        self.pending.lock().len()
     }
 
-    /// Get the chunk at the given chunk position(1 unit = 32 blocks) if it's
-    /// loaded and not already being borrowed (im)mutably.
-    pub fn get_chunk_mut(&self, pos: Vec3<i32>) -> Option<impl DerefMut<Target = Chunk> + '_>
+    /// Set the maximum number of edits deferred by [World::set_deferred], past which
+    /// the oldest are dropped. Defaults to `65536`.
+    pub fn set_pending_edit_cap(&self, cap: usize)
+    {
+        self.pending.lock().set_cap(cap);
+    }
+
+    /// Returns an owned summary of the [Block] at the world coordinates `pos` if the
+    /// chunk it's in is loaded and not locked. This is a non-blocking operation.
+    pub fn block_at(&self, pos: Vec3<i32>) -> Option<block::Info>
     {
+        let (chunk, local) = block_to_chunk(pos);
+
         self.chunks
-            .get(&pos)?
+            .get(&chunk)?
+            .chunk
+            .try_read()?
+            .info(local)
+    }
+
+    /// Get summaries of the six blocks adjacent to the world coordinates `pos`, indexed
+    /// by `Direction as usize`. Unlike [Chunk::neighbors], this crosses chunk
+    /// boundaries; neighbors are only `None` if their chunk isn't loaded or is locked.
+    /// This is a non-blocking operation.
+    pub fn neighbors(&self, pos: Vec3<i32>) -> [Option<block::Info>; 6]
+    {
+        let mut out = [None; 6];
+
+        for (neighbor, &dir) in out.iter_mut().zip(Direction::ALL.iter())
+        {
+            *neighbor = self.block_at(pos + dir.offset());
+        }
+        out
+    }
+
+    /// Set the [Block] at the world coordinates `pos` if the chunk it's in is loaded.
+    /// This blocks until the chunk can be borrowed mutably, which includes waiting
+    /// for it to finish generating, see [World::try_set] otherwise.
+    ///
+    /// With [auto-loading](World::set_auto_load) on, edits to unloaded chunks are
+    /// deferred rather than failing.
+    pub fn set<T: Block>(&self, pos: impl Into<Vec3<i32>>, block: T) -> Result<(), WorldAccessError>
+    {
+        let pos = pos.into();
+        let (chunk, local) = block_to_chunk(pos);
+        let entry = match self.loaded_entry(chunk)
+        {
+            Some(entry) => entry,
+            None if self.auto_load =>
+            {
+                // Loaded in the meantime, or deferred
+                self.set_deferred(pos, block)?;
+                self.requested.lock().insert(chunk);
+                return Ok(())
+            },
+            None => return Err(WorldAccessError::ChunkNotLoaded),
+        };
+        let mut lock = entry
+            .chunk
+            // Block until acquired a read-write lock
+            .write();
+
+        unsafe
+        {
+            // SAFETY:
+            // Position is euclidian reminder'd by 32, and
+            // therefore must be in bounds
+            lock.set_unchecked(local, block);
+        }
+        self.notify_neighbors(pos);
+
+        Ok(())
+    }
+
+    /// Set the [Block] at the world coordinates `pos` if the chunk it's in is loaded
+    /// and not borrowed. This is a non-blocking operation.
+    pub fn try_set<T: Block>(&self, pos: impl Into<Vec3<i32>>, block: T) -> Result<(), WorldAccessError>
+    {
+        let pos = pos.into();
+        let (chunk, local) = block_to_chunk(pos);
+        let mut lock = self
+            .entry(chunk)
+            .ok_or(WorldAccessError::ChunkNotLoaded)?
+            .chunk
             .try_write()
+            .ok_or(WorldAccessError::WouldBlock)?;
+
+        unsafe
+        {
+            // SAFETY:
+            // Position is euclidian reminder'd by 32, and
+            // therefore must be in bounds
+            lock.set_unchecked(local, block);
+        }
+        self.notify_neighbors(pos);
+
+        Ok(())
     }
 
-    /// Loads the chunk at the given chunk position(1 unit = 32 blocks) if it's
-    /// not already loaded. This is non-blocking, but the chunk isn't loaded
-    /// instantaneously and won't be available until it's done.
-    pub fn load_chunk(&mut self, pos: Vec3<i32>)
+    /// Place each [block::Choice] at its world coordinates. Edits are grouped by chunk
+    /// so that each chunk is locked only once, and are applied in order within a
+    /// chunk. Chunks that aren't loaded or are locked are reported back, but don't
+    /// prevent the rest of the edits. This is a non-blocking operation.
+    pub fn set_many<I>(&self, edits: I) -> BatchResult
+    where
+        I: IntoIterator<Item = (Vec3<i32>, block::Choice)>
     {
-        // Don't override
-        if self.chunks.contains_key(&pos) { return }
+        let mut groups = HashMap::<Vec3<i32>, Vec<_>>::new();
 
-        // Create empty chunk
-        let chunk = Arc::new(RwLock::new(Chunk::new(pos, &self.registry)));
-        
-        // Fire-off the chunk generation
-        let gen = Arc::clone(&chunk);
-        let count = Arc::clone(&self.loading);
-        let noise = Arc::clone(&self.noise);
-        rayon::spawn(move ||
+        for (pos, block) in edits
         {
-            const CHUNK_SIZE: i32 = Chunk::SIZE as i32;
+            let (chunk, local) = block_to_chunk(pos);
 
-            // mark this chunk as loading
-            count.fetch_add(1, Ordering::Acquire);
+            groups
+                .entry(chunk)
+                .or_default()
+                .push((pos, local, block));
+        }
 
-            let mut chunk = gen.write();
+        let mut groups = groups
+            .into_iter()
+            .collect::<Vec<_>>();
+        groups.sort_unstable_by_key(|(pos, _)| (pos.x, pos.y, pos.z));
 
-            for (x, z) in (0..CHUNK_SIZE).flat_map(|x| (0..CHUNK_SIZE).map(move |z| (x, z)))
+        let mut result = BatchResult::default();
+        for (chunk, edits) in groups
+        {
+            let lock = match self.chunks.get(&chunk)
+            {
+                Some(entry) => entry.chunk.try_write().ok_or(WorldAccessError::WouldBlock),
+                None => Err(WorldAccessError::ChunkNotLoaded),
+            };
+            match lock
             {
-                let height = (noise.get([x as f64 * 0.2, z as f64 * 0.2]) * 100.0) as i32;
-                for y in 0..CHUNK_SIZE
+                Ok(mut lock) =>
                 {
-                    if y + chunk.pos().y * CHUNK_SIZE <= height
+                    result.applied += edits.len();
+
+                    for (pos, local, block) in edits
                     {
-                        unsafe
-                        {
-                            // SAFETY:
-                            // x, y, z is >= 0 and < Chunk::SIZE
-                            chunk.set_unchecked(Vec3::new(x, y, z).as_(), crate::vanilla::blocks::BlockWoodenPlanks
-                            {
-                                variant: crate::vanilla::blocks::WoodVariant::Jungle,
-                            });
-                        }
+                        block.place(&mut lock, local);
+                        self.notify_neighbors(pos);
                     }
-                } 
+                },
+                Err(error) => result.failed.push(BatchFailure
+                {
+                    chunk,
+                    error,
+                    edits: edits
+                        .into_iter()
+                        .map(|(pos, _, _)| pos)
+                        .collect(),
+                }),
             }
-            
-            drop(chunk);
+        }
 
-            // mark this chunk as no longer loading
-            count.fetch_sub(1, Ordering::Release);
-        });
+        result
+    }
 
-        // Insert in world
-        self.chunks.insert(pos, chunk);
+    /// Place each [block::Choice] at its world coordinates, all at once: every chunk
+    /// involved is locked before any edit is made, so either all the edits are made
+    /// or none are, and no one sees them half-done. Edits are applied in order. This
+    /// is a non-blocking operation.
+    pub fn set_atomic<I>(&self, edits: I) -> Result<(), WorldAccessError>
+    where
+        I: IntoIterator<Item = (Vec3<i32>, block::Choice)>
+    {
+        let edits = edits
+            .into_iter()
+            .map(|(pos, block)| (pos, block_to_chunk(pos), block))
+            .collect::<Vec<_>>();
+
+        let mut locks = HashMap::new();
+        for &(_, (chunk, _), _) in &edits
+        {
+            if locks.contains_key(&chunk)
+            {
+                continue
+            }
+            let lock = self
+                .entry(chunk)
+                .ok_or(WorldAccessError::ChunkNotLoaded)?
+                .chunk
+                .try_write()
+                .ok_or(WorldAccessError::WouldBlock)?;
+
+            locks.insert(chunk, lock);
+        }
+
+        for (_, (chunk, local), block) in &edits
+        {
+            block.place(locks.get_mut(chunk).unwrap(), *local);
+        }
+        drop(locks);
+        for (pos, _, _) in edits
+        {
+            self.notify_neighbors(pos);
+        }
+
+        Ok(())
     }
 
-    /// Get the number of chunks currently loading
-    pub fn num_chunks_loading(&self) -> usize
+    /// Set every block in the box between the world coordinates `a` and `b`(inclusive,
+    /// in any order) to a copy of `block`. Each chunk overlapping the box is locked
+    /// once, and those that aren't loaded or are locked are skipped and reported
+    /// back. This is a non-blocking operation.
+    pub fn fill_region<T: Block + Clone>(&self, a: Vec3<i32>, b: Vec3<i32>, block: T) -> FillReport
+    {
+        let min = Vec3::partial_min(a, b);
+        let max = Vec3::partial_max(a, b);
+        let (cmin, _) = block_to_chunk(min);
+        let (cmax, _) = block_to_chunk(max);
+
+        let mut report = FillReport::default();
+        for pos in positions_in_box(cmin, cmax)
+        {
+            let lock = match self.chunks.get(&pos)
+            {
+                Some(entry) => entry.chunk.try_write().ok_or(WorldAccessError::WouldBlock),
+                None => Err(WorldAccessError::ChunkNotLoaded),
+            };
+            let mut lock = match lock
+            {
+                Ok(lock) => lock,
+                Err(err) =>
+                {
+                    report.skipped.push((pos, err));
+                    continue;
+                }
+            };
+
+            // Part of the box within this chunk, in chunk-space
+            let (origin, end) = chunk_block_range(pos);
+            let lo = (Vec3::partial_max(min, origin) - origin).map(|n: i32| n as usize);
+            let hi = (Vec3::partial_min(max, end) - origin).map(|n: i32| n as usize);
+
+            report.written += lock.fill_region(lo, hi, block.clone());
+            drop(lock);
+
+            for pos in positions_in_box(origin + lo.map(|n| n as i32), origin + hi.map(|n| n as i32))
+            {
+                self.notify_neighbors(pos);
+            }
+        }
+
+        report
+    }
+
+    /// Run `edit` on every [Block] within `radius` of the world coordinates `center`,
+    /// along with its distance to it, replacing the block with whatever `edit` returns.
+    /// Each chunk overlapping the sphere is locked once, and those that aren't loaded
+    /// or are locked are skipped and reported back. This is a non-blocking operation.
+    pub fn edit_sphere<F>(&self, center: Vec3<i32>, radius: f32, mut edit: F) -> FillReport
+    where
+        F: FnMut(Vec3<i32>, f32, &dyn block::Object) -> Option<block::Choice>
+    {
+        let reach = radius.max(0.0).ceil() as i32;
+        let min = center - reach;
+        let max = center + reach;
+        let (cmin, _) = block_to_chunk(min);
+        let (cmax, _) = block_to_chunk(max);
+
+        let mut report = FillReport::default();
+        let mut replaced = Vec::new();
+        for pos in positions_in_box(cmin, cmax)
+        {
+            let lock = match self.chunks.get(&pos)
+            {
+                Some(entry) => entry.chunk.try_write().ok_or(WorldAccessError::WouldBlock),
+                None => Err(WorldAccessError::ChunkNotLoaded),
+            };
+            let mut lock = match lock
+            {
+                Ok(lock) => lock,
+                Err(err) =>
+                {
+                    report.skipped.push((pos, err));
+                    continue;
+                }
+            };
+
+            // Part of the sphere's bounding box within this chunk
+            let (origin, end) = chunk_block_range(pos);
+
+            for pos in positions_in_box(Vec3::partial_max(min, origin), Vec3::partial_min(max, end))
+            {
+                let local = (pos - origin).map(|n| n as usize);
+                let distance = (pos - center).map(|n| n as f32).magnitude();
+                if distance > radius
+                {
+                    continue;
+                }
+                // SAFETY:
+                // The box was clamped to the chunk's bounds
+                if let Some(new) = edit(pos, distance, unsafe { lock.get_unchecked(local) })
+                {
+                    new.place(&mut lock, local);
+                    replaced.push(pos);
+                }
+            }
+        }
+        report.written = replaced.len();
+        for pos in replaced
+        {
+            self.notify_neighbors(pos);
+        }
+
+        report
+    }
+
+    /// Replace the [Block] at the world coordinates `pos` with `new` only if it's
+    /// currently equal to `expected`, returning whether it was replaced. The check
+    /// and write happen under the same chunk lock, so no other access can come
+    /// in-between. This is a non-blocking operation.
+    pub fn compare_and_set<T: Block, U: Block>(&self, pos: Vec3<i32>, expected: T, new: U) -> Result<bool, WorldAccessError>
     {
-        self.loading.load(Ordering::Acquire)
+        let (chunk, local) = block_to_chunk(pos);
+        let mut lock = self
+            .entry(chunk)
+            .ok_or(WorldAccessError::ChunkNotLoaded)?
+            .chunk
+            .try_write()
+            .ok_or(WorldAccessError::WouldBlock)?;
+
+        // SAFETY:
+        // Position is euclidian reminder'd by 32, and
+        // therefore must be in bounds
+        if !unsafe { lock.get_unchecked(local) }.dyn_eq(&expected)
+        {
+            return Ok(false)
+        }
+        unsafe
+        {
+            // SAFETY:
+            // Same as above
+            lock.set_unchecked(local, new);
+        }
+        self.notify_neighbors(pos);
+
+        Ok(true)
     }
-}
\ No newline at end of file
+
+    /// Place `block` at the world coordinates `pos` as if by clicking on the `face` of
+    /// the block it's placed against, after running its [Block::on_place] hook. Returns
+    /// whether it was placed, which it isn't if the hook refuses. The hook may also
+    /// substitute another block for it(see [block::PlaceContext::substitute]), which
+    /// counts as placed. Blocks the hook sets along with it are placed all at once,
+    /// see [World::set_atomic]. This is a non-blocking operation.
+    pub fn place<T: Block + Clone>(&self, pos: Vec3<i32>, face: Direction, block: T) -> Result<bool, WorldAccessError>
+    {
+        // The hook may look around
+        match self.chunk_state(block_to_chunk(pos).0)
+        {
+            ChunkState::Absent => return Err(WorldAccessError::ChunkNotLoaded),
+            ChunkState::Generating => return Err(WorldAccessError::WouldBlock),
+            ChunkState::Loaded => (),
+        }
+        let ctx = block::PlaceContext::new(self, pos, face);
+        let block = block.on_place(&ctx);
+        let (substitute, edits) = ctx.into_edits();
+        let edit = match (substitute, block)
+        {
+            (Some(edit), _) => edit,
+            (None, Some(block)) => (pos, block::Choice::new(block)),
+            (None, None) => return Ok(false),
+        };
+
+        // Along with whatever else the hook wants to set
+        self.set_atomic(std::iter::once(edit).chain(edits))?;
+
+        Ok(true)
+    }
+
+    /// Replace the [Block] at the world coordinates `pos` with air, along with the
+    /// blocks its [Block::on_break] hook returns, all at once(see [World::set_atomic]).
+    /// This is a non-blocking operation.
+    pub fn break_block(&self, pos: Vec3<i32>) -> Result<(), WorldAccessError>
+    {
+        let ctx = block::TickContext::new(self, pos);
+        let others = self
+            .try_get(pos)?
+            .on_break(&ctx);
+
+        self.set_atomic(std::iter::once((pos, block::Choice::new(BlockAir))).chain(others))?;
+        ctx.run_deferred();
+
+        Ok(())
+    }
+
+    /// Run the [Block::random_tick] hook of the [Block] at the world coordinates `pos`,
+    /// replacing it with whatever it returns. Returns whether the block was replaced.
+    /// Picking which blocks to tick(at random) is left to the caller. This is a
+    /// non-blocking operation.
+    pub fn random_tick(&self, pos: Vec3<i32>) -> Result<bool, WorldAccessError>
+    {
+        self.run_hook(pos, |block, ctx| block.random_tick(ctx))
+    }
+
+    /// Run the [Block::on_neighbor_changed] hook of every block whose neighbor was
+    /// replaced since the last call, replacing them with whatever it returns, and
+    /// returns how many were. Blocks replaced by the hooks are only propagated to
+    /// their own neighbors on the next call.
+    ///
+    /// Replacements made by [World::set], [World::try_set], [World::set_many],
+    /// [World::set_atomic], [World::compare_and_set], [World::place], [World::break_block],
+    /// [World::fill_region], [World::edit_sphere], [World::random_tick], [World::tick]
+    /// and this are tracked. Those whose neighbors aren't loaded or are locked by then
+    /// are dropped. This is a non-blocking operation.
+    ///
+    /// A block is only updated once per neighbor however many times it was replaced
+    /// since the last call, and past [World::set_neighbor_update_cap] queued updates
+    /// the oldest are dropped, so worlds that never call this don't grow without bound.
+    pub fn process_updates(&self) -> usize
+    {
+        let updates = self.updates.lock().take();
+
+        updates
+            .into_iter()
+            .filter(|&(pos, dir)| self.run_hook(pos, |block, ctx| block.on_neighbor_changed(ctx, dir)) == Ok(true))
+            .count()
+    }
+
+    /// Get the number of updates queued for [World::process_updates]
+    pub fn neighbor_update_count(&self) -> usize
+    {
+        self.updates.lock().len()
+    }
+
+    /// Set the maximum number of updates queued for [World::process_updates], past
+    /// which the oldest are dropped. Defaults to `65536`.
+    pub fn set_neighbor_update_cap(&self, cap: usize)
+    {
+        self.updates.lock().set_cap(cap);
+    }
+
+    /// Schedule a tick for the [Block] at the world coordinates `pos`, `delay` ticks
+    /// from now(at least one). Its [Block::on_scheduled_tick] hook runs when that
+    /// tick comes due, see [World::tick]. Scheduling doesn't need the block's chunk
+    /// to be loaded.
+    pub fn schedule_tick(&self, pos: Vec3<i32>, delay: u64)
+    {
+        self.scheduled.lock().push(pos, delay.max(1));
+    }
+
+    /// Advance this world by one tick, running the [Block::on_scheduled_tick] hook of
+    /// every block whose scheduled tick is due, replacing them with whatever it returns,
+    /// and returns how many were. Ticks for blocks whose chunk is locked are pushed
+    /// back to the next tick, and those whose chunk isn't loaded are dropped. This is
+    /// a non-blocking operation.
+    ///
+    /// At most `max_updates` hooks are run, so that ie. a redstone loop can't stall
+    /// a tick. The rest are deferred to the next tick, ahead of the ticks coming due
+    /// then and still in the order they were scheduled, see [World::pending_update_count].
+    pub fn tick(&self, max_updates: usize) -> usize
+    {
+        let due = self.scheduled.lock().advance(max_updates);
+
+        due
+            .into_iter()
+            .filter(|&pos| match self.run_hook(pos, |block, ctx| block.on_scheduled_tick(ctx))
+            {
+                Ok(replaced) => replaced,
+                Err(WorldAccessError::WouldBlock) =>
+                {
+                    self.schedule_tick(pos, 1);
+                    false
+                },
+                Err(_) => false,
+            })
+            .count()
+    }
+
+    /// Get the number of ticks this world was advanced by, see [World::tick]
+    pub fn current_tick(&self) -> u64
+    {
+        self.scheduled.lock().now()
+    }
+
+    /// Get the number of ticks scheduled by [World::schedule_tick] which haven't
+    /// come due yet
+    pub fn scheduled_tick_count(&self) -> usize
+    {
+        self.scheduled.lock().len()
+    }
+
+    /// Get the number of ticks scheduled by [World::schedule_tick] which came due,
+    /// but were deferred by [World::tick]'s budget. Those are run first on the next
+    /// tick, so a count that keeps growing means the world can't keep up.
+    pub fn pending_update_count(&self) -> usize
+    {
+        self.scheduled.lock().overdue()
+    }
+
+    /// Replace the [Block] at the world coordinates `pos` with what `hook` returns
+    /// for it, if anything, and return whether it was replaced
+    fn run_hook<F>(&self, pos: Vec3<i32>, hook: F) -> Result<bool, WorldAccessError>
+    where
+        F: FnOnce(&dyn block::Object, &block::TickContext<'_>) -> Option<block::Choice>
+    {
+        let (chunk, local) = block_to_chunk(pos);
+        let entry = self
+            .entry(chunk)
+            .ok_or(WorldAccessError::ChunkNotLoaded)?;
+
+        if entry.generating.load(Ordering::Acquire)
+        {
+            return Err(WorldAccessError::WouldBlock)
+        }
+        // Upgradable so that the hook can still read its neighbors in this chunk
+        let lock = entry.chunk
+            .try_upgradable_read()
+            .ok_or(WorldAccessError::WouldBlock)?;
+
+        let ctx = block::TickContext::new(self, pos);
+        // SAFETY:
+        // Position is euclidian reminder'd by 32, and
+        // therefore must be in bounds
+        let new = hook(unsafe { lock.get_unchecked(local) }, &ctx);
+        let replaced = match new
+        {
+            Some(new) =>
+            {
+                new.place(&mut RwLockUpgradableReadGuard::upgrade(lock), local);
+                self.notify_neighbors(pos);
+                true
+            },
+            None =>
+            {
+                drop(lock);
+                false
+            },
+        };
+        ctx.run_deferred();
+
+        Ok(replaced)
+    }
+
+    /// Queue an update for each block adjacent to the world coordinates `pos`,
+    /// whose block was just replaced, see [World::process_updates]
+    fn notify_neighbors(&self, pos: Vec3<i32>)
+    {
+        let mut updates = self.updates.lock();
+
+        for &dir in Direction::ALL.iter()
+        {
+            updates.push(pos + dir.offset(), dir.opposite());
+        }
+    }
+
+    /// Get the chunk at the given chunk position(1 unit = 32 blocks) if it's
+    /// loaded and not already being borrowed mutably.
+    pub fn get_chunk(&self, pos: impl Into<Vec3<i32>>) -> Option<impl Deref<Target = Chunk> + '_>
+    {
+        self.chunks
+            .get(&pos.into())?
+            .chunk
+            .try_read()
+    }
+
+    /// Get the chunk at the given chunk position(1 unit = 32 blocks) if it's
+    /// loaded and not already being borrowed (im)mutably.
+    pub fn get_chunk_mut(&self, pos: impl Into<Vec3<i32>>) -> Option<impl DerefMut<Target = Chunk> + '_>
+    {
+        self.chunks
+            .get(&pos.into())?
+            .chunk
+            .try_write()
+    }
+
+    /// Get the state of the chunk at the given chunk position(1 unit = 32 blocks).
+    /// Unlike [World::get_chunk], this doesn't care whether the chunk is borrowed,
+    /// and never blocks.
+    pub fn chunk_state(&self, pos: Vec3<i32>) -> ChunkState
+    {
+        match self.chunks.get(&pos)
+        {
+            None => ChunkState::Absent,
+            Some(entry) if entry.generating.load(Ordering::Acquire) => ChunkState::Generating,
+            Some(_) => ChunkState::Loaded,
+        }
+    }
+
+    /// Whether the chunk at the given chunk position(1 unit = 32 blocks) is loaded
+    /// and done generating, see [World::chunk_state]
+    pub fn is_chunk_loaded(&self, pos: Vec3<i32>) -> bool
+    {
+        self.chunk_state(pos) == ChunkState::Loaded
+    }
+
+    /// Whether the chunk containing the world coordinates `pos` is loaded and done
+    /// generating, see [World::chunk_state]
+    pub fn contains_block(&self, pos: Vec3<i32>) -> bool
+    {
+        self.is_chunk_loaded(block_to_chunk(pos).0)
+    }
+
+    /// Get the chunk positions(1 unit = 32 blocks) of all the chunks in this world,
+    /// including those still loading, sorted. This never blocks.
+    pub fn chunk_positions(&self) -> Vec<Vec3<i32>>
+    {
+        let mut positions = self.chunks
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+        positions.sort_unstable_by_key(|pos| (pos.x, pos.y, pos.z));
+        positions
+    }
+
+    /// Iterate over the loaded chunks of this world along with their chunk position
+    /// (1 unit = 32 blocks), in the same order as [World::chunk_positions]. Chunks
+    /// that are still loading or are borrowed mutably are skipped, so this never
+    /// blocks.
+    pub fn chunks(&self) -> impl Iterator<Item = (Vec3<i32>, impl Deref<Target = Chunk> + '_)> + '_
+    {
+        self.chunk_positions()
+            .into_iter()
+            .filter_map(move |pos|
+            {
+                let entry = &self.chunks[&pos];
+
+                if entry.generating.load(Ordering::Acquire)
+                {
+                    return None
+                }
+                Some((pos, entry.chunk.try_read()?))
+            })
+    }
+
+    /// Get a shared pointer to the chunk at the given chunk position(1 unit = 32 blocks)
+    /// if it's loaded, regardless of whether it's currently borrowed. This lets the
+    /// chunk be handed off(ie. to another thread) without borrowing this `World`.
+    ///
+    /// Note that the chunk isn't freed for as long as the returned `Arc` is held,
+    /// even if it's unloaded from this `World` in the meantime.
+    pub fn get_chunk_arc(&self, pos: Vec3<i32>) -> Option<Arc<RwLock<Chunk>>>
+    {
+        self.chunks
+            .get(&pos)
+            .map(|entry| Arc::clone(&entry.chunk))
+    }
+
+    /// Loads the chunk at the given chunk position(1 unit = 32 blocks) if it's
+    /// not already loaded. This is non-blocking, but the chunk isn't loaded
+    /// instantaneously and won't be available until it's done.
+    ///
+    /// Chunks are loaded by this world's worker threads, those closest to the
+    /// [load anchor](World::set_load_anchor) first.
+    pub fn load_chunk(&mut self, pos: Vec3<i32>)
+    {
+        // Don't override
+        if self.chunks.contains_key(&pos) { return }
+
+        // Create empty chunk
+        let chunk = Arc::new(RwLock::new(Chunk::new(pos, &self.registry)));
+        
+        // Mark this chunk as loading before it's even spawned, so that there's
+        // no window where it's in the world but not accounted for
+        let generating = Arc::new(AtomicBool::new(true));
+        let guard = LoadingGuard::new(&self.loading, &generating);
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        // Queue the chunk generation
+        let gen = Arc::clone(&chunk);
+        let cancel = Arc::clone(&cancelled);
+        let generator = self.generator();
+        let registry = Arc::clone(&self.registry);
+        let subscribers = Arc::clone(&self.subscribers);
+        let pending = Arc::clone(&self.pending);
+        let path = self.save_dir
+            .as_ref()
+            .map(|dir| save::chunk_path(dir, pos));
+        self.queue.lock().push(pos, move ||
+        {
+            // Moved into this task, so that the chunk is marked as no longer loading
+            // once it's done(or panics, or is cancelled). Declared before `gen` so
+            // that the task's reference to the chunk is released first.
+            let guard = guard;
+            let gen = gen;
+
+            // Unloaded between being popped off the queue and starting
+            if cancel.load(Ordering::Acquire)
+            {
+                return
+            }
+            let mut chunk = gen.write();
+
+            // Missing or corrupt saves fall back to generation
+            let saved = path
+                .and_then(|path| std::fs::read(path).ok())
+                .and_then(|bytes| Chunk::from_bytes(pos, &registry, &bytes));
+            let event = match saved
+            {
+                Some(saved) =>
+                {
+                    *chunk = saved;
+                    ChunkEvent::Loaded(pos)
+                },
+                None =>
+                {
+                    // Bails early once unloaded, to be discarded below
+                    generator.generate_cancellable(pos, &mut chunk, &cancel);
+                    ChunkEvent::Generated(pos)
+                },
+            };
+
+            // Apply the edits made before the chunk was loaded. It's marked as done
+            // generating under the same lock, so that no more edits can be deferred
+            // to it once drained. Chunks unloaded in the meantime(also under that
+            // lock) are discarded, leaving their edits for when they're next loaded.
+            {
+                let mut pending = pending.lock();
+
+                if cancel.load(Ordering::Acquire)
+                {
+                    return
+                }
+                for (local, block) in pending.drain(pos)
+                {
+                    chunk.set_boxed(local, block);
+                }
+                guard.generating.store(false, Ordering::Release);
+            }
+
+            // Release the lock so that subscribers can access the chunk, and notify
+            // them before the chunk's marked as loaded
+            drop(chunk);
+            subscribers.send(event);
+        });
+
+        // Each spawned task runs whichever job is the most urgent by then
+        let queue = Arc::clone(&self.queue);
+        self.pool.spawn(move ||
+        {
+            // Jobs may have been cancelled, leaving nothing to run
+            let job = queue.lock().pop();
+
+            if let Some(job) = job
+            {
+                job();
+            }
+        });
+
+        // Insert in world
+        let last_access = AtomicU64::new(self.tick.fetch_add(1, Ordering::Relaxed));
+
+        self.chunks.insert(pos, Entry { chunk, generating, cancelled, last_access });
+    }
+
+    /// Set the chunk position(1 unit = 32 blocks) around which chunks are loaded
+    /// first, ie. the player's. Chunks already queued are reprioritized. Defaults
+    /// to the origin.
+    pub fn set_load_anchor(&self, anchor: Vec3<i32>)
+    {
+        self.queue.lock().set_anchor(anchor);
+    }
+
+    /// Set whether [World::set] on a chunk that isn't loaded defers the edit until it
+    /// is(see [World::set_deferred]) rather than failing, so that edits at the edge of
+    /// the loaded region(ie. by commands) aren't lost. Those chunks are then loaded by
+    /// [World::load_requested]. Defaults to `false`.
+    ///
+    /// Loading stays on this world's worker threads, so `set` remains cheap. Still,
+    /// every chunk edited this way is generated once requested, which can be far more
+    /// work than the edit itself for edits scattered across many chunks.
+    pub fn set_auto_load(&mut self, auto_load: bool)
+    {
+        self.auto_load = auto_load;
+    }
+
+    /// Load the chunks edited by [World::set] while they weren't loaded, with
+    /// [auto-loading](World::set_auto_load) on. Returns how many were queued, see
+    /// [World::load_chunk].
+    pub fn load_requested(&mut self) -> usize
+    {
+        let mut queued = 0;
+
+        for pos in std::mem::take(self.requested.get_mut())
+        {
+            if !self.chunks.contains_key(&pos)
+            {
+                self.load_chunk(pos);
+                queued += 1;
+            }
+        }
+        queued
+    }
+
+    /// Set the number of worker threads loading chunks, which defaults to the number
+    /// of CPUs. Chunks already queued may still be loaded by the previous threads,
+    /// which exit once they're done.
+    pub fn set_worker_threads(&mut self, threads: usize)
+    {
+        self.pool = worker_pool(Some(threads));
+    }
+
+    /// Loads every chunk in the cube of chunks spanning `radius` chunks on every side
+    /// of the chunk position `center`(1 unit = 32 blocks), skipping those already
+    /// loaded. A `radius` of `0` only loads `center`, and negative radii load nothing.
+    pub fn load_chunks_in_radius(&mut self, center: Vec3<i32>, radius: i32)
+    {
+        for pos in positions_in_box(center - radius, center + radius)
+        {
+            self.load_chunk(pos);
+        }
+    }
+
+    /// Unloads every chunk outside the cube of [World::load_chunks_in_radius] for the
+    /// same `center` and `radius`, returning them. Chunks that can't be unloaded are
+    /// skipped, see [World::unload_chunk].
+    pub fn unload_chunks_outside_radius(&mut self, center: Vec3<i32>, radius: i32) -> Vec<Chunk>
+    {
+        self.unload_where(|pos| (pos - center).map(i32::abs).reduce_max() > radius)
+    }
+
+    /// Loads every chunk within `radius` chunks of the chunk position `center`(1 unit
+    /// = 32 blocks), nearest first, if they're not already loaded. Chunks outside
+    /// that range are left as-is, but reported as evictable.
+    ///
+    /// The range is a ball, ie. chunks whose position is at a euclidean distance
+    /// of at most `radius` from `center`.
+    pub fn ensure_loaded_around(&mut self, center: Vec3<i32>, radius: u32) -> StreamingReport
+    {
+        let missing = chunks_in_sphere(center, radius as i32)
+            .filter(|pos| !self.chunks.contains_key(pos))
+            .collect::<Vec<_>>();
+
+        let queued = missing.len();
+        for pos in missing
+        {
+            self.load_chunk(pos);
+        }
+
+        let evictable = self
+            .chunk_positions()
+            .into_iter()
+            .filter(|&pos| !in_range(center, radius, pos))
+            .collect();
+
+        StreamingReport { queued, evictable }
+    }
+
+    /// Unloads the chunks out of the range of [World::ensure_loaded_around] for the
+    /// same `center` and `radius`, returning them. Chunks that can't be unloaded are
+    /// skipped, see [World::unload_chunk].
+    pub fn evict_out_of_range(&mut self, center: Vec3<i32>, radius: u32) -> Vec<Chunk>
+    {
+        self.unload_where(|pos| !in_range(center, radius, pos))
+    }
+
+    /// Unloads the chunk at the given chunk position(1 unit = 32 blocks), handing
+    /// it back so it can be saved before being dropped. Returns `Ok(None)` if no
+    /// chunk was loaded there, or if the chunk is still shared via an `Arc` from
+    /// [World::get_chunk_arc], in which case it's removed from this `World` but
+    /// only freed once that `Arc` is dropped.
+    ///
+    /// Chunks which are still generating are cancelled and removed from this
+    /// `World`, returning `Ok(None)`. Those that haven't started are never loaded,
+    /// and those that have stop as soon as their generator allows(see
+    /// [ChunkGenerator::generate_cancellable]) and are discarded. Neither is ever
+    /// announced as loaded nor unloaded, and edits deferred to them are kept.
+    ///
+    /// Chunks which are currently borrowed are left as-is and an error is returned.
+    pub fn unload_chunk(&mut self, pos: Vec3<i32>) -> Result<Option<Chunk>, UnloadError>
+    {
+        let entry = match self.chunks.get(&pos)
+        {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+
+        if entry.generating.load(Ordering::Acquire)
+        {
+            // Checked again under the same lock the generation task finishes with,
+            // see `World::load_chunk`
+            let _pending = self.pending.lock();
+
+            if entry.generating.load(Ordering::Acquire)
+            {
+                entry.cancelled.store(true, Ordering::Release);
+                self.queue.lock().remove(pos);
+                self.chunks.remove(&pos);
+
+                return Ok(None)
+            }
+        }
+        // `&mut self` means no borrows from this `World` are alive, but there
+        // could still be some through an external `Arc`
+        if entry.chunk.is_locked()
+        {
+            return Err(UnloadError::Locked)
+        }
+
+        let entry = self.chunks.remove(&pos).unwrap();
+
+        self.subscribers.send(ChunkEvent::Unloaded(pos));
+
+        Ok(Arc::try_unwrap(entry.chunk)
+            .ok()
+            .map(RwLock::into_inner))
+    }
+
+    /// Unloads all the chunks whose chunk position(1 unit = 32 blocks) matches
+    /// the predicate, returning those that were unloaded. Chunks that can't be
+    /// unloaded(see [World::unload_chunk]) are skipped.
+    pub fn unload_where(&mut self, mut pred: impl FnMut(Vec3<i32>) -> bool) -> Vec<Chunk>
+    {
+        let matching = self.chunks
+            .keys()
+            .copied()
+            .filter(|&pos| pred(pos))
+            .collect::<Vec<_>>();
+
+        matching
+            .into_iter()
+            .filter_map(|pos| self.unload_chunk(pos).ok().flatten())
+            .collect()
+    }
+
+    /// Unloads the least recently accessed chunks until at most `max_chunks` are left,
+    /// returning those that were unloaded. Chunks are accessed by [World::get],
+    /// [World::set] and their variants.
+    ///
+    /// Chunks which are still generating, currently borrowed, or shared via an `Arc`
+    /// from [World::get_chunk_arc] are never unloaded, so more than `max_chunks` may
+    /// be left.
+    pub fn trim(&mut self, max_chunks: usize) -> Vec<Chunk>
+    {
+        let mut excess = self.chunks.len().saturating_sub(max_chunks);
+        let mut unloaded = Vec::new();
+
+        let mut lru = self.chunks
+            .iter()
+            .map(|(&pos, entry)| (entry.last_access.load(Ordering::Relaxed), pos))
+            .collect::<Vec<_>>();
+        lru.sort_unstable_by_key(|(tick, _)| *tick);
+
+        for (_, pos) in lru
+        {
+            if excess == 0 { break }
+
+            let entry = &self.chunks[&pos];
+            // Unlike `World::unload_chunk`, chunks still shared are kept
+            if entry.generating.load(Ordering::Acquire)
+                || entry.chunk.is_locked()
+                || Arc::strong_count(&entry.chunk) > 1
+            {
+                continue;
+            }
+            if let Ok(Some(chunk)) = self.unload_chunk(pos)
+            {
+                unloaded.push(chunk);
+                excess -= 1;
+            }
+        }
+        unloaded
+    }
+
+    /// Get the entry of the chunk at the given chunk position, marking it as
+    /// accessed for [World::trim]
+    fn entry(&self, pos: Vec3<i32>) -> Option<&Entry>
+    {
+        let entry = self.chunks.get(&pos)?;
+
+        entry.last_access.store(self.tick.fetch_add(1, Ordering::Relaxed), Ordering::Relaxed);
+        Some(entry)
+    }
+
+    /// [World::entry], but blocks until the chunk is done generating
+    fn loaded_entry(&self, pos: Vec3<i32>) -> Option<&Entry>
+    {
+        let entry = self.entry(pos)?;
+        let mut lock = self.loading.lock.lock();
+
+        while entry.generating.load(Ordering::Acquire)
+        {
+            self.loading.done.wait(&mut lock);
+        }
+        Some(entry)
+    }
+
+    /// [World::load_chunk], returning a future which resolves once the chunk is done
+    /// loading(or was unloaded before it could be), for use with async runtimes. The
+    /// future doesn't borrow the world, and resolves right away if the chunk was
+    /// already loaded.
+    #[cfg(feature = "async")]
+    pub fn load_chunk_async(&mut self, pos: Vec3<i32>) -> impl std::future::Future<Output = ()>
+    {
+        self.load_chunk(pos);
+
+        LoadFuture
+        {
+            loading: Arc::clone(&self.loading),
+            generating: self.chunks
+                .get(&pos)
+                .map(|entry| Arc::clone(&entry.generating)),
+        }
+    }
+
+    /// Get a receiver for the [ChunkEvent]s that happen in this world from now on.
+    /// Every receiver gets every event, and dropping it unsubscribes.
+    pub fn subscribe_chunk_events(&self) -> Receiver<ChunkEvent>
+    {
+        let (tx, rx) = mpsc::channel();
+
+        self.subscribers.0.lock().push(tx);
+        rx
+    }
+
+    /// Get the number of chunks currently loading
+    pub fn num_chunks_loading(&self) -> usize
+    {
+        self.loading.count.load(Ordering::Acquire)
+    }
+
+    /// Block the current thread until no chunks are loading. Returns immediately if
+    /// none are.
+    pub fn wait_for_loads(&self)
+    {
+        let mut lock = self.loading.lock.lock();
+
+        while self.num_chunks_loading() != 0
+        {
+            self.loading.done.wait(&mut lock);
+        }
+    }
+
+    /// [World::wait_for_loads], but gives up after `timeout`. Returns whether all
+    /// chunks are done loading.
+    pub fn wait_for_loads_timeout(&self, timeout: Duration) -> bool
+    {
+        let deadline = Instant::now() + timeout;
+        let mut lock = self.loading.lock.lock();
+
+        while self.num_chunks_loading() != 0
+        {
+            if self.loading.done.wait_until(&mut lock, deadline).timed_out()
+            {
+                return self.num_chunks_loading() == 0
+            }
+        }
+        true
+    }
+
+    /// Are any chunks still loading? This includes chunks that were inserted by
+    /// [World::load_chunk] but whose generation hasn't started yet.
+    pub fn has_pending_loads(&self) -> bool
+    {
+        self.num_chunks_loading() != 0
+    }
+}
+
+/// A [Block] of type `T` borrowed from a [World] along with its chunk, as returned
+/// by [World::get_as]
+struct TypedRef<'a, T: Block>
+{
+    /// Borrows from the chunk, so is declared(and dropped) first
+    block: block::Ref<'a, T>,
+    /// Keeps the chunk borrowed
+    _lock: RwLockReadGuard<'a, Chunk>,
+}
+
+/// A [Block] of type `T` borrowed mutably from a [World] along with its chunk, as
+/// returned by [World::get_mut_as]
+struct TypedRefMut<'a, T: Block>
+{
+    /// Borrows from the chunk, and re-packs into it when dropped, so is declared
+    /// (and dropped) first
+    block: block::RefMut<'a, T>,
+    /// Keeps the chunk borrowed
+    _lock: RwLockWriteGuard<'a, Chunk>,
+}
+
+impl<'a, T: Block> Deref for TypedRef<'a, T>
+{
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target
+    {
+        &self.block
+    }
+}
+
+impl<'a, T: Block> Deref for TypedRefMut<'a, T>
+{
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target
+    {
+        &self.block
+    }
+}
+
+impl<'a, T: Block> DerefMut for TypedRefMut<'a, T>
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target
+    {
+        &mut self.block
+    }
+}
+
+/// Reads blocks from a [World], keeping the last chunk accessed borrowed. See
+/// [World::cached_reader]
+pub struct CachedReader<'a>
+{
+    /// World being read from
+    world: &'a World,
+    /// Position and borrow of the last chunk accessed, if it was loaded
+    cached: Option<(Vec3<i32>, RwLockReadGuard<'a, Chunk>)>,
+}
+
+impl<'a> CachedReader<'a>
+{
+    /// [World::get], skipping the chunk lookup and lock if `pos` is in the same
+    /// chunk as the last block read. Otherwise, the last chunk is released and
+    /// this blocks until the new one can be borrowed immutably.
+    pub fn get(&mut self, pos: impl Into<Vec3<i32>>) -> Option<&dyn block::Object>
+    {
+        let (chunk, local) = block_to_chunk(pos.into());
+
+        if !matches!(self.cached, Some((cached, _)) if cached == chunk)
+        {
+            // Release the last chunk first, so that at most one is ever borrowed
+            self.cached = None;
+            self.cached = Some((chunk, self.world.loaded_entry(chunk)?.chunk.read()));
+        }
+        let (_, lock) = self.cached.as_ref()?;
+
+        // SAFETY:
+        // Position is euclidian reminder'd by 32, and
+        // therefore must be in bounds
+        Some(unsafe { lock.get_unchecked(local) })
+    }
+
+    /// Get the chunk position(1 unit = 32 blocks) of the chunk currently borrowed,
+    /// if any
+    pub fn cached_chunk(&self) -> Option<Vec3<i32>>
+    {
+        self.cached.as_ref().map(|(pos, _)| *pos)
+    }
+}
+
+/// Senders to every receiver of a [World]'s [ChunkEvent]s
+#[derive(Default)]
+struct Subscribers(Mutex<Vec<Sender<ChunkEvent>>>);
+
+impl Subscribers
+{
+    /// Send the event to every subscriber, forgetting those that unsubscribed
+    fn send(&self, event: ChunkEvent)
+    {
+        self.0
+            .lock()
+            .retain(|tx| tx.send(event).is_ok());
+    }
+}
+
+/// Number of chunks loading in a [World], which can be waited on
+#[derive(Default)]
+struct Loading
+{
+    /// Number of chunks currently loading
+    count: AtomicUsize,
+    /// Held while checking `count`(or a chunk's generating flag) before waiting
+    /// on `done`, so that the notification can't be missed
+    lock: Mutex<()>,
+    /// Notified whenever a chunk is done loading
+    done: Condvar,
+    /// Woken whenever a chunk is done loading, see [World::load_chunk_async]
+    wakers: Mutex<Vec<Waker>>,
+}
+
+/// Counts a chunk as loading for as long as it's alive. Decrementing on drop
+/// means a panicking generation task can't leave the counter stuck, nor threads
+/// waiting on it hanging.
+struct LoadingGuard
+{
+    /// The `World`'s number of chunks loading
+    loading: Arc<Loading>,
+    /// The chunk's generating flag
+    generating: Arc<AtomicBool>,
+}
+
+impl LoadingGuard
+{
+    fn new(loading: &Arc<Loading>, generating: &Arc<AtomicBool>) -> Self
+    {
+        loading.count.fetch_add(1, Ordering::Acquire);
+
+        Self
+        {
+            loading: Arc::clone(loading),
+            generating: Arc::clone(generating),
+        }
+    }
+}
+
+impl Drop for LoadingGuard
+{
+    fn drop(&mut self)
+    {
+        self.generating.store(false, Ordering::Release);
+        self.loading.count.fetch_sub(1, Ordering::AcqRel);
+
+        // Waiters either saw the chunk loading(or the count before it hit zero)
+        // and are waiting, or will see it's done once they get the lock
+        let _lock = self.loading.lock.lock();
+
+        self.loading.done.notify_all();
+
+        // Futures either saw the chunk loading and registered their waker, or will
+        // see it's done once they get the lock
+        for waker in self.loading.wakers.lock().drain(..)
+        {
+            waker.wake();
+        }
+    }
+}
+
+/// Future returned by [World::load_chunk_async]
+#[cfg(feature = "async")]
+struct LoadFuture
+{
+    /// The `World`'s number of chunks loading, whose wakers are woken
+    loading: Arc<Loading>,
+    /// The chunk's generating flag, or `None` if it isn't in the world
+    generating: Option<Arc<AtomicBool>>,
+}
+
+#[cfg(feature = "async")]
+impl std::future::Future for LoadFuture
+{
+    type Output = ();
+
+    fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<()>
+    {
+        let generating = match &self.generating
+        {
+            Some(generating) => generating,
+            None => return std::task::Poll::Ready(()),
+        };
+        // Checked under the lock, so that the chunk can't be done between the check
+        // and registering the waker
+        let mut wakers = self.loading.wakers.lock();
+
+        if !generating.load(Ordering::Acquire)
+        {
+            return std::task::Poll::Ready(())
+        }
+        if !wakers.iter().any(|waker| waker.will_wake(cx.waker()))
+        {
+            wakers.push(cx.waker().clone());
+        }
+        std::task::Poll::Pending
+    }
+}
+
+/// Create a pool of worker threads for loading chunks, with as many threads as
+/// there are CPUs by default
+fn worker_pool(threads: Option<usize>) -> rayon::ThreadPool
+{
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads.unwrap_or(0))
+        .thread_name(|i| format!("chunk-worker-{}", i))
+        .build()
+        .expect("failed to spawn chunk worker threads")
+}
+
+/// Get the chunk positions(1 unit = 32 blocks) within a euclidean distance of
+/// `radius` chunks from the chunk position `center`, nearest first, starting with
+/// `center` itself. Negative radii yield nothing.
+pub fn chunks_in_sphere(center: Vec3<i32>, radius: i32) -> impl Iterator<Item = Vec3<i32>>
+{
+    let mut chunks = positions_in_sphere(center, radius as f32).collect::<Vec<_>>();
+
+    // Stable, so ties stay in the order of `positions_in_box`
+    chunks.sort_by_key(|&pos| (pos - center).map(|n| n as i64).magnitude_squared());
+    chunks.into_iter()
+}
+
+/// Whether the chunk position `pos` is within `radius` chunks of `center`, as
+/// used by [World::ensure_loaded_around]
+fn in_range(center: Vec3<i32>, radius: u32, pos: Vec3<i32>) -> bool
+{
+    (pos - center).map(|n| n as i64).magnitude_squared() <= (radius as i64).pow(2)
+}